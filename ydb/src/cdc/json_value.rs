@@ -0,0 +1,194 @@
+//! Changefeed JSON values to [`Value`]s of the column types.
+//!
+//! Integers and floats are JSON numbers, floats may be also `"nan"`, `"inf"` and `"-inf"`.
+//! `String` and `Yson` are base64 strings, `Decimal` is a decimal string. Dates and times are
+//! RFC 3339 strings, numbers are accepted too in the units of the wire format.
+//! `Interval` is a number of microseconds. `Json` and `JsonDocument` are embedded as JSON.
+
+use std::time::{Duration, SystemTime};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use serde_json::Value as JsonValue;
+
+use crate::types::{
+    SECONDS_PER_DAY, SignedInterval, ValueOptional, YdbDecimal, signed_days_to_system_time,
+    signed_micros_to_system_time, signed_secs_to_system_time,
+};
+use crate::{Value, YdbError, YdbResult};
+
+pub(super) fn from_json(example: &Value, json: &JsonValue) -> YdbResult<Value> {
+    let value = match example {
+        Value::Optional(optional) => {
+            let value = match json {
+                JsonValue::Null => None,
+                json => Some(from_json(&optional.t, json)?),
+            };
+            return Ok(Value::Optional(Box::new(ValueOptional {
+                t: optional.t.clone(),
+                value,
+            })));
+        }
+        Value::Null => json.is_null().then_some(Value::Null),
+        Value::Bool(_) => json.as_bool().map(Value::Bool),
+        Value::Int8(_) => int(json).map(Value::Int8),
+        Value::Uint8(_) => int(json).map(Value::Uint8),
+        Value::Int16(_) => int(json).map(Value::Int16),
+        Value::Uint16(_) => int(json).map(Value::Uint16),
+        Value::Int32(_) => int(json).map(Value::Int32),
+        Value::Uint32(_) => int(json).map(Value::Uint32),
+        Value::Int64(_) => json.as_i64().map(Value::Int64),
+        Value::Uint64(_) => json.as_u64().map(Value::Uint64),
+        Value::Float(_) => float(json).map(|f| Value::Float(f as f32)),
+        Value::Double(_) => float(json).map(Value::Double),
+        Value::Date(_) => time(json, |days| {
+            let days = u64::try_from(days).ok()?;
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(days * SECONDS_PER_DAY))
+        })
+        .map(Value::Date),
+        Value::DateTime(_) => time(json, |secs| {
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(u64::try_from(secs).ok()?))
+        })
+        .map(Value::DateTime),
+        Value::Timestamp(_) => time(json, |micros| {
+            Some(SystemTime::UNIX_EPOCH + Duration::from_micros(u64::try_from(micros).ok()?))
+        })
+        .map(Value::Timestamp),
+        Value::Date32(_) => time(json, |days| {
+            Some(signed_days_to_system_time(i32::try_from(days).ok()?))
+        })
+        .map(Value::Date32),
+        Value::Datetime64(_) => {
+            time(json, |secs| Some(signed_secs_to_system_time(secs))).map(Value::Datetime64)
+        }
+        Value::Timestamp64(_) => {
+            time(json, |micros| Some(signed_micros_to_system_time(micros))).map(Value::Timestamp64)
+        }
+        Value::IntervalMicros(_) => json
+            .as_i64()
+            .map(|micros| Value::IntervalMicros(SignedInterval::from_micros(micros))),
+        Value::Interval64(_) => json
+            .as_i64()
+            .map(|micros| Value::Interval64(SignedInterval::from_micros(micros))),
+        Value::Bytes(_) => base64(json).map(|bytes| Value::Bytes(bytes.into())),
+        Value::Yson(_) => base64(json).map(|bytes| Value::Yson(bytes.into())),
+        Value::Text(_) => json.as_str().map(|s| Value::Text(s.to_string())),
+        Value::Json(_) => Some(Value::Json(json.to_string())),
+        Value::JsonDocument(_) => Some(Value::JsonDocument(json.to_string())),
+        Value::Uuid(_) => json
+            .as_str()
+            .and_then(|s| uuid::Uuid::parse_str(s).ok())
+            .map(Value::Uuid),
+        Value::Decimal(example) => json
+            .as_str()
+            .and_then(|s| s.parse::<decimal_rs::Decimal>().ok())
+            .map(|decimal| YdbDecimal::try_new(decimal, example.precision(), example.scale()))
+            .transpose()?
+            .map(Value::Decimal),
+        _ => {
+            return Err(YdbError::Convert(format!(
+                "{} columns are unsupported",
+                example.kind_static()
+            )));
+        }
+    };
+    value.ok_or_else(|| {
+        YdbError::Convert(format!("can't convert {json} to {}", example.kind_static()))
+    })
+}
+
+fn int<T: TryFrom<i64>>(json: &JsonValue) -> Option<T> {
+    json.as_i64()?.try_into().ok()
+}
+
+fn float(json: &JsonValue) -> Option<f64> {
+    match json.as_str() {
+        Some("nan") => Some(f64::NAN),
+        Some("inf") => Some(f64::INFINITY),
+        Some("-inf") => Some(f64::NEG_INFINITY),
+        Some(_) => None,
+        None => json.as_f64(),
+    }
+}
+
+fn time(
+    json: &JsonValue,
+    from_units: impl FnOnce(i64) -> Option<SystemTime>,
+) -> Option<SystemTime> {
+    match json {
+        JsonValue::String(s) => chrono::DateTime::parse_from_rfc3339(s)
+            .ok()
+            .map(SystemTime::from),
+        json => from_units(json.as_i64()?),
+    }
+}
+
+fn base64(json: &JsonValue) -> Option<Vec<u8>> {
+    BASE64.decode(json.as_str()?).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn primitive_values() {
+        let cases = [
+            (Value::Int8(0), "-5", Value::Int8(-5)),
+            (
+                Value::Uint64(0),
+                "18446744073709551615",
+                Value::Uint64(u64::MAX),
+            ),
+            (Value::Double(0.0), r#""inf""#, Value::Double(f64::INFINITY)),
+            (
+                Value::Bytes(Vec::new().into()),
+                r#""AAE=""#,
+                Value::Bytes(vec![0, 1].into()),
+            ),
+            (
+                Value::Json(String::new()),
+                r#"{"a":1}"#,
+                Value::Json(r#"{"a":1}"#.to_string()),
+            ),
+            (
+                Value::Date(SystemTime::UNIX_EPOCH),
+                r#""1970-01-02T00:00:00.000000Z""#,
+                Value::Date(SystemTime::UNIX_EPOCH + Duration::from_secs(SECONDS_PER_DAY)),
+            ),
+            (
+                Value::Timestamp64(SystemTime::UNIX_EPOCH),
+                "-1",
+                Value::Timestamp64(SystemTime::UNIX_EPOCH - Duration::from_micros(1)),
+            ),
+            (
+                Value::IntervalMicros(SignedInterval::default()),
+                "-3",
+                Value::IntervalMicros(SignedInterval::from_micros(-3)),
+            ),
+        ];
+        for (example, json, expected) in cases {
+            let json: JsonValue = serde_json::from_str(json).unwrap();
+            assert_eq!(from_json(&example, &json).unwrap(), expected, "{json}");
+        }
+    }
+
+    #[test]
+    fn decimal_keeps_column_precision() {
+        let example = Value::Decimal(YdbDecimal::new_unchecked(decimal_rs::Decimal::ZERO, 22, 9));
+        let Value::Decimal(decimal) = from_json(&example, &JsonValue::from("1.5")).unwrap() else {
+            panic!("decimal expected");
+        };
+        assert_eq!((decimal.precision(), decimal.scale()), (22, 9));
+        assert_eq!(
+            *decimal.decimal(),
+            "1.5".parse::<decimal_rs::Decimal>().unwrap()
+        );
+    }
+
+    #[test]
+    fn out_of_range_is_rejected() {
+        assert!(from_json(&Value::Uint8(0), &JsonValue::from(256)).is_err());
+        assert!(from_json(&Value::Text(String::new()), &JsonValue::from(1)).is_err());
+    }
+}
@@ -0,0 +1,393 @@
+//! Typed changefeed (CDC) records.
+//!
+//! Changefeeds in the `JSON` format write one record per row change into a topic:
+//! `key`, one of `update` or `erase`, `newImage` and `oldImage` depending on the changefeed
+//! mode, and `ts` when virtual timestamps are enabled. Partitions also receive
+//! `{"resolved": [step, txId]}` records when resolved timestamps are enabled.
+//!
+//! [`ChangeRecordDecoder`] parses these records into [`ChangeRecord`]s. Key and column values
+//! are converted to [`Value`]s of the source table column types, taken from
+//! [`crate::TableClient::describe_table`]. Nullable columns decode to [`Value::Optional`], like
+//! in query results.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use ydb::{ClientBuilder, TopicReaderOptions, YdbResult};
+//! # use ydb::cdc::{ChangeRecord, ChangeRecordDecoder, ChangefeedMode};
+//! # #[tokio::main]
+//! # async fn main() -> YdbResult<()> {
+//! # let client = ClientBuilder::new_from_connection_string("grpc://localhost:2136/local")?.client()?;
+//! let table = client.table_client().describe_table("/local/users".to_string()).await?;
+//! let decoder = ChangeRecordDecoder::new(&table, ChangefeedMode::NewAndOldImages)?;
+//!
+//! let mut reader = client
+//!     .topic_client()
+//!     .create_reader_with_params(
+//!         TopicReaderOptions::builder()
+//!             .consumer("cdc-consumer".to_string())
+//!             .topic("/local/users/updates".to_string())
+//!             .build(),
+//!     )
+//!     .await?;
+//! let mut batch = reader.read_batch().await?;
+//! for message in batch.messages.iter_mut() {
+//!     match decoder.decode_message(message).await? {
+//!         ChangeRecord::Row(change) => println!("{:?} {:?}", change.kind, change.key),
+//!         ChangeRecord::Resolved(ts) => println!("all changes up to {ts:?} received"),
+//!         _ => {}
+//!     }
+//! }
+//! reader.commit(batch.get_commit_marker())?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod json_value;
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_json::{Map, Value as JsonValue};
+
+use crate::table_service_types::TableDescription;
+use crate::{TopicReaderMessage, Value, YdbError, YdbResult};
+
+/// Changefeed mode of the source table, it defines which parts of a record are present.
+#[cfg_attr(not(feature = "force-exhaustive-all"), non_exhaustive)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChangefeedMode {
+    /// Only the primary key of changed rows.
+    KeysOnly,
+    /// Primary key and the updated columns.
+    Updates,
+    /// Primary key and the row after the change.
+    NewImage,
+    /// Primary key and the row before the change.
+    OldImage,
+    /// Primary key and the rows before and after the change.
+    NewAndOldImages,
+}
+
+impl ChangefeedMode {
+    fn has_old_image(self) -> bool {
+        matches!(self, Self::OldImage | Self::NewAndOldImages)
+    }
+}
+
+/// Virtual timestamp of a change: the plan step and the transaction id.
+///
+/// Timestamps of one table are totally ordered.
+#[cfg_attr(not(feature = "force-exhaustive-all"), non_exhaustive)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CdcTimestamp {
+    pub step: u64,
+    pub tx_id: u64,
+}
+
+#[cfg_attr(not(feature = "force-exhaustive-all"), non_exhaustive)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// The row didn't exist before the change.
+    /// Reported only by changefeeds with an old image.
+    Insert,
+    /// The row existed before the change.
+    /// Reported only by changefeeds with an old image.
+    Update,
+    /// The row was inserted or updated, changefeeds without an old image don't tell which.
+    Upsert,
+    Delete,
+}
+
+/// Change of one row.
+#[cfg_attr(not(feature = "force-exhaustive-all"), non_exhaustive)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RowChange {
+    pub kind: ChangeKind,
+    /// Primary key columns in the primary key order.
+    pub key: Vec<(String, Value)>,
+    /// Updated columns, present in the [`ChangefeedMode::Updates`] mode.
+    pub update: Option<HashMap<String, Value>>,
+    /// Non-key columns after the change, absent for deleted rows.
+    pub new_image: Option<HashMap<String, Value>>,
+    /// Non-key columns before the change, absent for inserted rows.
+    pub old_image: Option<HashMap<String, Value>>,
+    /// Present when the changefeed has virtual timestamps enabled.
+    pub ts: Option<CdcTimestamp>,
+}
+
+/// Record of a changefeed topic.
+#[cfg_attr(not(feature = "force-exhaustive-all"), non_exhaustive)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum ChangeRecord {
+    Row(RowChange),
+    /// All changes of the partition with smaller or equal timestamps were already written
+    /// to it. Changes of the table up to the timestamp are complete when every partition
+    /// has resolved it.
+    Resolved(CdcTimestamp),
+}
+
+/// Decodes changefeed JSON records of one table.
+#[derive(Clone, Debug)]
+pub struct ChangeRecordDecoder {
+    key_columns: Vec<(String, Value)>,
+    columns: HashMap<String, Value>,
+    mode: ChangefeedMode,
+}
+
+impl ChangeRecordDecoder {
+    /// Fails if a column type can't be represented as [`Value`].
+    pub fn new(table: &TableDescription, mode: ChangefeedMode) -> YdbResult<Self> {
+        let mut columns = HashMap::with_capacity(table.columns.len());
+        for column in table.columns.iter() {
+            let example = column.type_value.clone().map_err(|err| {
+                YdbError::Convert(format!(
+                    "changefeed column {}: type {} is unsupported: {}",
+                    column.name, column.type_name, err.error
+                ))
+            })?;
+            columns.insert(column.name.clone(), example);
+        }
+
+        let key_columns = table
+            .primary_key
+            .iter()
+            .map(|name| {
+                let example = columns.get(name).cloned().ok_or_else(|| {
+                    YdbError::Convert(format!("primary key column {name} is not described"))
+                })?;
+                Ok((name.clone(), example))
+            })
+            .collect::<YdbResult<_>>()?;
+
+        Ok(Self {
+            key_columns,
+            columns,
+            mode,
+        })
+    }
+
+    /// Takes the message data, see [`TopicReaderMessage::read_and_take`].
+    pub async fn decode_message(
+        &self,
+        message: &mut TopicReaderMessage,
+    ) -> YdbResult<ChangeRecord> {
+        let data = message.read_and_take().await?.unwrap_or_default();
+        self.decode(&data)
+    }
+
+    pub fn decode(&self, data: &[u8]) -> YdbResult<ChangeRecord> {
+        let record: RawRecord = serde_json::from_slice(data)
+            .map_err(|err| YdbError::Convert(format!("bad changefeed record: {err}")))?;
+
+        if let Some(resolved) = record.resolved {
+            return Ok(ChangeRecord::Resolved(resolved.into()));
+        }
+
+        let key = record
+            .key
+            .ok_or_else(|| YdbError::Convert("changefeed record has no key".to_string()))?;
+        if key.len() != self.key_columns.len() {
+            return Err(YdbError::Convert(format!(
+                "changefeed record key has {} columns, table primary key has {}",
+                key.len(),
+                self.key_columns.len()
+            )));
+        }
+        let key = self
+            .key_columns
+            .iter()
+            .zip(key.iter())
+            .map(|((name, example), json)| Ok((name.clone(), convert(name, example, json)?)))
+            .collect::<YdbResult<_>>()?;
+
+        let kind = match (&record.update, &record.erase) {
+            (Some(_), None) if !self.mode.has_old_image() => ChangeKind::Upsert,
+            (Some(_), None) if record.old_image.is_some() => ChangeKind::Update,
+            (Some(_), None) => ChangeKind::Insert,
+            (None, Some(_)) => ChangeKind::Delete,
+            _ => {
+                return Err(YdbError::Convert(
+                    "changefeed record must have exactly one of update and erase".to_string(),
+                ));
+            }
+        };
+        let update = match record.update {
+            Some(update) if self.mode == ChangefeedMode::Updates => Some(self.columns(update)?),
+            _ => None,
+        };
+
+        Ok(ChangeRecord::Row(RowChange {
+            kind,
+            key,
+            update,
+            new_image: record.new_image.map(|i| self.columns(i)).transpose()?,
+            old_image: record.old_image.map(|i| self.columns(i)).transpose()?,
+            ts: record.ts.map(Into::into),
+        }))
+    }
+
+    fn columns(&self, columns: Map<String, JsonValue>) -> YdbResult<HashMap<String, Value>> {
+        columns
+            .into_iter()
+            .map(|(name, json)| {
+                let example = self.columns.get(&name).ok_or_else(|| {
+                    YdbError::Convert(format!("changefeed column {name} is not in the table"))
+                })?;
+                let value = convert(&name, example, &json)?;
+                Ok((name, value))
+            })
+            .collect()
+    }
+}
+
+fn convert(name: &str, example: &Value, json: &JsonValue) -> YdbResult<Value> {
+    json_value::from_json(example, json)
+        .map_err(|err| YdbError::Convert(format!("changefeed column {name}: {err}")))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawRecord {
+    key: Option<Vec<JsonValue>>,
+    update: Option<Map<String, JsonValue>>,
+    erase: Option<Map<String, JsonValue>>,
+    new_image: Option<Map<String, JsonValue>>,
+    old_image: Option<Map<String, JsonValue>>,
+    ts: Option<(u64, u64)>,
+    resolved: Option<(u64, u64)>,
+}
+
+impl From<(u64, u64)> for CdcTimestamp {
+    fn from((step, tx_id): (u64, u64)) -> Self {
+        Self { step, tx_id }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table_service_types::{ColumnDescription, StoreType};
+    use crate::types::ValueOptional;
+
+    fn column(name: &str, type_value: Value) -> ColumnDescription {
+        ColumnDescription {
+            name: name.to_string(),
+            type_name: String::new(),
+            type_value: Ok(type_value),
+            not_null: false,
+            family: String::new(),
+        }
+    }
+
+    fn optional(t: Value, value: Option<Value>) -> Value {
+        Value::Optional(Box::new(ValueOptional { t, value }))
+    }
+
+    fn decoder(mode: ChangefeedMode) -> ChangeRecordDecoder {
+        let table = TableDescription {
+            columns: vec![
+                column("id", Value::Uint64(0)),
+                column("name", optional(Value::Text(String::new()), None)),
+            ],
+            primary_key: vec!["id".to_string()],
+            indexes: Vec::new(),
+            store_type: StoreType::Row,
+            attributes: HashMap::new(),
+            column_families: Vec::new(),
+            ttl_settings: None,
+            partitioning_settings: None,
+        };
+        ChangeRecordDecoder::new(&table, mode).unwrap()
+    }
+
+    fn row(record: ChangeRecord) -> RowChange {
+        match record {
+            ChangeRecord::Row(change) => change,
+            other => panic!("unexpected record: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn images_decode_to_table_types() {
+        let change = row(decoder(ChangefeedMode::NewAndOldImages)
+            .decode(
+                br#"{"key":[1],"update":{},"newImage":{"name":"b"},"oldImage":{"name":null},"ts":[10,20]}"#,
+            )
+            .unwrap());
+
+        assert_eq!(change.kind, ChangeKind::Update);
+        assert_eq!(change.key, vec![("id".to_string(), Value::Uint64(1))]);
+        assert_eq!(
+            change.new_image.unwrap()["name"],
+            optional(
+                Value::Text(String::new()),
+                Some(Value::Text("b".to_string()))
+            )
+        );
+        assert_eq!(
+            change.old_image.unwrap()["name"],
+            optional(Value::Text(String::new()), None)
+        );
+        assert_eq!(
+            change.ts,
+            Some(CdcTimestamp {
+                step: 10,
+                tx_id: 20
+            })
+        );
+    }
+
+    #[test]
+    fn kind_depends_on_mode() {
+        let insert = br#"{"key":[1],"update":{},"newImage":{"name":"a"}}"#;
+        assert_eq!(
+            row(decoder(ChangefeedMode::NewAndOldImages)
+                .decode(insert)
+                .unwrap())
+            .kind,
+            ChangeKind::Insert
+        );
+        assert_eq!(
+            row(decoder(ChangefeedMode::NewImage).decode(insert).unwrap()).kind,
+            ChangeKind::Upsert
+        );
+
+        let update = row(decoder(ChangefeedMode::Updates)
+            .decode(br#"{"key":[1],"update":{"name":"a"}}"#)
+            .unwrap());
+        assert_eq!(update.kind, ChangeKind::Upsert);
+        assert_eq!(update.update.unwrap().len(), 1);
+
+        let erase = row(decoder(ChangefeedMode::KeysOnly)
+            .decode(br#"{"key":[1],"erase":{}}"#)
+            .unwrap());
+        assert_eq!(erase.kind, ChangeKind::Delete);
+    }
+
+    #[test]
+    fn resolved_timestamp() {
+        assert_eq!(
+            decoder(ChangefeedMode::KeysOnly)
+                .decode(br#"{"resolved":[5,0]}"#)
+                .unwrap(),
+            ChangeRecord::Resolved(CdcTimestamp { step: 5, tx_id: 0 })
+        );
+    }
+
+    #[test]
+    fn mismatched_records_are_rejected() {
+        let decoder = decoder(ChangefeedMode::NewImage);
+        for record in [
+            r#"{"key":["1"],"update":{}}"#,
+            r#"{"key":[1,2],"update":{}}"#,
+            r#"{"key":[1],"update":{},"newImage":{"unknown":1}}"#,
+            r#"{"key":[1]}"#,
+        ] {
+            assert!(
+                matches!(decoder.decode(record.as_bytes()), Err(YdbError::Convert(_))),
+                "{record}"
+            );
+        }
+    }
+}
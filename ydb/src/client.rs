@@ -146,7 +146,12 @@ impl Client {
     /// Create instance of client for directory service
     #[instrument(name = "ydb.Driver.SchemeClient", skip_all, fields(db.system.name = "ydb", db.namespace = %self.credentials.database))]
     pub fn scheme_client(&self) -> SchemeClient {
-        SchemeClient::new(self.connection_manager.clone())
+        SchemeClient::new(
+            self.connection_manager.clone(),
+            self.table_client(),
+            self.topic_client(),
+            self.coordination_client(),
+        )
     }
 
     /// Create instance of client for topic service
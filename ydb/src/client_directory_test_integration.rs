@@ -1,11 +1,13 @@
 use std::time;
 use std::time::UNIX_EPOCH;
 
+use futures_util::TryStreamExt;
 use tracing_test::traced_test;
 
-use crate::client_scheme::list_types::SchemeEntryType;
+use crate::client_scheme::list_types::{SchemeEntryType, SchemeWalkEntry};
 use crate::errors::YdbResult;
 use crate::test_integration_helper::create_client;
+use crate::{CreateTopicOptionsBuilder, NodeConfigBuilder};
 
 #[tokio::test]
 #[traced_test]
@@ -33,3 +35,160 @@ async fn create_list_remove_directory() -> YdbResult<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+#[traced_test]
+#[ignore] // need YDB access
+async fn modify_directory_permissions() -> YdbResult<()> {
+    let client = create_client().await?;
+    let database_path = client.database();
+    let mut scheme_client = client.scheme_client();
+    let time_now = time::SystemTime::now().duration_since(UNIX_EPOCH)?;
+    let directory_path = format!("{}/acl_directory_{}", database_path, time_now.as_millis());
+    let subject = "test_acl_user@builtin";
+
+    scheme_client.make_directory(directory_path.clone()).await?;
+
+    scheme_client
+        .modify_permissions(directory_path.clone())
+        .grant(subject, ["ydb.generic.read"])
+        .await?;
+
+    let entry = scheme_client.describe_path(directory_path.clone()).await?;
+    assert!(entry.permissions.iter().any(
+        |p| p.subject == subject && p.permission_names.iter().any(|n| n == "ydb.generic.read")
+    ));
+    assert!(entry.has_effective_permission(subject, "ydb.generic.read"));
+
+    scheme_client
+        .modify_permissions(directory_path.clone())
+        .revoke(subject, ["ydb.generic.read"])
+        .interrupt_inheritance(true)
+        .await?;
+
+    let entry = scheme_client.describe_path(directory_path.clone()).await?;
+    assert!(!entry.permissions.iter().any(|p| p.subject == subject));
+
+    scheme_client
+        .modify_permissions(directory_path.clone())
+        .clear_permissions()
+        .interrupt_inheritance(false)
+        .await?;
+
+    scheme_client.remove_directory(directory_path).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+#[ignore] // need YDB access
+async fn walk_and_remove_recursive() -> YdbResult<()> {
+    let client = create_client().await?;
+    let database_path = client.database();
+    let mut scheme_client = client.scheme_client();
+    let time_now = time::SystemTime::now().duration_since(UNIX_EPOCH)?;
+    let root_path = format!("{}/walk_directory_{}", database_path, time_now.as_millis());
+    let nested_path = format!("{root_path}/nested");
+
+    scheme_client.make_directory(nested_path.clone()).await?;
+    client
+        .query_client()
+        .exec(format!(
+            "CREATE TABLE `{nested_path}/table` (id Int64 NOT NULL, PRIMARY KEY (id))"
+        ))
+        .await?;
+    client
+        .topic_client()
+        .create_topic(
+            format!("{root_path}/topic"),
+            CreateTopicOptionsBuilder::default().build()?,
+        )
+        .await?;
+    client
+        .coordination_client()
+        .create_node(
+            format!("{nested_path}/node"),
+            NodeConfigBuilder::default().build()?,
+        )
+        .await?;
+
+    let mut entries: Vec<SchemeWalkEntry> = scheme_client
+        .walk(root_path.clone(), None)
+        .try_collect()
+        .await?;
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+    let paths: Vec<(&str, usize)> = entries
+        .iter()
+        .map(|item| (item.path.as_str(), item.depth))
+        .collect();
+    assert_eq!(
+        paths,
+        vec![
+            (nested_path.as_str(), 1),
+            (format!("{nested_path}/node").as_str(), 2),
+            (format!("{nested_path}/table").as_str(), 2),
+            (format!("{root_path}/topic").as_str(), 1),
+        ]
+    );
+
+    let shallow: Vec<SchemeWalkEntry> = scheme_client
+        .walk(root_path.clone(), Some(1))
+        .try_collect()
+        .await?;
+    assert_eq!(shallow.len(), 2);
+
+    scheme_client.remove_recursive(root_path.clone()).await?;
+    let directories = scheme_client.list_directory(database_path).await?;
+    assert!(!directories.iter().any(|d| root_path.ends_with(&d.name)));
+
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+#[ignore] // need YDB access
+async fn dump_schema() -> YdbResult<()> {
+    let client = create_client().await?;
+    let database_path = client.database();
+    let mut scheme_client = client.scheme_client();
+    let time_now = time::SystemTime::now().duration_since(UNIX_EPOCH)?;
+    let root_path = format!("{}/dump_directory_{}", database_path, time_now.as_millis());
+
+    scheme_client
+        .make_directory(format!("{root_path}/empty"))
+        .await?;
+    client
+        .query_client()
+        .exec(format!(
+            "CREATE TABLE `{root_path}/table` (
+                id Uint64 NOT NULL,
+                value Utf8,
+                ts Timestamp,
+                PRIMARY KEY (id),
+                INDEX by_value GLOBAL ON (value)
+            ) WITH (TTL = Interval(\"PT1H\") ON ts)"
+        ))
+        .await?;
+    client
+        .topic_client()
+        .create_topic(
+            format!("{root_path}/topic"),
+            CreateTopicOptionsBuilder::default().build()?,
+        )
+        .await?;
+
+    let dump = scheme_client.dump_schema(root_path.clone()).await?;
+    let paths: Vec<&str> = dump.objects.iter().map(|o| o.path()).collect();
+    assert_eq!(paths, vec!["empty", "table", "topic"]);
+
+    let script = dump.script();
+    assert!(script.contains("-- directory: empty"));
+    assert!(script.contains("CREATE TABLE `table`"));
+    assert!(script.contains("`id` Uint64 NOT NULL"));
+    assert!(script.contains("INDEX `by_value` GLOBAL SYNC ON (`value`)"));
+    assert!(script.contains("TTL = Interval(\"PT3600S\") ON `ts`"));
+    assert!(script.contains("CREATE TOPIC `topic`"));
+
+    scheme_client.remove_recursive(root_path).await?;
+    Ok(())
+}
@@ -1,26 +1,53 @@
 use crate::client::TimeoutSettings;
-use crate::client_scheme::list_types::SchemeEntry;
+use crate::client_coordination::client::CoordinationClient;
+use crate::client_scheme::dump_schema::{
+    SchemaDump, SchemaObject, create_table_statement, create_topic_statement, relative_path,
+};
+use crate::client_scheme::list_types::{
+    SchemeEntry, SchemeEntryType, SchemePermissionsAction, SchemeWalkEntry,
+};
+use crate::client_scheme::modify_permissions::ModifyPermissionsBuilder;
+use crate::client_scheme::walk::walk_stream;
+use crate::client_table::TableClient;
+use crate::client_topic::client::{DescribeTopicOptionsBuilder, TopicClient};
 use crate::grpc_connection_manager::GrpcConnectionManager;
 use crate::grpc_wrapper::raw_scheme_client::client::{
     RawMakeDirectoryRequest, RawRemoveDirectoryRequest,
 };
 use crate::grpc_wrapper::raw_scheme_client::describe_path_types::RawDescribePathRequest;
 use crate::grpc_wrapper::raw_scheme_client::list_directory_types::RawListDirectoryRequest;
+use crate::grpc_wrapper::raw_scheme_client::modify_permissions_types::RawModifyPermissionsRequest;
 
-use crate::{YdbResult, grpc_wrapper};
+use crate::table_requests::DropTableRequest;
+use crate::{YdbError, YdbResult, grpc_wrapper};
+use futures_util::TryStreamExt;
+use futures_util::stream::BoxStream;
 use tracing::instrument;
 
 #[derive(Clone)]
 pub struct SchemeClient {
     timeouts: TimeoutSettings,
     connection_manager: GrpcConnectionManager,
+
+    // used by remove_recursive for drop non-directory entries
+    table_client: TableClient,
+    topic_client: TopicClient,
+    coordination_client: CoordinationClient,
 }
 
 impl SchemeClient {
-    pub(crate) fn new(connection_manager: GrpcConnectionManager) -> Self {
+    pub(crate) fn new(
+        connection_manager: GrpcConnectionManager,
+        table_client: TableClient,
+        topic_client: TopicClient,
+        coordination_client: CoordinationClient,
+    ) -> Self {
         Self {
             timeouts: TimeoutSettings::default(),
             connection_manager,
+            table_client,
+            topic_client,
+            coordination_client,
         }
     }
 
@@ -35,6 +62,10 @@ impl SchemeClient {
         Ok(())
     }
 
+    /// Describe the scheme object.
+    ///
+    /// Result contains both explicit `permissions` and `effective_permissions` (explicit and
+    /// inherited from parents), see [`SchemeEntry::has_effective_permission`].
     #[instrument(name = "ydb.SchemeClient.DescribePath", skip_all, fields(db.system.name = "ydb", ydb.path = %path))]
     pub async fn describe_path(&mut self, path: String) -> YdbResult<SchemeEntry> {
         let req = RawDescribePathRequest {
@@ -72,6 +103,162 @@ impl SchemeClient {
         Ok(())
     }
 
+    /// Walk the scheme tree under `path` recursively.
+    ///
+    /// The root itself is not returned. Every entry is returned with its full path and depth,
+    /// direct children of the root have depth 1. Directories are descended into until
+    /// `max_depth` (`None` for unlimited), so `Some(1)` is equivalent to [`Self::list_directory`].
+    ///
+    /// The stream ends after the first error.
+    pub fn walk(
+        &self,
+        path: String,
+        max_depth: Option<usize>,
+    ) -> BoxStream<'static, YdbResult<SchemeWalkEntry>> {
+        walk_stream(self.clone(), path, max_depth)
+    }
+
+    /// Remove `path` with all its content.
+    ///
+    /// Tables are dropped via [`TableClient`], topics via [`TopicClient`], coordination nodes via
+    /// [`CoordinationClient`], then directories are removed from the deepest one up to `path`.
+    /// Returns an error on the first entry of a type which can't be removed.
+    #[instrument(name = "ydb.SchemeClient.RemoveRecursive", skip_all, fields(db.system.name = "ydb", ydb.path = %path), err)]
+    pub async fn remove_recursive(&mut self, path: String) -> YdbResult<()> {
+        let root = self.describe_path(path.clone()).await?;
+        if root.r#type != SchemeEntryType::Directory {
+            return self.remove_entry(path, root.r#type).await;
+        }
+
+        let entries: Vec<SchemeWalkEntry> = self.walk(path.clone(), None).try_collect().await?;
+        let (mut directories, others): (Vec<_>, Vec<_>) = entries
+            .into_iter()
+            .partition(|item| item.entry.r#type == SchemeEntryType::Directory);
+
+        for item in others {
+            self.remove_entry(item.path, item.entry.r#type).await?;
+        }
+
+        directories.sort_by_key(|item| std::cmp::Reverse(item.depth));
+        for item in directories {
+            self.remove_directory(item.path).await?;
+        }
+
+        self.remove_directory(path).await
+    }
+
+    /// Dump schema of `path` with all its content as YQL DDL.
+    ///
+    /// Tables are described via [`TableClient::describe_table`] and converted to `CREATE TABLE`
+    /// statements (columns, NOT NULL, families, indexes, TTL and partitioning), topics are
+    /// converted to `CREATE TOPIC` statements. See [`SchemaDump`] for the result format.
+    #[instrument(name = "ydb.SchemeClient.DumpSchema", skip_all, fields(db.system.name = "ydb", ydb.path = %path), err)]
+    pub async fn dump_schema(&mut self, path: String) -> YdbResult<SchemaDump> {
+        let root = self.describe_path(path.clone()).await?;
+        let entries: Vec<(String, SchemeEntryType)> = if root.r#type == SchemeEntryType::Directory {
+            self.walk(path.clone(), None)
+                .map_ok(|item| (item.path, item.entry.r#type))
+                .try_collect()
+                .await?
+        } else {
+            vec![(path.clone(), root.r#type)]
+        };
+
+        let mut objects = Vec::with_capacity(entries.len());
+        for (entry_path, entry_type) in entries {
+            let object = self.dump_object(&path, entry_path, entry_type).await?;
+            objects.push(object);
+        }
+        objects.sort_by(|a, b| a.path().cmp(b.path()));
+
+        Ok(SchemaDump {
+            root: path,
+            objects,
+        })
+    }
+
+    async fn dump_object(
+        &mut self,
+        root: &str,
+        path: String,
+        entry_type: SchemeEntryType,
+    ) -> YdbResult<SchemaObject> {
+        let relative = relative_path(root, &path);
+        let object = match entry_type {
+            SchemeEntryType::Directory => SchemaObject::Directory { path: relative },
+            SchemeEntryType::Table | SchemeEntryType::ColumnTable => {
+                let description = self.table_client.describe_table(path).await?;
+                SchemaObject::Table {
+                    statement: create_table_statement(&relative, &description),
+                    path: relative,
+                    description,
+                }
+            }
+            SchemeEntryType::Topic => {
+                let description = self
+                    .topic_client
+                    .describe_topic(path, DescribeTopicOptionsBuilder::default().build()?)
+                    .await?;
+                SchemaObject::Topic {
+                    statement: create_topic_statement(&relative, &description),
+                    path: relative,
+                    description,
+                }
+            }
+            entry_type => SchemaObject::Unsupported {
+                path: relative,
+                entry_type,
+            },
+        };
+        Ok(object)
+    }
+
+    async fn remove_entry(&mut self, path: String, entry_type: SchemeEntryType) -> YdbResult<()> {
+        match entry_type {
+            SchemeEntryType::Directory => self.remove_directory(path).await,
+            SchemeEntryType::Table | SchemeEntryType::ColumnTable => {
+                self.table_client
+                    .drop_table(DropTableRequest::new(path))
+                    .await
+            }
+            SchemeEntryType::Topic | SchemeEntryType::PersQueueGroup => {
+                self.topic_client.drop_topic(path).await
+            }
+            SchemeEntryType::CoordinationNode => self.coordination_client.drop_node(path).await,
+            other => Err(YdbError::custom(format!(
+                "remove recursive: unsupported scheme entry type {other:?} for path: {path}"
+            ))),
+        }
+    }
+
+    /// Change ACL of the scheme object: grant, revoke and set permissions, change owner,
+    /// clear permissions and interrupt inheritance.
+    ///
+    /// Returned builder sends the request when awaited.
+    pub fn modify_permissions(&mut self, path: String) -> ModifyPermissionsBuilder<'_> {
+        ModifyPermissionsBuilder::new(self, path)
+    }
+
+    #[instrument(name = "ydb.SchemeClient.ModifyPermissions", skip_all, fields(db.system.name = "ydb", ydb.path = %path))]
+    pub(crate) async fn modify_permissions_impl(
+        &mut self,
+        path: String,
+        actions: Vec<SchemePermissionsAction>,
+        clear_permissions: bool,
+        interrupt_inheritance: Option<bool>,
+    ) -> YdbResult<()> {
+        let req = RawModifyPermissionsRequest {
+            operation_params: self.timeouts.operation_params(),
+            path,
+            actions,
+            clear_permissions,
+            interrupt_inheritance,
+        };
+        let mut service = self.connection().await?;
+        service.modify_permissions(req).await?;
+        Ok(())
+    }
+
     async fn connection(
         &self,
     ) -> YdbResult<grpc_wrapper::raw_scheme_client::client::RawSchemeClient> {
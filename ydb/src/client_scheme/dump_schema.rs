@@ -0,0 +1,645 @@
+use std::fmt::Write;
+use std::time::{Duration, SystemTime};
+
+use crate::client_scheme::list_types::SchemeEntryType;
+use crate::client_topic::list_types::{
+    AutoPartitioningSettings, AutoPartitioningStrategy, Codec, Consumer, MeteringMode,
+    TopicDescription,
+};
+use crate::table_service_types::{
+    ColumnFamilyCompression, IndexType, StoreType, TableDescription, TtlMode, TtlUnit,
+};
+
+/// Result of [`crate::SchemeClient::dump_schema`].
+///
+/// Paths of objects and in the statements are relative to the dumped root, so the same
+/// schema dumped from different databases compares equal.
+/// Apply the script with `PRAGMA TablePathPrefix` pointing to the target root.
+#[derive(Debug, Clone)]
+pub struct SchemaDump {
+    /// Dumped root path, as passed to `dump_schema`
+    pub root: String,
+
+    /// Scheme objects ordered by path
+    pub objects: Vec<SchemaObject>,
+}
+
+impl SchemaDump {
+    /// YQL script with statements of all objects, separated with empty lines.
+    ///
+    /// Directories and objects without DDL equivalent are written as comments.
+    pub fn script(&self) -> String {
+        let mut res = String::new();
+        for object in &self.objects {
+            if !res.is_empty() {
+                res.push('\n');
+            }
+            match object {
+                SchemaObject::Directory { path } => {
+                    let _ = writeln!(res, "-- directory: {path}");
+                }
+                SchemaObject::Table { statement, .. } | SchemaObject::Topic { statement, .. } => {
+                    res.push_str(statement);
+                    res.push('\n');
+                }
+                SchemaObject::Unsupported { path, entry_type } => {
+                    let _ = writeln!(res, "-- unsupported {entry_type:?}: {path}");
+                }
+            }
+        }
+        res
+    }
+}
+
+/// Scheme object from [`SchemaDump`].
+#[derive(Debug, Clone)]
+#[cfg_attr(not(feature = "force-exhaustive-all"), non_exhaustive)]
+pub enum SchemaObject {
+    /// Directory. YQL has no statement for create a directory, it is created with the first
+    /// object inside, so empty directories are kept in the dump explicitly.
+    Directory { path: String },
+
+    Table {
+        path: String,
+        description: TableDescription,
+        /// `CREATE TABLE` statement
+        statement: String,
+    },
+
+    Topic {
+        path: String,
+        description: TopicDescription,
+        /// `CREATE TOPIC` statement
+        statement: String,
+    },
+
+    /// Object without a DDL equivalent in the dump, for example a coordination node.
+    Unsupported {
+        path: String,
+        entry_type: SchemeEntryType,
+    },
+}
+
+impl SchemaObject {
+    pub fn path(&self) -> &str {
+        match self {
+            SchemaObject::Directory { path }
+            | SchemaObject::Table { path, .. }
+            | SchemaObject::Topic { path, .. }
+            | SchemaObject::Unsupported { path, .. } => path,
+        }
+    }
+
+    /// YQL statement, which creates the object. None for directories and unsupported objects.
+    pub fn statement(&self) -> Option<&str> {
+        match self {
+            SchemaObject::Table { statement, .. } | SchemaObject::Topic { statement, .. } => {
+                Some(statement)
+            }
+            SchemaObject::Directory { .. } | SchemaObject::Unsupported { .. } => None,
+        }
+    }
+}
+
+pub(crate) fn relative_path(root: &str, path: &str) -> String {
+    let root = root.trim_end_matches('/');
+    match path.strip_prefix(root) {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => {
+            rest.trim_start_matches('/').to_string()
+        }
+        _ => path.to_string(),
+    }
+}
+
+pub(crate) fn create_table_statement(path: &str, description: &TableDescription) -> String {
+    let mut items = Vec::new();
+
+    for column in &description.columns {
+        let mut item = format!(
+            "{} {}",
+            quote_identifier(&column.name),
+            column_type(&column.type_name, column.not_null)
+        );
+        if !column.family.is_empty() {
+            let _ = write!(item, " FAMILY {}", quote_identifier(&column.family));
+        }
+        if column.not_null {
+            item.push_str(" NOT NULL");
+        }
+        items.push(item);
+    }
+
+    items.push(format!(
+        "PRIMARY KEY ({})",
+        identifier_list(&description.primary_key)
+    ));
+
+    for index in &description.indexes {
+        let kind = match index.index_type {
+            IndexType::GlobalAsync => "GLOBAL ASYNC",
+            IndexType::GlobalUnique => "GLOBAL UNIQUE SYNC",
+            IndexType::Global | IndexType::Unspecified => "GLOBAL SYNC",
+        };
+        let mut item = format!(
+            "INDEX {} {kind} ON ({})",
+            quote_identifier(&index.name),
+            identifier_list(&index.index_columns)
+        );
+        if !index.data_columns.is_empty() {
+            let _ = write!(item, " COVER ({})", identifier_list(&index.data_columns));
+        }
+        items.push(item);
+    }
+
+    for family in &description.column_families {
+        let mut settings = Vec::new();
+        if !family.data.is_empty() {
+            settings.push(format!("DATA = {}", quote_string(&family.data)));
+        }
+        match family.compression {
+            ColumnFamilyCompression::None => settings.push("COMPRESSION = \"off\"".to_string()),
+            ColumnFamilyCompression::Lz4 => settings.push("COMPRESSION = \"lz4\"".to_string()),
+            ColumnFamilyCompression::Unspecified => {}
+        }
+        if settings.is_empty() && family.name == "default" {
+            continue;
+        }
+        items.push(format!(
+            "FAMILY {} ({})",
+            quote_identifier(&family.name),
+            settings.join(", ")
+        ));
+    }
+
+    let mut res = format!("CREATE TABLE {} (\n", quote_identifier(path));
+    res.push_str(&indent_list(&items));
+    res.push_str("\n)");
+
+    let is_column_table = description.store_type == StoreType::Column;
+    let mut with = Vec::new();
+    if is_column_table {
+        with.push("STORE = COLUMN".to_string());
+    }
+
+    if let Some(partitioning) = &description.partitioning_settings {
+        if is_column_table && !partitioning.partition_by.is_empty() {
+            let _ = write!(
+                res,
+                "\nPARTITION BY HASH({})",
+                identifier_list(&partitioning.partition_by)
+            );
+        }
+        if let Some(enabled) = partitioning.partitioning_by_size {
+            with.push(format!("AUTO_PARTITIONING_BY_SIZE = {}", flag(enabled)));
+        }
+        if partitioning.partition_size_mb > 0 {
+            with.push(format!(
+                "AUTO_PARTITIONING_PARTITION_SIZE_MB = {}",
+                partitioning.partition_size_mb
+            ));
+        }
+        if let Some(enabled) = partitioning.partitioning_by_load {
+            with.push(format!("AUTO_PARTITIONING_BY_LOAD = {}", flag(enabled)));
+        }
+        if partitioning.min_partitions_count > 0 {
+            with.push(format!(
+                "AUTO_PARTITIONING_MIN_PARTITIONS_COUNT = {}",
+                partitioning.min_partitions_count
+            ));
+        }
+        if partitioning.max_partitions_count > 0 {
+            with.push(format!(
+                "AUTO_PARTITIONING_MAX_PARTITIONS_COUNT = {}",
+                partitioning.max_partitions_count
+            ));
+        }
+    }
+
+    if let Some(ttl) = &description.ttl_settings {
+        let mut item = format!(
+            "TTL = {} ON {}",
+            interval_literal(ttl.expire_after),
+            quote_identifier(&ttl.column_name)
+        );
+        if let TtlMode::ValueSinceUnixEpoch(unit) = ttl.mode {
+            let unit = match unit {
+                TtlUnit::Milliseconds => "MILLISECONDS",
+                TtlUnit::Microseconds => "MICROSECONDS",
+                TtlUnit::Nanoseconds => "NANOSECONDS",
+                TtlUnit::Seconds | TtlUnit::Unspecified => "SECONDS",
+            };
+            let _ = write!(item, " AS {unit}");
+        }
+        with.push(item);
+    }
+
+    if !with.is_empty() {
+        res.push_str("\nWITH (\n");
+        res.push_str(&indent_list(&with));
+        res.push_str("\n)");
+    }
+
+    res.push(';');
+    res
+}
+
+pub(crate) fn create_topic_statement(path: &str, description: &TopicDescription) -> String {
+    let mut res = format!("CREATE TOPIC {}", quote_identifier(path));
+
+    let consumers: Vec<String> = description.consumers.iter().map(consumer_item).collect();
+    if !consumers.is_empty() {
+        res.push_str(" (\n");
+        res.push_str(&indent_list(&consumers));
+        res.push_str("\n)");
+    }
+
+    let partitioning = &description.partitioning_settings;
+    let mut with = vec![format!(
+        "min_active_partitions = {}",
+        partitioning.min_active_partitions
+    )];
+    if partitioning.partition_count_limit > 0 {
+        with.push(format!(
+            "partition_count_limit = {}",
+            partitioning.partition_count_limit
+        ));
+    }
+    if let Some(auto) = &partitioning.auto_partitioning_settings {
+        with.extend(auto_partitioning_items(auto));
+    }
+    with.push(format!(
+        "retention_period = {}",
+        interval_literal(description.retention_period)
+    ));
+    if let Some(storage_mb) = description.retention_storage_mb {
+        with.push(format!("retention_storage_mb = {storage_mb}"));
+    }
+    if !description.supported_codecs.is_empty() {
+        with.push(format!(
+            "supported_codecs = {}",
+            codecs_literal(&description.supported_codecs)
+        ));
+    }
+    if description.partition_write_speed_bytes_per_second > 0 {
+        with.push(format!(
+            "partition_write_speed_bytes_per_second = {}",
+            description.partition_write_speed_bytes_per_second
+        ));
+    }
+    if description.partition_write_burst_bytes > 0 {
+        with.push(format!(
+            "partition_write_burst_bytes = {}",
+            description.partition_write_burst_bytes
+        ));
+    }
+    if let Some(mode) = &description.metering_mode {
+        let mode = match mode {
+            MeteringMode::ReservedCapacity => "reserved_capacity",
+            MeteringMode::RequestUnits => "request_units",
+        };
+        with.push(format!("metering_mode = {}", quote_string(mode)));
+    }
+
+    res.push_str(" WITH (\n");
+    res.push_str(&indent_list(&with));
+    res.push_str("\n);");
+    res
+}
+
+fn auto_partitioning_items(settings: &AutoPartitioningSettings) -> Vec<String> {
+    let mut items = vec![];
+    if let Some(strategy) = settings.strategy {
+        let strategy = match strategy {
+            AutoPartitioningStrategy::Disabled => "disabled",
+            AutoPartitioningStrategy::ScaleUp => "scale_up",
+            AutoPartitioningStrategy::ScaleUpAndDown => "scale_up_and_down",
+            AutoPartitioningStrategy::Paused => "paused",
+        };
+        items.push(format!(
+            "auto_partitioning_strategy = {}",
+            quote_string(strategy)
+        ));
+    }
+    if let Some(window) = settings.stabilization_window
+        && !window.is_zero()
+    {
+        items.push(format!(
+            "auto_partitioning_stabilization_window = {}",
+            interval_literal(window)
+        ));
+    }
+    if settings.up_utilization_percent > 0 {
+        items.push(format!(
+            "auto_partitioning_up_utilization_percent = {}",
+            settings.up_utilization_percent
+        ));
+    }
+    if settings.down_utilization_percent > 0 {
+        items.push(format!(
+            "auto_partitioning_down_utilization_percent = {}",
+            settings.down_utilization_percent
+        ));
+    }
+    items
+}
+
+fn consumer_item(consumer: &Consumer) -> String {
+    let mut settings = Vec::new();
+    if consumer.important {
+        settings.push("important = true".to_string());
+    }
+    if let Some(read_from) = consumer.read_from {
+        settings.push(format!("read_from = {}", timestamp_literal(read_from)));
+    }
+    if !consumer.supported_codecs.is_empty() {
+        settings.push(format!(
+            "supported_codecs = {}",
+            codecs_literal(&consumer.supported_codecs)
+        ));
+    }
+
+    let mut item = format!("CONSUMER {}", quote_identifier(&consumer.name));
+    if !settings.is_empty() {
+        let _ = write!(item, " WITH ({})", settings.join(", "));
+    }
+    item
+}
+
+// nullable columns described as Optional<T>, but declared as T without NOT NULL
+fn column_type(type_name: &str, not_null: bool) -> &str {
+    if not_null {
+        return type_name;
+    }
+    type_name
+        .strip_prefix("Optional<")
+        .and_then(|t| t.strip_suffix('>'))
+        .unwrap_or(type_name)
+}
+
+fn codecs_literal(codecs: &[Codec]) -> String {
+    let names: Vec<String> = codecs
+        .iter()
+        .map(|codec| match *codec {
+            Codec::RAW => "raw".to_string(),
+            Codec::GZIP => "gzip".to_string(),
+            Codec::LZOP => "lzop".to_string(),
+            Codec::ZSTD => "zstd".to_string(),
+            other => other.code.to_string(),
+        })
+        .collect();
+    quote_string(&names.join(","))
+}
+
+fn flag(enabled: bool) -> &'static str {
+    if enabled { "ENABLED" } else { "DISABLED" }
+}
+
+fn interval_literal(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let micros = duration.subsec_micros();
+    if micros == 0 {
+        format!("Interval(\"PT{seconds}S\")")
+    } else {
+        format!("Interval(\"PT{seconds}.{micros:06}S\")")
+    }
+}
+
+fn timestamp_literal(time: SystemTime) -> String {
+    let time: chrono::DateTime<chrono::Utc> = time.into();
+    format!(
+        "Timestamp({})",
+        quote_string(&time.to_rfc3339_opts(chrono::SecondsFormat::Micros, true))
+    )
+}
+
+fn identifier_list(names: &[String]) -> String {
+    names
+        .iter()
+        .map(|name| quote_identifier(name))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn indent_list(items: &[String]) -> String {
+    items
+        .iter()
+        .map(|item| format!("    {item}"))
+        .collect::<Vec<_>>()
+        .join(",\n")
+}
+
+fn quote_identifier(name: &str) -> String {
+    format!("`{}`", name.replace('\\', "\\\\").replace('`', "\\`"))
+}
+
+fn quote_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Value;
+    use crate::client_topic::list_types::PartitioningSettings;
+    use crate::table_service_types::{
+        ColumnDescription, ColumnFamilyDescription, IndexDescription, IndexStatus,
+        TablePartitioningSettings, TtlSettings,
+    };
+    use std::collections::HashMap;
+
+    fn column(name: &str, type_name: &str, not_null: bool, family: &str) -> ColumnDescription {
+        ColumnDescription {
+            name: name.to_string(),
+            type_name: type_name.to_string(),
+            type_value: Ok(Value::Null),
+            not_null,
+            family: family.to_string(),
+        }
+    }
+
+    #[test]
+    fn relative_path_strips_root() {
+        assert_eq!(relative_path("/local/app", "/local/app/dir/t"), "dir/t");
+        assert_eq!(relative_path("/local/app/", "/local/app/t"), "t");
+        assert_eq!(
+            relative_path("/local/app", "/local/application"),
+            "/local/application"
+        );
+    }
+
+    #[test]
+    fn create_row_table() {
+        let description = TableDescription {
+            columns: vec![
+                column("id", "Uint64", true, ""),
+                column("value", "Optional<Utf8>", false, "cold"),
+                column("ts", "Optional<Timestamp>", false, ""),
+            ],
+            primary_key: vec!["id".to_string()],
+            indexes: vec![IndexDescription {
+                name: "by_value".to_string(),
+                index_columns: vec!["value".to_string()],
+                data_columns: vec!["ts".to_string()],
+                status: IndexStatus::Ready,
+                index_type: IndexType::GlobalAsync,
+            }],
+            store_type: StoreType::Row,
+            attributes: HashMap::new(),
+            column_families: vec![
+                ColumnFamilyDescription {
+                    name: "default".to_string(),
+                    data: String::new(),
+                    compression: ColumnFamilyCompression::Unspecified,
+                },
+                ColumnFamilyDescription {
+                    name: "cold".to_string(),
+                    data: "rot".to_string(),
+                    compression: ColumnFamilyCompression::Lz4,
+                },
+            ],
+            ttl_settings: Some(TtlSettings {
+                column_name: "ts".to_string(),
+                expire_after: Duration::from_secs(3600),
+                mode: TtlMode::DateTypeColumn,
+            }),
+            partitioning_settings: Some(TablePartitioningSettings {
+                partition_by: vec![],
+                partitioning_by_size: Some(true),
+                partition_size_mb: 2048,
+                partitioning_by_load: Some(false),
+                min_partitions_count: 1,
+                max_partitions_count: 0,
+            }),
+        };
+
+        assert_eq!(
+            create_table_statement("dir/table", &description),
+            r#"CREATE TABLE `dir/table` (
+    `id` Uint64 NOT NULL,
+    `value` Utf8 FAMILY `cold`,
+    `ts` Timestamp,
+    PRIMARY KEY (`id`),
+    INDEX `by_value` GLOBAL ASYNC ON (`value`) COVER (`ts`),
+    FAMILY `cold` (DATA = "rot", COMPRESSION = "lz4")
+)
+WITH (
+    AUTO_PARTITIONING_BY_SIZE = ENABLED,
+    AUTO_PARTITIONING_PARTITION_SIZE_MB = 2048,
+    AUTO_PARTITIONING_BY_LOAD = DISABLED,
+    AUTO_PARTITIONING_MIN_PARTITIONS_COUNT = 1,
+    TTL = Interval("PT3600S") ON `ts`
+);"#
+        );
+    }
+
+    #[test]
+    fn create_column_table() {
+        let description = TableDescription {
+            columns: vec![
+                column("id", "Int64", true, ""),
+                column("created", "Uint32", true, ""),
+            ],
+            primary_key: vec!["id".to_string()],
+            indexes: vec![],
+            store_type: StoreType::Column,
+            attributes: HashMap::new(),
+            column_families: vec![],
+            ttl_settings: Some(TtlSettings {
+                column_name: "created".to_string(),
+                expire_after: Duration::from_secs(60),
+                mode: TtlMode::ValueSinceUnixEpoch(TtlUnit::Seconds),
+            }),
+            partitioning_settings: Some(TablePartitioningSettings {
+                partition_by: vec!["id".to_string()],
+                min_partitions_count: 4,
+                ..Default::default()
+            }),
+        };
+
+        assert_eq!(
+            create_table_statement("t", &description),
+            r#"CREATE TABLE `t` (
+    `id` Int64 NOT NULL,
+    `created` Uint32 NOT NULL,
+    PRIMARY KEY (`id`)
+)
+PARTITION BY HASH(`id`)
+WITH (
+    STORE = COLUMN,
+    AUTO_PARTITIONING_MIN_PARTITIONS_COUNT = 4,
+    TTL = Interval("PT60S") ON `created` AS SECONDS
+);"#
+        );
+    }
+
+    #[test]
+    fn create_topic() {
+        let description = TopicDescription {
+            path: "topic".to_string(),
+            partitioning_settings: PartitioningSettings {
+                min_active_partitions: 2,
+                partition_count_limit: 0,
+                auto_partitioning_settings: Some(AutoPartitioningSettings {
+                    strategy: Some(AutoPartitioningStrategy::ScaleUp),
+                    stabilization_window: Some(Duration::from_secs(300)),
+                    up_utilization_percent: 90,
+                    down_utilization_percent: 0,
+                }),
+            },
+            partitions: vec![],
+            retention_period: Duration::from_secs(86400),
+            retention_storage_mb: None,
+            supported_codecs: vec![Codec::RAW, Codec::GZIP],
+            partition_write_speed_bytes_per_second: 1048576,
+            partition_total_read_speed_bytes_per_second: 0,
+            partition_consumer_read_speed_bytes_per_second: 0,
+            partition_write_burst_bytes: 0,
+            attributes: HashMap::new(),
+            consumers: vec![Consumer {
+                name: "consumer".to_string(),
+                important: true,
+                read_from: None,
+                supported_codecs: vec![],
+                attributes: HashMap::new(),
+                consumer_stats: None,
+            }],
+            metering_mode: None,
+            stats: None,
+        };
+
+        assert_eq!(
+            create_topic_statement("dir/topic", &description),
+            r#"CREATE TOPIC `dir/topic` (
+    CONSUMER `consumer` WITH (important = true)
+) WITH (
+    min_active_partitions = 2,
+    auto_partitioning_strategy = "scale_up",
+    auto_partitioning_stabilization_window = Interval("PT300S"),
+    auto_partitioning_up_utilization_percent = 90,
+    retention_period = Interval("PT86400S"),
+    supported_codecs = "raw,gzip",
+    partition_write_speed_bytes_per_second = 1048576
+);"#
+        );
+    }
+
+    #[test]
+    fn script_keeps_directories() {
+        let dump = SchemaDump {
+            root: "/local".to_string(),
+            objects: vec![
+                SchemaObject::Directory {
+                    path: "empty".to_string(),
+                },
+                SchemaObject::Unsupported {
+                    path: "node".to_string(),
+                    entry_type: SchemeEntryType::CoordinationNode,
+                },
+            ],
+        };
+        assert_eq!(
+            dump.script(),
+            "-- directory: empty\n\n-- unsupported CoordinationNode: node\n"
+        );
+    }
+}
@@ -1,4 +1,4 @@
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 #[cfg_attr(not(feature = "force-exhaustive-all"), non_exhaustive)]
 pub struct SchemeEntry {
     pub name: String,
@@ -9,7 +9,7 @@ pub struct SchemeEntry {
     pub size_bytes: u64,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[cfg_attr(not(feature = "force-exhaustive-all"), non_exhaustive)]
 pub enum SchemeEntryType {
     Unspecified,
@@ -31,8 +31,62 @@ pub enum SchemeEntryType {
     Unknown(i32),
 }
 
-#[derive(Debug)]
+impl SchemeEntry {
+    /// Permission names the subject effectively has on the entry, including inherited ones.
+    pub fn effective_permissions_for(&self, subject: &str) -> Vec<&str> {
+        self.effective_permissions
+            .iter()
+            .filter(|p| p.subject == subject)
+            .flat_map(|p| p.permission_names.iter().map(String::as_str))
+            .collect()
+    }
+
+    /// Check if the subject effectively has the permission on the entry, including inherited ones.
+    pub fn has_effective_permission(&self, subject: &str, permission_name: &str) -> bool {
+        self.effective_permissions.iter().any(|p| {
+            p.subject == subject && p.permission_names.iter().any(|n| n == permission_name)
+        })
+    }
+}
+
+/// Entry found by [`crate::SchemeClient::walk`].
+#[derive(Clone, Debug)]
+#[cfg_attr(not(feature = "force-exhaustive-all"), non_exhaustive)]
+pub struct SchemeWalkEntry {
+    /// Full path of the entry: walk root path joined with the entry names.
+    pub path: String,
+
+    /// Depth from the walk root: direct children of the root have depth 1.
+    pub depth: usize,
+
+    pub entry: SchemeEntry,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SchemePermissions {
     pub subject: String,
     pub permission_names: Vec<String>,
 }
+
+impl SchemePermissions {
+    pub fn new(subject: impl Into<String>, permission_names: Vec<String>) -> Self {
+        Self {
+            subject: subject.into(),
+            permission_names,
+        }
+    }
+}
+
+/// Single change of a scheme object ACL, used by [`crate::SchemeClient::modify_permissions`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "force-exhaustive-all"), non_exhaustive)]
+pub enum SchemePermissionsAction {
+    /// Add permissions to the subject
+    Grant(SchemePermissions),
+    /// Remove permissions from the subject
+    Revoke(SchemePermissions),
+    /// Replace all permissions of the subject (last set wins for the same subject)
+    Set(SchemePermissions),
+    /// Change owner of the object
+    ChangeOwner(String),
+}
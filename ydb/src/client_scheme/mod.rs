@@ -1,2 +1,5 @@
 pub mod client;
+pub mod dump_schema;
 pub mod list_types;
+pub mod modify_permissions;
+pub(crate) mod walk;
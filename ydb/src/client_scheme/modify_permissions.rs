@@ -0,0 +1,129 @@
+use std::future::IntoFuture;
+
+use futures_util::future::BoxFuture;
+
+use crate::YdbResult;
+use crate::client_scheme::client::SchemeClient;
+use crate::client_scheme::list_types::{SchemePermissions, SchemePermissionsAction};
+
+/// Builder of ACL changes for a scheme object, created by [`SchemeClient::modify_permissions`].
+///
+/// All actions are sent in one request and applied atomically by the server.
+/// Await the builder to execute the request.
+///
+/// # Example
+///
+/// ```no_run
+/// # use ydb::{SchemeClient, YdbResult};
+/// # async fn example(mut scheme_client: SchemeClient) -> YdbResult<()> {
+/// scheme_client
+///     .modify_permissions("/local/tenant_a".to_string())
+///     .grant("tenant_a@as", ["ydb.generic.read", "ydb.generic.write"])
+///     .revoke("guest@as", ["ydb.generic.read"])
+///     .interrupt_inheritance(true)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[must_use = "the request is sent only when the builder is awaited"]
+pub struct ModifyPermissionsBuilder<'a> {
+    client: &'a mut SchemeClient,
+    path: String,
+    actions: Vec<SchemePermissionsAction>,
+    clear_permissions: bool,
+    interrupt_inheritance: Option<bool>,
+}
+
+impl<'a> ModifyPermissionsBuilder<'a> {
+    pub(crate) fn new(client: &'a mut SchemeClient, path: String) -> Self {
+        Self {
+            client,
+            path,
+            actions: Vec::new(),
+            clear_permissions: false,
+            interrupt_inheritance: None,
+        }
+    }
+
+    /// Add permissions to the subject.
+    pub fn grant<I, S>(self, subject: impl Into<String>, permission_names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let permissions = make_permissions(subject, permission_names);
+        self.action(SchemePermissionsAction::Grant(permissions))
+    }
+
+    /// Remove permissions from the subject.
+    pub fn revoke<I, S>(self, subject: impl Into<String>, permission_names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let permissions = make_permissions(subject, permission_names);
+        self.action(SchemePermissionsAction::Revoke(permissions))
+    }
+
+    /// Replace all permissions of the subject with the given ones.
+    pub fn set<I, S>(self, subject: impl Into<String>, permission_names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let permissions = make_permissions(subject, permission_names);
+        self.action(SchemePermissionsAction::Set(permissions))
+    }
+
+    /// Change owner of the object.
+    pub fn change_owner(self, owner: impl Into<String>) -> Self {
+        self.action(SchemePermissionsAction::ChangeOwner(owner.into()))
+    }
+
+    /// Append a prepared action. Actions are applied in order.
+    pub fn action(mut self, action: SchemePermissionsAction) -> Self {
+        self.actions.push(action);
+        self
+    }
+
+    /// Remove all explicit permissions of all subjects before applying the actions.
+    pub fn clear_permissions(mut self) -> Self {
+        self.clear_permissions = true;
+        self
+    }
+
+    /// Stop (`true`) or restore (`false`) inheritance of permissions from the parent object.
+    pub fn interrupt_inheritance(mut self, interrupt: bool) -> Self {
+        self.interrupt_inheritance = Some(interrupt);
+        self
+    }
+}
+
+impl<'a> IntoFuture for ModifyPermissionsBuilder<'a> {
+    type Output = YdbResult<()>;
+    type IntoFuture = BoxFuture<'a, Self::Output>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(async move {
+            self.client
+                .modify_permissions_impl(
+                    self.path,
+                    self.actions,
+                    self.clear_permissions,
+                    self.interrupt_inheritance,
+                )
+                .await
+        })
+    }
+}
+
+fn make_permissions<I, S>(subject: impl Into<String>, permission_names: I) -> SchemePermissions
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    SchemePermissions::new(
+        subject,
+        permission_names.into_iter().map(Into::into).collect(),
+    )
+}
@@ -0,0 +1,82 @@
+use std::collections::VecDeque;
+
+use futures_util::stream::{self, BoxStream};
+
+use crate::YdbResult;
+use crate::client_scheme::client::SchemeClient;
+use crate::client_scheme::list_types::{SchemeEntryType, SchemeWalkEntry};
+
+struct WalkState {
+    client: SchemeClient,
+    max_depth: Option<usize>,
+    ready: VecDeque<SchemeWalkEntry>,
+    // directories to list: (path, depth of the directory)
+    pending: Vec<(String, usize)>,
+}
+
+pub(crate) fn walk_stream(
+    client: SchemeClient,
+    path: String,
+    max_depth: Option<usize>,
+) -> BoxStream<'static, YdbResult<SchemeWalkEntry>> {
+    let state = WalkState {
+        client,
+        max_depth,
+        ready: VecDeque::new(),
+        pending: vec![(path, 0)],
+    };
+
+    Box::pin(stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(item) = state.ready.pop_front() {
+                if item.entry.r#type == SchemeEntryType::Directory && state.can_descend(item.depth)
+                {
+                    state.pending.push((item.path.clone(), item.depth));
+                }
+                return Some((Ok(item), state));
+            }
+
+            let (dir_path, depth) = state.pending.pop()?;
+            match state.client.list_directory(dir_path.clone()).await {
+                Ok(children) => {
+                    state
+                        .ready
+                        .extend(children.into_iter().map(|entry| SchemeWalkEntry {
+                            path: join_path(&dir_path, &entry.name),
+                            depth: depth + 1,
+                            entry,
+                        }));
+                }
+                Err(err) => {
+                    state.pending.clear();
+                    return Some((Err(err), state));
+                }
+            }
+        }
+    }))
+}
+
+impl WalkState {
+    fn can_descend(&self, depth: usize) -> bool {
+        self.max_depth.is_none_or(|max_depth| depth < max_depth)
+    }
+}
+
+pub(crate) fn join_path(parent: &str, name: &str) -> String {
+    if parent.ends_with('/') {
+        format!("{parent}{name}")
+    } else {
+        format!("{parent}/{name}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::join_path;
+
+    #[test]
+    fn join_path_adds_single_separator() {
+        assert_eq!(join_path("/local/dir", "table"), "/local/dir/table");
+        assert_eq!(join_path("/local/dir/", "table"), "/local/dir/table");
+    }
+}
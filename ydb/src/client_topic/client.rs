@@ -5,24 +5,34 @@ use crate::YdbError::InternalError;
 use crate::client::TimeoutSettings;
 use crate::client_common::TokenCache;
 use crate::client_query::Transaction;
-use crate::client_topic::list_types::{AlterConsumer, Consumer, MeteringMode};
-use crate::client_topic::topicreader::reader::{TopicReader, TopicSelectors};
+use crate::client_topic::list_types::{
+    AlterConsumer, AutoPartitioningSettings, AutoPartitioningStrategy, Consumer,
+    ConsumerPartitionInfo, ConsumerResetTarget, MeteringMode, PartitionStats,
+};
+use crate::client_topic::topicreader::consumer::{TopicConsumer, TopicConsumerOptions};
+use crate::client_topic::topicreader::dead_letter::DeadLetterWriter;
+use crate::client_topic::topicreader::messages::TopicReaderMessage;
+use crate::client_topic::topicreader::partition_range::{PartitionRangeReader, PartitionReadStart};
+use crate::client_topic::topicreader::reader::{TopicReader, TopicSelector, TopicSelectors};
 use crate::client_topic::topicreader::reader_options::TopicReaderOptions;
+use crate::client_topic::topicwriter::keyed_writer::{KeyedTopicWriter, KeyedTopicWriterOptions};
 use crate::client_topic::topicwriter::writer::TopicWriter;
 use crate::client_topic::topicwriter::writer_options::TopicWriterOptions;
 use crate::client_topic::topicwriter::writer_tx::TopicWriterTx;
 use crate::errors;
 use crate::grpc_connection_manager::GrpcConnectionManager;
 use crate::grpc_wrapper::raw_topic_service::alter_topic::RawAlterTopicRequest;
+use crate::grpc_wrapper::raw_topic_service::commit_offset::RawCommitOffsetRequest;
 use crate::grpc_wrapper::raw_topic_service::create_topic::RawCreateTopicRequest;
 use crate::grpc_wrapper::raw_topic_service::describe_consumer::RawDescribeConsumerRequest;
 use crate::grpc_wrapper::raw_topic_service::describe_topic::RawDescribeTopicRequest;
 use crate::grpc_wrapper::raw_topic_service::drop_topic::RawDropTopicRequest;
-use crate::{YdbResult, grpc_wrapper};
+use crate::{YdbError, YdbResult, grpc_wrapper};
 use derive_builder::{Builder, UninitializedFieldError};
+use futures_util::{Stream, StreamExt};
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 use tracing::instrument;
 
 #[derive(Builder)]
@@ -34,6 +44,8 @@ pub struct CreateTopicOptions {
     #[builder(default)]
     pub partition_count_limit: i64,
     #[builder(setter(strip_option), default)]
+    pub auto_partitioning_settings: Option<AutoPartitioningSettings>,
+    #[builder(setter(strip_option), default)]
     pub retention_period: Option<Duration>,
     #[builder(default)]
     pub retention_storage_mb: i64,
@@ -61,6 +73,18 @@ pub struct AlterTopicOptions {
     #[builder(setter(strip_option), default)]
     pub set_partition_count_limit: Option<i64>,
 
+    #[builder(setter(strip_option), default)]
+    pub set_auto_partitioning_strategy: Option<AutoPartitioningStrategy>,
+
+    #[builder(setter(strip_option), default)]
+    pub set_auto_partitioning_stabilization_window: Option<Duration>,
+
+    #[builder(setter(strip_option), default)]
+    pub set_auto_partitioning_up_utilization_percent: Option<i32>,
+
+    #[builder(setter(strip_option), default)]
+    pub set_auto_partitioning_down_utilization_percent: Option<i32>,
+
     #[builder(setter(strip_option), default)]
     pub set_retention_period: Option<Duration>,
 
@@ -213,6 +237,133 @@ impl TopicClient {
         Ok(())
     }
 
+    /// Sets the committed offset of the consumer for the topic partition.
+    ///
+    /// Offset may be moved backward as well as forward. Server restarts the read session which
+    /// currently reads the partition.
+    #[instrument(name = "ydb.TopicClient.CommitOffset", skip_all, fields(db.system.name = "ydb", ydb.topic.path = %path, ydb.consumer.name = %consumer))]
+    pub async fn commit_offset(
+        &mut self,
+        path: String,
+        partition_id: i64,
+        consumer: String,
+        offset: i64,
+    ) -> YdbResult<()> {
+        let req = RawCommitOffsetRequest {
+            operation_params: self.timeouts.operation_params(),
+            path,
+            partition_id,
+            consumer,
+            offset,
+        };
+
+        let mut service = self.raw_client_connection().await?;
+        service.commit_offset(req).await?;
+
+        Ok(())
+    }
+
+    /// Moves the committed offsets of the consumer to the target in every partition of the topic.
+    ///
+    /// Target offsets are computed from the consumer partition stats. For
+    /// [`ConsumerResetTarget::Timestamp`] partitions written after the timestamp are read without
+    /// consumer to find the first message offset, so consumer read sessions are not affected.
+    ///
+    /// Returns committed offsets by partition id.
+    #[instrument(name = "ydb.TopicClient.ResetConsumer", skip_all, fields(db.system.name = "ydb", ydb.topic.path = %path, ydb.consumer.name = %consumer))]
+    pub async fn reset_consumer(
+        &mut self,
+        path: String,
+        consumer: String,
+        to: ConsumerResetTarget,
+    ) -> YdbResult<HashMap<i64, i64>> {
+        let description = self
+            .describe_consumer(
+                path.clone(),
+                consumer.clone(),
+                DescribeConsumerOptions {
+                    include_stats: true,
+                    include_location: false,
+                },
+            )
+            .await?;
+
+        let offsets = match to {
+            ConsumerResetTarget::Earliest => description
+                .partitions
+                .iter()
+                .map(|partition| (partition.partition_id, partition.stats.start_offset))
+                .collect(),
+            ConsumerResetTarget::Latest => description
+                .partitions
+                .iter()
+                .map(|partition| (partition.partition_id, partition.stats.end_offset))
+                .collect(),
+            ConsumerResetTarget::Timestamp(time) => {
+                self.offsets_at(&path, &description.partitions, time)
+                    .await?
+            }
+            ConsumerResetTarget::Offsets(offsets) => offsets,
+        };
+
+        for (&partition_id, &offset) in &offsets {
+            self.commit_offset(path.clone(), partition_id, consumer.clone(), offset)
+                .await?;
+        }
+
+        Ok(offsets)
+    }
+
+    async fn offsets_at(
+        &mut self,
+        path: &str,
+        partitions: &[ConsumerPartitionInfo],
+        time: SystemTime,
+    ) -> YdbResult<HashMap<i64, i64>> {
+        let mut offsets = HashMap::new();
+        let mut unresolved = Vec::new();
+        for partition in partitions {
+            let stats = &partition.stats;
+            if stats.start_offset == stats.end_offset || stats.last_write_time < time {
+                offsets.insert(partition.partition_id, stats.end_offset);
+            } else {
+                unresolved.push(partition.partition_id);
+            }
+        }
+        if unresolved.is_empty() {
+            return Ok(offsets);
+        }
+
+        // Every unresolved partition has a message written after the timestamp,
+        // so the reader receives at least one message for each of them.
+        let selector = TopicSelector::builder()
+            .path(path)
+            .partition_ids(unresolved.clone())
+            .read_from(time)
+            .build();
+        let mut reader = self
+            .create_reader_with_params(
+                TopicReaderOptions::builder()
+                    .consumer("")
+                    .topic(selector)
+                    .build(),
+            )
+            .await?;
+
+        while !unresolved.is_empty() {
+            let batch = reader.read_batch().await?;
+            for message in batch.messages {
+                let partition_id = message.get_partition_id();
+                if let Some(index) = unresolved.iter().position(|&id| id == partition_id) {
+                    unresolved.swap_remove(index);
+                    offsets.insert(partition_id, message.offset);
+                }
+            }
+        }
+
+        Ok(offsets)
+    }
+
     #[instrument(name = "ydb.TopicClient.CreateReader", skip_all)]
     pub async fn create_reader(
         &mut self,
@@ -246,6 +397,109 @@ impl TopicClient {
         .await
     }
 
+    /// Creates a [`TopicConsumer`] which runs a handler for every read batch and commits
+    /// handled offsets.
+    ///
+    /// The dead-letter topic writer of [`crate::DeadLetterPolicy`] is created here as well.
+    #[instrument(name = "ydb.TopicClient.CreateConsumer", skip_all)]
+    pub async fn create_consumer(
+        &mut self,
+        mut options: TopicConsumerOptions,
+    ) -> YdbResult<TopicConsumer> {
+        options.reader_options.partition_session_events = true;
+        let dead_letter = match &options.dead_letter_policy {
+            Some(policy) => Some(DeadLetterWriter::new(
+                policy,
+                self.create_writer_with_params(policy.writer_options.clone())
+                    .await?,
+            )?),
+            None => None,
+        };
+        let reader = self
+            .create_reader_with_params(options.reader_options.clone())
+            .await?;
+        TopicConsumer::new(reader, options, dead_letter)
+    }
+
+    /// Reads messages of one partition without a consumer, from `from` up to the partition end
+    /// offset at the moment of the call.
+    ///
+    /// Nothing is committed and no consumer has to be registered on the topic, so the call
+    /// suits replay and debugging tools. The stream ends after the last message of the range,
+    /// when retention deleted the rest of the range, or after the first error.
+    #[instrument(name = "ydb.TopicClient.ReadPartitionRange", skip_all, fields(db.system.name = "ydb", ydb.topic.path = %path, ydb.topic.partition_id = partition_id))]
+    pub async fn read_partition_range(
+        &mut self,
+        path: String,
+        partition_id: i64,
+        from: impl Into<PartitionReadStart>,
+    ) -> YdbResult<impl Stream<Item = YdbResult<TopicReaderMessage>> + Send + 'static> {
+        let from = from.into();
+        let stats = self.partition_stats(path.clone(), partition_id).await?;
+
+        let (start_offset, read_from) = match from {
+            PartitionReadStart::Offset(offset) => (Some(offset), None),
+            PartitionReadStart::Time(time) if stats.last_write_time < time => {
+                (Some(stats.end_offset), None)
+            }
+            PartitionReadStart::Time(time) => (None, Some(time)),
+        };
+        if start_offset.is_some_and(|offset| offset >= stats.end_offset) {
+            return Ok(futures_util::stream::empty().left_stream());
+        }
+
+        let selector = TopicSelector::builder()
+            .path(path.clone())
+            .partition_ids(vec![partition_id])
+            .maybe_read_from(read_from)
+            .build();
+        let reader = self
+            .create_reader_with_params(
+                TopicReaderOptions::builder()
+                    .consumer("")
+                    .topic(selector)
+                    .partition_session_events(true)
+                    .build(),
+            )
+            .await?;
+
+        Ok(PartitionRangeReader::new(
+            reader,
+            self.clone(),
+            path,
+            partition_id,
+            start_offset,
+            stats.end_offset,
+        )?
+        .into_stream()
+        .right_stream())
+    }
+
+    pub(crate) async fn partition_stats(
+        &mut self,
+        path: String,
+        partition_id: i64,
+    ) -> YdbResult<PartitionStats> {
+        let description = self
+            .describe_topic(
+                path.clone(),
+                DescribeTopicOptions {
+                    include_stats: true,
+                    include_location: false,
+                },
+            )
+            .await?;
+        description
+            .partitions
+            .into_iter()
+            .find(|partition| partition.partition_id == partition_id)
+            .ok_or_else(|| {
+                YdbError::custom(format!("topic {path} has no partition {partition_id}"))
+            })?
+            .stats
+            .ok_or_else(|| YdbError::custom("describe topic returned no partition stats"))
+    }
+
     #[instrument(name = "ydb.TopicClient.CreateWriter", skip_all)]
     pub async fn create_writer_with_params(
         &mut self,
@@ -294,6 +548,16 @@ impl TopicClient {
         .await
     }
 
+    /// Creates a writer which routes every message to a partition by its key,
+    /// see [`KeyedTopicWriter`].
+    #[instrument(name = "ydb.TopicClient.CreateKeyedWriter", skip_all)]
+    pub async fn create_keyed_writer(
+        &mut self,
+        options: KeyedTopicWriterOptions,
+    ) -> YdbResult<KeyedTopicWriter> {
+        KeyedTopicWriter::new(options, self.clone()).await
+    }
+
     pub(crate) async fn raw_client_connection(
         &self,
     ) -> YdbResult<grpc_wrapper::raw_topic_service::client::RawTopicClient> {
@@ -65,3 +65,87 @@ impl CompressionDecoder for GzipDecoder {
         Codec::GZIP
     }
 }
+
+/// ZSTD encoder with a configurable compression level.
+///
+/// Registered by default with [`ZstdEncoder::DEFAULT_LEVEL`]; pass another instance to
+/// [`crate::TopicWriterOptionsBuilder::add_encoder`] to override the level for a writer.
+#[cfg(feature = "zstd")]
+#[derive(Debug, Clone)]
+pub struct ZstdEncoder {
+    level: i32,
+}
+
+#[cfg(feature = "zstd")]
+impl ZstdEncoder {
+    pub const DEFAULT_LEVEL: i32 = zstd::DEFAULT_COMPRESSION_LEVEL;
+
+    /// Creates an encoder for the given level, see `zstd::compression_level_range`
+    /// for accepted values.
+    pub fn new(level: i32) -> Self {
+        Self { level }
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl Default for ZstdEncoder {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_LEVEL)
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl CompressionEncoder for ZstdEncoder {
+    fn encode(&self, data: &[u8]) -> Result<Vec<u8>, Box<dyn Error + 'static>> {
+        Ok(zstd::bulk::compress(data, self.level)?)
+    }
+
+    fn codec(&self) -> Codec {
+        Codec::ZSTD
+    }
+}
+
+#[cfg(feature = "zstd")]
+#[derive(Debug)]
+pub(super) struct ZstdDecoder;
+
+#[cfg(feature = "zstd")]
+impl CompressionDecoder for ZstdDecoder {
+    fn decode(&self, data: &[u8]) -> Result<Vec<u8>, Box<dyn Error + 'static>> {
+        Ok(zstd::stream::decode_all(data)?)
+    }
+
+    fn codec(&self) -> Codec {
+        Codec::ZSTD
+    }
+}
+
+#[cfg(feature = "lzop")]
+#[derive(Debug)]
+pub(super) struct LzopEncoder;
+
+#[cfg(feature = "lzop")]
+impl CompressionEncoder for LzopEncoder {
+    fn encode(&self, data: &[u8]) -> Result<Vec<u8>, Box<dyn Error + 'static>> {
+        super::lzop::compress(data)
+    }
+
+    fn codec(&self) -> Codec {
+        Codec::LZOP
+    }
+}
+
+#[cfg(feature = "lzop")]
+#[derive(Debug)]
+pub(super) struct LzopDecoder;
+
+#[cfg(feature = "lzop")]
+impl CompressionDecoder for LzopDecoder {
+    fn decode(&self, data: &[u8]) -> Result<Vec<u8>, Box<dyn Error + 'static>> {
+        super::lzop::decompress(data)
+    }
+
+    fn codec(&self) -> Codec {
+        Codec::LZOP
+    }
+}
@@ -43,6 +43,16 @@ impl CodecRegistry {
         registry.register_decoder(Arc::new(RawDecoder));
         registry.register_encoder(Arc::new(GzipEncoder));
         registry.register_decoder(Arc::new(GzipDecoder));
+        #[cfg(feature = "zstd")]
+        {
+            registry.register_encoder(Arc::new(ZstdEncoder::default()));
+            registry.register_decoder(Arc::new(ZstdDecoder));
+        }
+        #[cfg(feature = "lzop")]
+        {
+            registry.register_encoder(Arc::new(LzopEncoder));
+            registry.register_decoder(Arc::new(LzopDecoder));
+        }
 
         registry
     }
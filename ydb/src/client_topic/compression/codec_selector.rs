@@ -202,6 +202,8 @@ async fn measure_codecs(
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(feature = "zstd")]
+    use crate::client_topic::compression::ZstdEncoder;
     use crate::test_integration_helper::InplaceExecutor;
 
     fn test_executor() -> Arc<dyn Executor> {
@@ -312,6 +314,40 @@ mod tests {
         assert!(selector.is_ok());
     }
 
+    #[cfg(all(feature = "zstd", feature = "lzop"))]
+    #[test]
+    fn selector_auto_empty_server_list_includes_zstd_and_lzop() {
+        let registry = CodecRegistry::new();
+        let selector = CodecSelector::new(
+            CodecSelection::Auto,
+            vec![],
+            registry.into(),
+            test_executor(),
+        )
+        .unwrap();
+
+        let CodecSelector::Auto(auto) = selector else {
+            panic!("expected auto selector");
+        };
+        let codecs: Vec<Codec> = auto.accepted_encoders.iter().map(|e| e.codec()).collect();
+        assert!(codecs.contains(&Codec::ZSTD));
+        assert!(codecs.contains(&Codec::LZOP));
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn zstd_encoder_level_roundtrip() {
+        let registry = CodecRegistry::new();
+        let decoder = registry.get_decoder(Codec::ZSTD).unwrap();
+        let data = b"zstd level roundtrip ".repeat(100);
+
+        for level in [1, ZstdEncoder::DEFAULT_LEVEL, 19] {
+            let encoded = ZstdEncoder::new(level).encode(&data).unwrap();
+            assert!(encoded.len() < data.len());
+            assert_eq!(decoder.decode(&encoded).unwrap(), data);
+        }
+    }
+
     #[test]
     fn selector_fixed_empty_server_list_allows_any_valid_codec() {
         let registry = CodecRegistry::new();
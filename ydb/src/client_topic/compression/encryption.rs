@@ -0,0 +1,328 @@
+//! Envelope encryption codec: payloads are encoded by an inner codec and then sealed with
+//! AES-256-GCM, so the server only stores ciphertext.
+//!
+//! Payload layout:
+//! `version: u8 | inner codec: i32 BE | key id length: u8 | key id | nonce: [u8; 12] | ciphertext + tag`.
+//! Everything before the nonce is authenticated as associated data.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, RwLock};
+
+use ring::aead::{AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use ring::rand::{SecureRandom, SystemRandom};
+
+use super::codec_registry::{CodecRegistry, CompressionDecoder, CompressionEncoder};
+use crate::{Codec, YdbError, YdbResult};
+
+const FORMAT_VERSION: u8 = 1;
+
+/// AES-256 key with its id. The id is stored with every encrypted payload.
+#[derive(Clone)]
+pub struct EncryptionKey {
+    id: String,
+    material: [u8; 32],
+}
+
+impl EncryptionKey {
+    /// Fails if the id is empty or longer than 255 bytes.
+    pub fn new(id: impl Into<String>, material: [u8; 32]) -> YdbResult<Self> {
+        let id = id.into();
+        if id.is_empty() || id.len() > u8::MAX as usize {
+            return Err(YdbError::custom(
+                "encryption key id must be 1 to 255 bytes long",
+            ));
+        }
+        Ok(Self { id, material })
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn aead_key(&self) -> Result<LessSafeKey, Box<dyn Error + 'static>> {
+        Ok(LessSafeKey::new(UnboundKey::new(
+            &AES_256_GCM,
+            &self.material,
+        )?))
+    }
+}
+
+impl Debug for EncryptionKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EncryptionKey")
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Source of encryption keys for [`EncryptionEncoder`] and [`EncryptionDecoder`].
+///
+/// Rotate keys by changing the current key: payloads written earlier keep their key id and
+/// are decrypted with [`KeyProvider::key`], so old keys must stay available while their
+/// messages are retained in the topic.
+pub trait KeyProvider: Debug + Send + Sync {
+    /// Key for new payloads.
+    fn current_key(&self) -> Result<EncryptionKey, Box<dyn Error + 'static>>;
+
+    /// Key with the id read from an encrypted payload.
+    fn key(&self, id: &str) -> Result<EncryptionKey, Box<dyn Error + 'static>>;
+}
+
+/// In-memory [`KeyProvider`].
+#[derive(Debug)]
+pub struct StaticKeyProvider {
+    state: RwLock<StaticKeys>,
+}
+
+#[derive(Debug)]
+struct StaticKeys {
+    current: String,
+    keys: HashMap<String, EncryptionKey>,
+}
+
+impl StaticKeyProvider {
+    pub fn new(current: EncryptionKey) -> Self {
+        let id = current.id.clone();
+        Self {
+            state: RwLock::new(StaticKeys {
+                current: id.clone(),
+                keys: HashMap::from([(id, current)]),
+            }),
+        }
+    }
+
+    /// Makes the key current. Previous keys stay available for decryption.
+    pub fn rotate(&self, key: EncryptionKey) {
+        let mut state = self.state.write().expect("static key provider lock");
+        state.current = key.id.clone();
+        state.keys.insert(key.id.clone(), key);
+    }
+
+    /// Adds a key for decryption only.
+    pub fn add(&self, key: EncryptionKey) {
+        let mut state = self.state.write().expect("static key provider lock");
+        state.keys.entry(key.id.clone()).or_insert(key);
+    }
+}
+
+impl KeyProvider for StaticKeyProvider {
+    fn current_key(&self) -> Result<EncryptionKey, Box<dyn Error + 'static>> {
+        let state = self.state.read().expect("static key provider lock");
+        Ok(state.keys[&state.current].clone())
+    }
+
+    fn key(&self, id: &str) -> Result<EncryptionKey, Box<dyn Error + 'static>> {
+        let state = self.state.read().expect("static key provider lock");
+        state
+            .keys
+            .get(id)
+            .cloned()
+            .ok_or_else(|| format!("encryption key {id} is unknown").into())
+    }
+}
+
+/// Encodes payloads with an inner codec and encrypts them with the current key.
+///
+/// Register it with [`crate::TopicWriterOptionsBuilder::add_encoder`] and select it with
+/// [`crate::CodecSelection::Fixed`]: automatic codec selection prefers smaller payloads and
+/// would never pick encryption.
+#[derive(Debug)]
+pub struct EncryptionEncoder {
+    codec: Codec,
+    inner: Arc<dyn CompressionEncoder>,
+    keys: Arc<dyn KeyProvider>,
+    random: SystemRandom,
+}
+
+impl EncryptionEncoder {
+    /// `codec` must be a custom codec; `inner` must be a codec built into the SDK,
+    /// e.g. [`Codec::ZSTD`] or [`Codec::RAW`].
+    pub fn new(codec: Codec, inner: Codec, keys: Arc<dyn KeyProvider>) -> YdbResult<Self> {
+        check_codecs(codec, inner)?;
+        let inner = CodecRegistry::new()
+            .get_encoder(inner)
+            .ok_or_else(|| YdbError::custom(format!("no built-in encoder for codec {inner:?}")))?;
+        Ok(Self {
+            codec,
+            inner,
+            keys,
+            random: SystemRandom::new(),
+        })
+    }
+}
+
+impl CompressionEncoder for EncryptionEncoder {
+    fn encode(&self, data: &[u8]) -> Result<Vec<u8>, Box<dyn Error + 'static>> {
+        let key = self.keys.current_key()?;
+        let mut output = header(self.inner.codec(), &key.id);
+        let header_len = output.len();
+
+        let mut nonce = [0u8; NONCE_LEN];
+        self.random.fill(&mut nonce)?;
+        output.extend_from_slice(&nonce);
+
+        let mut sealed = self.inner.encode(data)?;
+        key.aead_key()?.seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(&output[..header_len]),
+            &mut sealed,
+        )?;
+        output.extend_from_slice(&sealed);
+        Ok(output)
+    }
+
+    fn codec(&self) -> Codec {
+        self.codec
+    }
+}
+
+/// Decrypts payloads of [`EncryptionEncoder`] and decodes them with the inner codec.
+///
+/// Register it with [`crate::TopicReaderOptionsBuilder::add_decoder`].
+#[derive(Debug)]
+pub struct EncryptionDecoder {
+    codec: Codec,
+    inner: Arc<dyn CompressionDecoder>,
+    keys: Arc<dyn KeyProvider>,
+}
+
+impl EncryptionDecoder {
+    /// `codec` and `inner` must match the ones of the writer's [`EncryptionEncoder`].
+    pub fn new(codec: Codec, inner: Codec, keys: Arc<dyn KeyProvider>) -> YdbResult<Self> {
+        check_codecs(codec, inner)?;
+        let inner = CodecRegistry::new()
+            .get_decoder(inner)
+            .ok_or_else(|| YdbError::custom(format!("no built-in decoder for codec {inner:?}")))?;
+        Ok(Self { codec, inner, keys })
+    }
+}
+
+impl CompressionDecoder for EncryptionDecoder {
+    fn decode(&self, data: &[u8]) -> Result<Vec<u8>, Box<dyn Error + 'static>> {
+        let (&version, rest) = data.split_first().ok_or("encrypted payload is empty")?;
+        if version != FORMAT_VERSION {
+            return Err(format!("unsupported encrypted payload version {version}").into());
+        }
+        let (inner_code, rest) = rest
+            .split_first_chunk::<4>()
+            .ok_or("encrypted payload is truncated")?;
+        let inner_codec = Codec {
+            code: i32::from_be_bytes(*inner_code),
+        };
+        if inner_codec != self.inner.codec() {
+            return Err(format!(
+                "encrypted payload inner codec {inner_codec:?}, decoder expects {:?}",
+                self.inner.codec()
+            )
+            .into());
+        }
+        let (&id_len, rest) = rest.split_first().ok_or("encrypted payload is truncated")?;
+        let id = rest
+            .get(..id_len as usize)
+            .ok_or("encrypted payload is truncated")?;
+        let rest = &rest[id_len as usize..];
+        let (nonce, sealed) = rest
+            .split_first_chunk::<NONCE_LEN>()
+            .ok_or("encrypted payload is truncated")?;
+        let header_len = data.len() - rest.len();
+
+        let key = self.keys.key(std::str::from_utf8(id)?)?;
+        let mut sealed = sealed.to_vec();
+        let plain_len = key
+            .aead_key()?
+            .open_in_place(
+                Nonce::assume_unique_for_key(*nonce),
+                Aad::from(&data[..header_len]),
+                &mut sealed,
+            )?
+            .len();
+        sealed.truncate(plain_len);
+
+        self.inner.decode(&sealed)
+    }
+
+    fn codec(&self) -> Codec {
+        self.codec
+    }
+}
+
+fn check_codecs(codec: Codec, inner: Codec) -> YdbResult<()> {
+    if !codec.is_custom() {
+        return Err(YdbError::custom(format!(
+            "encryption codec {codec:?} must be in the custom codec range"
+        )));
+    }
+    if inner == codec {
+        return Err(YdbError::custom("encryption codec can't wrap itself"));
+    }
+    Ok(())
+}
+
+fn header(inner: Codec, key_id: &str) -> Vec<u8> {
+    let mut header = Vec::with_capacity(6 + key_id.len() + NONCE_LEN);
+    header.push(FORMAT_VERSION);
+    header.extend_from_slice(&inner.code.to_be_bytes());
+    header.push(key_id.len() as u8);
+    header.extend_from_slice(key_id.as_bytes());
+    header
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CODEC: Codec = Codec { code: 10_001 };
+
+    fn keys() -> Arc<StaticKeyProvider> {
+        Arc::new(StaticKeyProvider::new(
+            EncryptionKey::new("k1", [1; 32]).unwrap(),
+        ))
+    }
+
+    #[test]
+    fn roundtrip_hides_plaintext() {
+        let keys = keys();
+        let encoder = EncryptionEncoder::new(CODEC, Codec::GZIP, keys.clone()).unwrap();
+        let decoder = EncryptionDecoder::new(CODEC, Codec::GZIP, keys).unwrap();
+        let data = b"secret payload secret payload".to_vec();
+
+        let encrypted = encoder.encode(&data).unwrap();
+
+        assert!(!encrypted.windows(6).any(|window| window == b"secret"));
+        assert_eq!(decoder.decode(&encrypted).unwrap(), data);
+    }
+
+    #[test]
+    fn rotated_keys_decrypt_old_payloads() {
+        let keys = keys();
+        let encoder = EncryptionEncoder::new(CODEC, Codec::RAW, keys.clone()).unwrap();
+        let decoder = EncryptionDecoder::new(CODEC, Codec::RAW, keys.clone()).unwrap();
+
+        let old = encoder.encode(b"old").unwrap();
+        keys.rotate(EncryptionKey::new("k2", [2; 32]).unwrap());
+        let new = encoder.encode(b"new").unwrap();
+
+        assert_eq!(decoder.decode(&old).unwrap(), b"old");
+        assert_eq!(decoder.decode(&new).unwrap(), b"new");
+    }
+
+    #[test]
+    fn tampered_payload_is_rejected() {
+        let keys = keys();
+        let encoder = EncryptionEncoder::new(CODEC, Codec::RAW, keys.clone()).unwrap();
+        let decoder = EncryptionDecoder::new(CODEC, Codec::RAW, keys).unwrap();
+
+        let mut encrypted = encoder.encode(b"payload").unwrap();
+        let last = encrypted.len() - 1;
+        encrypted[last] ^= 1;
+
+        assert!(decoder.decode(&encrypted).is_err());
+    }
+
+    #[test]
+    fn codec_must_be_custom() {
+        assert!(EncryptionEncoder::new(Codec::GZIP, Codec::RAW, keys()).is_err());
+    }
+}
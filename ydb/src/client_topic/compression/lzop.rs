@@ -0,0 +1,247 @@
+//! Minimal lzop container on top of raw LZO1X blocks.
+//!
+//! YDB topic LZOP payloads are whole lzop files (as written by the Java SDK and the `lzop` tool),
+//! so the raw LZO1X stream has to be wrapped into the lzop header and block framing.
+
+use std::error::Error;
+
+const MAGIC: [u8; 9] = [0x89, b'L', b'Z', b'O', 0x00, b'\r', b'\n', 0x1a, b'\n'];
+
+const LZOP_VERSION: u16 = 0x1040;
+const LZO_LIB_VERSION: u16 = 0x2080;
+const LZOP_VERSION_NEEDED: u16 = 0x0940;
+
+// All of these methods produce LZO1X streams and share one decompressor.
+const M_LZO1X_1: u8 = 1;
+const M_LZO1X_1_15: u8 = 2;
+const M_LZO1X_999: u8 = 3;
+
+const F_ADLER32_D: u32 = 0x0000_0001;
+const F_ADLER32_C: u32 = 0x0000_0002;
+const F_H_EXTRA_FIELD: u32 = 0x0000_0040;
+const F_CRC32_D: u32 = 0x0000_0100;
+const F_CRC32_C: u32 = 0x0000_0200;
+const F_H_FILTER: u32 = 0x0000_0800;
+const F_H_CRC32: u32 = 0x0000_1000;
+
+// Same block size as the lzop tool uses.
+const BLOCK_SIZE: usize = 256 * 1024;
+
+type LzopResult<T> = Result<T, Box<dyn Error + 'static>>;
+
+pub(super) fn compress(data: &[u8]) -> LzopResult<Vec<u8>> {
+    let mut output = Vec::with_capacity(data.len() / 2 + 64);
+    output.extend_from_slice(&MAGIC);
+
+    let mut header = Vec::with_capacity(32);
+    header.extend_from_slice(&LZOP_VERSION.to_be_bytes());
+    header.extend_from_slice(&LZO_LIB_VERSION.to_be_bytes());
+    header.extend_from_slice(&LZOP_VERSION_NEEDED.to_be_bytes());
+    header.push(M_LZO1X_1);
+    header.push(5); // level
+    header.extend_from_slice(&F_ADLER32_D.to_be_bytes());
+    header.extend_from_slice(&0u32.to_be_bytes()); // mode
+    header.extend_from_slice(&0u32.to_be_bytes()); // mtime low
+    header.extend_from_slice(&0u32.to_be_bytes()); // mtime high
+    header.push(0); // file name length
+    output.extend_from_slice(&header);
+    output.extend_from_slice(&adler32(&header).to_be_bytes());
+
+    for block in data.chunks(BLOCK_SIZE) {
+        let compressed = lzokay_native::compress(block)?;
+        output.extend_from_slice(&(block.len() as u32).to_be_bytes());
+        if compressed.len() < block.len() {
+            output.extend_from_slice(&(compressed.len() as u32).to_be_bytes());
+            output.extend_from_slice(&adler32(block).to_be_bytes());
+            output.extend_from_slice(&compressed);
+        } else {
+            output.extend_from_slice(&(block.len() as u32).to_be_bytes());
+            output.extend_from_slice(&adler32(block).to_be_bytes());
+            output.extend_from_slice(block);
+        }
+    }
+    output.extend_from_slice(&0u32.to_be_bytes());
+
+    Ok(output)
+}
+
+pub(super) fn decompress(data: &[u8]) -> LzopResult<Vec<u8>> {
+    let mut reader = Reader { data, pos: 0 };
+
+    if reader.bytes(MAGIC.len())? != MAGIC {
+        return Err("lzop: bad magic".into());
+    }
+
+    let header_start = reader.pos;
+    let version = reader.u16()?;
+    let _lib_version = reader.u16()?;
+    if version >= 0x0940 {
+        let version_needed = reader.u16()?;
+        if version_needed > LZOP_VERSION {
+            return Err(format!("lzop: unsupported format version {version_needed:#x}").into());
+        }
+    }
+    let method = reader.u8()?;
+    if !matches!(method, M_LZO1X_1 | M_LZO1X_1_15 | M_LZO1X_999) {
+        return Err(format!("lzop: unsupported compression method {method}").into());
+    }
+    if version >= 0x0940 {
+        let _level = reader.u8()?;
+    }
+    let flags = reader.u32()?;
+    if flags & F_H_FILTER != 0 {
+        return Err("lzop: filters are not supported".into());
+    }
+    let _mode = reader.u32()?;
+    let _mtime_low = reader.u32()?;
+    if version >= 0x0940 {
+        let _mtime_high = reader.u32()?;
+    }
+    let name_len = reader.u8()? as usize;
+    reader.bytes(name_len)?;
+
+    let header = &data[header_start..reader.pos];
+    let expected = reader.u32()?;
+    let actual = if flags & F_H_CRC32 != 0 {
+        crc32(header)
+    } else {
+        adler32(header)
+    };
+    if expected != actual {
+        return Err("lzop: header checksum mismatch".into());
+    }
+
+    if flags & F_H_EXTRA_FIELD != 0 {
+        let extra_len = reader.u32()? as usize;
+        reader.bytes(extra_len)?;
+        let _extra_checksum = reader.u32()?;
+    }
+
+    let mut output = Vec::new();
+    loop {
+        let uncompressed_len = reader.u32()? as usize;
+        if uncompressed_len == 0 {
+            break;
+        }
+        let compressed_len = reader.u32()? as usize;
+        if compressed_len > uncompressed_len {
+            return Err("lzop: compressed block is larger than uncompressed".into());
+        }
+
+        let adler32_d = (flags & F_ADLER32_D != 0)
+            .then(|| reader.u32())
+            .transpose()?;
+        let crc32_d = (flags & F_CRC32_D != 0).then(|| reader.u32()).transpose()?;
+        if compressed_len < uncompressed_len {
+            if flags & F_ADLER32_C != 0 {
+                reader.u32()?;
+            }
+            if flags & F_CRC32_C != 0 {
+                reader.u32()?;
+            }
+        }
+
+        let block = reader.bytes(compressed_len)?;
+        let block = if compressed_len < uncompressed_len {
+            lzokay_native::decompress_all(block, Some(uncompressed_len))?
+        } else {
+            block.to_vec()
+        };
+        if block.len() != uncompressed_len {
+            return Err("lzop: block size mismatch".into());
+        }
+        if adler32_d.is_some_and(|checksum| checksum != adler32(&block))
+            || crc32_d.is_some_and(|checksum| checksum != crc32(&block))
+        {
+            return Err("lzop: block checksum mismatch".into());
+        }
+
+        output.extend_from_slice(&block);
+    }
+
+    Ok(output)
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> LzopResult<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.data.len())
+            .ok_or("lzop: unexpected end of data")?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> LzopResult<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> LzopResult<u16> {
+        Ok(u16::from_be_bytes(self.bytes(2)?.try_into()?))
+    }
+
+    fn u32(&mut self) -> LzopResult<u32> {
+        Ok(u32::from_be_bytes(self.bytes(4)?.try_into()?))
+    }
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    const MOD_ADLER: u32 = 65521;
+    // Largest chunk which cannot overflow the u32 sums before the modulo.
+    const NMAX: usize = 5552;
+
+    let (mut a, mut b) = (1u32, 0u32);
+    for chunk in data.chunks(NMAX) {
+        for &byte in chunk {
+            a += byte as u32;
+            b += a;
+        }
+        a %= MOD_ADLER;
+        b %= MOD_ADLER;
+    }
+    (b << 16) | a
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = flate2::Crc::new();
+    crc.update(data);
+    crc.sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adler32_known_value() {
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+    }
+
+    #[test]
+    fn roundtrip() {
+        let compressible = b"ydb topic lzop ".repeat(50_000);
+        let incompressible: Vec<u8> = (0..1000u32)
+            .map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8)
+            .collect();
+
+        for data in [&b""[..], &compressible, &incompressible] {
+            let compressed = compress(data).unwrap();
+            assert_eq!(decompress(&compressed).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn rejects_corrupted_block() {
+        let mut compressed = compress(b"hello, lzop").unwrap();
+        let last_data_byte = compressed.len() - 5;
+        compressed[last_data_byte] ^= 0xff;
+        assert!(decompress(&compressed).is_err());
+    }
+}
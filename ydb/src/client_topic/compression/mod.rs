@@ -2,17 +2,27 @@ mod builtin_codecs;
 mod codec_registry;
 mod codec_selector;
 mod compression_worker;
+#[cfg(feature = "encryption")]
+mod encryption;
 mod executor;
+#[cfg(feature = "lzop")]
+mod lzop;
 mod ordered_task_queue;
 
 pub(crate) const MAX_MESSAGES_PER_CHUNK: usize = 100;
 pub(crate) const OUTPUT_BACKLOG_PER_TASK: std::num::NonZeroUsize =
     const { std::num::NonZeroUsize::new(4).unwrap() };
 
+#[cfg(feature = "zstd")]
+pub use builtin_codecs::ZstdEncoder;
 pub(crate) use codec_registry::CodecRegistry;
 pub use codec_registry::{CompressionDecoder, CompressionEncoder};
 pub use codec_selector::CodecSelection;
 pub(crate) use compression_worker::CompressionWorker;
+#[cfg(feature = "encryption")]
+pub use encryption::{
+    EncryptionDecoder, EncryptionEncoder, EncryptionKey, KeyProvider, StaticKeyProvider,
+};
 pub use executor::Executor;
 #[cfg(test)]
 pub(crate) use executor::RayonExecutor;
@@ -4,7 +4,9 @@ use crate::grpc_wrapper::raw_topic_service::common::metering_mode::RawMeteringMo
 use crate::grpc_wrapper::raw_topic_service::common::partition::{
     RawPartitionInfo, RawPartitionLocation, RawPartitionStats,
 };
-use crate::grpc_wrapper::raw_topic_service::common::partitioning_settings::RawPartitioningSettings;
+use crate::grpc_wrapper::raw_topic_service::common::partitioning_settings::{
+    RawAutoPartitioningSettings, RawAutoPartitioningStrategy, RawPartitioningSettings,
+};
 use crate::grpc_wrapper::raw_topic_service::common::topic::RawTopicStats;
 use crate::grpc_wrapper::raw_topic_service::describe_topic::RawDescribeTopicResult;
 use derive_builder::Builder;
@@ -168,6 +170,7 @@ impl From<AlterConsumer> for RawAlterConsumer {
 pub struct PartitioningSettings {
     pub min_active_partitions: i64,
     pub partition_count_limit: i64,
+    pub auto_partitioning_settings: Option<AutoPartitioningSettings>,
 }
 
 impl From<RawPartitioningSettings> for PartitioningSettings {
@@ -175,6 +178,7 @@ impl From<RawPartitioningSettings> for PartitioningSettings {
         Self {
             min_active_partitions: value.min_active_partitions,
             partition_count_limit: value.partition_count_limit,
+            auto_partitioning_settings: value.auto_partitioning_settings.map(Into::into),
         }
     }
 }
@@ -184,6 +188,90 @@ impl From<PartitioningSettings> for RawPartitioningSettings {
         Self {
             min_active_partitions: value.min_active_partitions,
             partition_count_limit: value.partition_count_limit,
+            auto_partitioning_settings: value.auto_partitioning_settings.map(Into::into),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(feature = "force-exhaustive-all"), non_exhaustive)]
+pub enum AutoPartitioningStrategy {
+    Disabled,
+    /// Split partitions under write load, never merge them.
+    ScaleUp,
+    /// Split partitions under write load and merge them when the load goes down.
+    ScaleUpAndDown,
+    /// Keep current partitions, autopartitioning may be resumed later.
+    Paused,
+}
+
+impl From<RawAutoPartitioningStrategy> for Option<AutoPartitioningStrategy> {
+    fn from(value: RawAutoPartitioningStrategy) -> Self {
+        match value {
+            RawAutoPartitioningStrategy::Unspecified => None,
+            RawAutoPartitioningStrategy::Disabled => Some(AutoPartitioningStrategy::Disabled),
+            RawAutoPartitioningStrategy::ScaleUp => Some(AutoPartitioningStrategy::ScaleUp),
+            RawAutoPartitioningStrategy::ScaleUpAndDown => {
+                Some(AutoPartitioningStrategy::ScaleUpAndDown)
+            }
+            RawAutoPartitioningStrategy::Paused => Some(AutoPartitioningStrategy::Paused),
+        }
+    }
+}
+
+impl From<Option<AutoPartitioningStrategy>> for RawAutoPartitioningStrategy {
+    fn from(value: Option<AutoPartitioningStrategy>) -> Self {
+        match value {
+            None => RawAutoPartitioningStrategy::Unspecified,
+            Some(AutoPartitioningStrategy::Disabled) => RawAutoPartitioningStrategy::Disabled,
+            Some(AutoPartitioningStrategy::ScaleUp) => RawAutoPartitioningStrategy::ScaleUp,
+            Some(AutoPartitioningStrategy::ScaleUpAndDown) => {
+                RawAutoPartitioningStrategy::ScaleUpAndDown
+            }
+            Some(AutoPartitioningStrategy::Paused) => RawAutoPartitioningStrategy::Paused,
+        }
+    }
+}
+
+/// Partition is split when its write speed is above `up_utilization_percent` of
+/// the partition write speed limit for `stabilization_window`, and merged when it is
+/// below `down_utilization_percent`.
+///
+/// Zero values and None mean server defaults.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Builder)]
+#[builder(build_fn(error = "crate::errors::YdbError"))]
+pub struct AutoPartitioningSettings {
+    #[builder(setter(strip_option), default)]
+    pub strategy: Option<AutoPartitioningStrategy>,
+
+    #[builder(setter(strip_option), default)]
+    pub stabilization_window: Option<std::time::Duration>,
+
+    #[builder(default)]
+    pub up_utilization_percent: i32,
+
+    #[builder(default)]
+    pub down_utilization_percent: i32,
+}
+
+impl From<RawAutoPartitioningSettings> for AutoPartitioningSettings {
+    fn from(value: RawAutoPartitioningSettings) -> Self {
+        Self {
+            strategy: value.strategy.into(),
+            stabilization_window: value.stabilization_window.map(Into::into),
+            up_utilization_percent: value.up_utilization_percent,
+            down_utilization_percent: value.down_utilization_percent,
+        }
+    }
+}
+
+impl From<AutoPartitioningSettings> for RawAutoPartitioningSettings {
+    fn from(value: AutoPartitioningSettings) -> Self {
+        Self {
+            strategy: value.strategy.into(),
+            stabilization_window: value.stabilization_window.map(Into::into),
+            up_utilization_percent: value.up_utilization_percent,
+            down_utilization_percent: value.down_utilization_percent,
         }
     }
 }
@@ -524,3 +612,17 @@ impl From<crate::grpc_wrapper::raw_topic_service::describe_consumer::RawDescribe
         }
     }
 }
+
+/// Target position for [`crate::TopicClient::reset_consumer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "force-exhaustive-all"), non_exhaustive)]
+pub enum ConsumerResetTarget {
+    /// First offset still stored in the partition.
+    Earliest,
+    /// Offset after the last written message, all messages are skipped.
+    Latest,
+    /// First message written at or after the given time.
+    Timestamp(SystemTime),
+    /// Explicit offsets by partition id. Partitions not listed are left untouched.
+    Offsets(HashMap<i64, i64>),
+}
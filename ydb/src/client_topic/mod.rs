@@ -1,5 +1,6 @@
 pub(crate) mod client;
 pub(crate) mod compression;
 pub(crate) mod list_types;
+pub(crate) mod payload_format;
 pub(crate) mod topicreader;
 pub(crate) mod topicwriter;
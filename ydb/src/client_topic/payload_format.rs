@@ -0,0 +1,149 @@
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::{YdbError, YdbResult};
+
+/// Metadata key of the payload content type, written by [`crate::TypedTopicWriter`].
+pub const CONTENT_TYPE_METADATA_KEY: &str = "content-type";
+/// Metadata key of the payload schema version, decimal, written by [`crate::TypedTopicWriter`].
+pub const SCHEMA_VERSION_METADATA_KEY: &str = "schema-version";
+
+/// Payload format of typed topic messages.
+pub trait PayloadFormat: Send + Sync {
+    /// Stored in the `content-type` metadata item and checked on read.
+    fn content_type(&self) -> &str;
+}
+
+/// Serializes values of `T` for [`crate::TypedTopicWriter`].
+pub trait PayloadEncoder<T>: PayloadFormat {
+    fn encode(&self, value: &T) -> YdbResult<Vec<u8>>;
+}
+
+/// Deserializes values of `T` for [`crate::TypedTopicReader`].
+pub trait PayloadDecoder<T>: PayloadFormat {
+    fn decode(&self, data: &[u8]) -> YdbResult<T>;
+}
+
+/// JSON through `serde_json`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct JsonPayload;
+
+impl PayloadFormat for JsonPayload {
+    fn content_type(&self) -> &str {
+        "application/json"
+    }
+}
+
+impl<T: Serialize> PayloadEncoder<T> for JsonPayload {
+    fn encode(&self, value: &T) -> YdbResult<Vec<u8>> {
+        serde_json::to_vec(value).map_err(|err| YdbError::Convert(err.to_string()))
+    }
+}
+
+impl<T: DeserializeOwned> PayloadDecoder<T> for JsonPayload {
+    fn decode(&self, data: &[u8]) -> YdbResult<T> {
+        serde_json::from_slice(data).map_err(|err| YdbError::Convert(err.to_string()))
+    }
+}
+
+/// MessagePack through `rmp-serde`, structs are written as maps with field names.
+#[cfg(feature = "msgpack")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MessagePackPayload;
+
+#[cfg(feature = "msgpack")]
+impl PayloadFormat for MessagePackPayload {
+    fn content_type(&self) -> &str {
+        "application/msgpack"
+    }
+}
+
+#[cfg(feature = "msgpack")]
+impl<T: Serialize> PayloadEncoder<T> for MessagePackPayload {
+    fn encode(&self, value: &T) -> YdbResult<Vec<u8>> {
+        rmp_serde::to_vec_named(value).map_err(|err| YdbError::Convert(err.to_string()))
+    }
+}
+
+#[cfg(feature = "msgpack")]
+impl<T: DeserializeOwned> PayloadDecoder<T> for MessagePackPayload {
+    fn decode(&self, data: &[u8]) -> YdbResult<T> {
+        rmp_serde::from_slice(data).map_err(|err| YdbError::Convert(err.to_string()))
+    }
+}
+
+/// Protobuf messages generated by `prost`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ProtobufPayload;
+
+impl PayloadFormat for ProtobufPayload {
+    fn content_type(&self) -> &str {
+        "application/x-protobuf"
+    }
+}
+
+impl<T: prost::Message> PayloadEncoder<T> for ProtobufPayload {
+    fn encode(&self, value: &T) -> YdbResult<Vec<u8>> {
+        Ok(value.encode_to_vec())
+    }
+}
+
+impl<T: prost::Message + Default> PayloadDecoder<T> for ProtobufPayload {
+    fn decode(&self, data: &[u8]) -> YdbResult<T> {
+        T::decode(data).map_err(|err| YdbError::Convert(err.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Event {
+        id: u64,
+        name: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct ProtoEvent {
+        #[prost(uint64, tag = "1")]
+        id: u64,
+    }
+
+    fn roundtrip<T, F: PayloadEncoder<T> + PayloadDecoder<T>>(format: F, value: &T) -> T {
+        let data = format.encode(value).unwrap();
+        format.decode(&data).unwrap()
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let event = Event {
+            id: 1,
+            name: "created".to_string(),
+        };
+        assert_eq!(roundtrip(JsonPayload, &event), event);
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn msgpack_roundtrip() {
+        let event = Event {
+            id: 1,
+            name: "created".to_string(),
+        };
+        assert_eq!(roundtrip(MessagePackPayload, &event), event);
+    }
+
+    #[test]
+    fn protobuf_roundtrip() {
+        let event = ProtoEvent { id: 42 };
+        assert_eq!(roundtrip(ProtobufPayload, &event), event);
+    }
+
+    #[test]
+    fn invalid_payload_is_convert_error() {
+        let result: YdbResult<Event> = JsonPayload.decode(b"not json");
+        assert!(matches!(result, Err(YdbError::Convert(_))));
+    }
+}
@@ -15,7 +15,7 @@ use crate::{YdbError, YdbResult};
 
 use super::reconnector;
 
-const UPDATE_TOKEN_INTERVAL: Duration = Duration::from_secs(3600);
+pub(super) const UPDATE_TOKEN_INTERVAL: Duration = Duration::from_secs(3600);
 
 pub(super) struct AuthTokenSender {
     token_cache: TokenCache,
@@ -0,0 +1,555 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore, mpsc};
+use tokio::task::{JoinHandle, JoinSet};
+use tokio::time::MissedTickBehavior;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, instrument, warn};
+
+use crate::client_topic::topicreader::dead_letter::{DeadLetterPolicy, DeadLetterWriter};
+use crate::client_topic::topicreader::ids::PartitionSessionId;
+use crate::client_topic::topicreader::messages::TopicReaderBatch;
+use crate::client_topic::topicreader::partition_events::{
+    PartitionSessionEvent, PartitionSessionEvents,
+};
+use crate::client_topic::topicreader::reader::{TopicReader, TopicReaderCommitMarker};
+use crate::client_topic::topicreader::reader_options::TopicReaderOptions;
+use crate::client_topic::topicreader::runtime::RuntimeHandle;
+use crate::{YdbError, YdbResult};
+
+#[derive(bon::Builder, Clone)]
+pub struct TopicConsumerOptions {
+    /// Options of the underlying reader. Partition session events are always enabled,
+    /// the consumer handles them itself.
+    pub(crate) reader_options: TopicReaderOptions,
+
+    /// Maximum number of handlers running at the same time.
+    /// Batches of one partition are always handled one by one.
+    #[builder(default = 8)]
+    pub(crate) max_concurrent_handlers: usize,
+
+    /// Maximum number of read batches waiting for a handler or being handled.
+    #[builder(default = 64)]
+    pub(crate) max_pending_batches: usize,
+
+    /// How often offsets of handled batches are committed.
+    #[builder(default = Duration::from_secs(1))]
+    pub(crate) commit_interval: Duration,
+
+    /// How many times a failed handler is retried with the same batch before the consumer stops
+    /// with the handler error.
+    #[builder(default = 3)]
+    pub(crate) handler_retries: usize,
+
+    /// Delay before the first handler retry, doubled for every next retry.
+    #[builder(default = Duration::from_millis(100))]
+    pub(crate) handler_retry_backoff: Duration,
+
+    /// Where batches go when all handler attempts failed. Without a policy the consumer stops
+    /// with the handler error.
+    pub(crate) dead_letter_policy: Option<DeadLetterPolicy>,
+}
+
+/// Runs an async handler for every batch read from the topic.
+///
+/// Batches of different partitions are handled concurrently, batches of one partition keep their
+/// order. Offsets of handled batches are committed every `commit_interval`.
+///
+/// Every handler gets a [`CancellationToken`] which is cancelled when the partition is revoked
+/// from the reader; the batch will be re-delivered to another reader unless it is already
+/// committed.
+pub struct TopicConsumer {
+    reader: TopicReader,
+    events: PartitionSessionEvents,
+    options: TopicConsumerOptions,
+    dead_letter: Option<DeadLetterWriter>,
+}
+
+type PartitionKey = (String, i64);
+
+struct PartitionWorker {
+    partition_session_id: PartitionSessionId,
+    batches: mpsc::UnboundedSender<(TopicReaderBatch, OwnedSemaphorePermit)>,
+    // Cancels handlers. Handled offsets are still committed on graceful release.
+    revoked: CancellationToken,
+    // Parent of `revoked`: the session is gone, its handled offsets are dropped.
+    retired: CancellationToken,
+    task: JoinHandle<()>,
+}
+
+// Commit markers of handled, but not committed yet batches.
+#[derive(Default)]
+struct HandledOffsets {
+    markers: Mutex<HashMap<PartitionKey, TopicReaderCommitMarker>>,
+}
+
+impl HandledOffsets {
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<PartitionKey, TopicReaderCommitMarker>> {
+        self.markers.lock().expect("topic consumer offsets lock")
+    }
+
+    // Checked under the lock, so a retired worker can't overwrite offsets of the new session.
+    fn record(
+        &self,
+        key: PartitionKey,
+        marker: TopicReaderCommitMarker,
+        retired: &CancellationToken,
+    ) {
+        let mut markers = self.lock();
+        if retired.is_cancelled() {
+            return;
+        }
+        match markers.get_mut(&key) {
+            Some(pending) if pending.partition_session_id == marker.partition_session_id => {
+                pending.end_offset = marker.end_offset;
+            }
+            _ => {
+                markers.insert(key, marker);
+            }
+        }
+    }
+
+    fn take(
+        &self,
+        key: &PartitionKey,
+        partition_session_id: PartitionSessionId,
+    ) -> Option<TopicReaderCommitMarker> {
+        let mut markers = self.lock();
+        if markers.get(key)?.partition_session_id != partition_session_id {
+            return None;
+        }
+        markers.remove(key)
+    }
+
+    fn take_all(&self) -> Vec<TopicReaderCommitMarker> {
+        self.lock().drain().map(|(_, marker)| marker).collect()
+    }
+
+    fn remove(&self, key: &PartitionKey) {
+        self.lock().remove(key);
+    }
+}
+
+struct WorkerContext<H> {
+    handler: H,
+    handler_retries: usize,
+    handler_retry_backoff: Duration,
+    dead_letter: Option<DeadLetterWriter>,
+    running_handlers: Semaphore,
+    handled: Arc<HandledOffsets>,
+    shutdown: CancellationToken,
+    errors: mpsc::UnboundedSender<YdbError>,
+}
+
+impl<H, F> WorkerContext<H>
+where
+    H: Fn(TopicReaderBatch, CancellationToken) -> F,
+    F: Future<Output = YdbResult<()>>,
+{
+    async fn handle(&self, batch: TopicReaderBatch, revoked: &CancellationToken) -> YdbResult<()> {
+        let Some(dead_letter) = &self.dead_letter else {
+            return self
+                .call_with_retries(
+                    batch,
+                    self.handler_retries + 1,
+                    self.handler_retry_backoff,
+                    revoked,
+                )
+                .await;
+        };
+
+        let err = match self
+            .call_with_retries(
+                batch.clone(),
+                dead_letter.max_attempts,
+                dead_letter.backoff,
+                revoked,
+            )
+            .await
+        {
+            Ok(()) => return Ok(()),
+            Err(err) if revoked.is_cancelled() || self.shutdown.is_cancelled() => return Err(err),
+            Err(err) => err,
+        };
+
+        warn!(
+            partition_id = batch.partition_id(),
+            messages = batch.messages.len(),
+            "topic consumer handler failed, writing batch to dead letter topic: {err}"
+        );
+        dead_letter.write(&batch, &err).await
+    }
+
+    async fn call_with_retries(
+        &self,
+        batch: TopicReaderBatch,
+        attempts: usize,
+        mut backoff: Duration,
+        revoked: &CancellationToken,
+    ) -> YdbResult<()> {
+        let mut attempt = 1;
+        loop {
+            if attempt >= attempts {
+                return (self.handler)(batch, revoked.clone()).await;
+            }
+            let err = match (self.handler)(batch.clone(), revoked.clone()).await {
+                Ok(()) => return Ok(()),
+                Err(err) => err,
+            };
+
+            warn!(
+                attempt,
+                partition_id = batch.partition_id(),
+                "topic consumer handler failed, retrying: {err}"
+            );
+            attempt += 1;
+            tokio::select! {
+                _ = revoked.cancelled() => return Err(err),
+                _ = self.shutdown.cancelled() => return Err(err),
+                _ = tokio::time::sleep(backoff) => {}
+            }
+            backoff = backoff.saturating_mul(2);
+        }
+    }
+
+    async fn run_partition(
+        &self,
+        key: PartitionKey,
+        mut batches: mpsc::UnboundedReceiver<(TopicReaderBatch, OwnedSemaphorePermit)>,
+        retired: CancellationToken,
+        revoked: CancellationToken,
+    ) {
+        loop {
+            let (batch, _pending) = tokio::select! {
+                biased;
+                _ = revoked.cancelled() => return,
+                _ = self.shutdown.cancelled() => return,
+                next = batches.recv() => match next {
+                    Some(next) => next,
+                    None => return,
+                },
+            };
+            let _running = tokio::select! {
+                biased;
+                _ = revoked.cancelled() => return,
+                _ = self.shutdown.cancelled() => return,
+                permit = self.running_handlers.acquire() => match permit {
+                    Ok(permit) => permit,
+                    Err(_) => return,
+                },
+            };
+
+            let marker = batch.get_commit_marker();
+            match self.handle(batch, &revoked).await {
+                Ok(()) => self.handled.record(key.clone(), marker, &retired),
+                Err(err) if revoked.is_cancelled() || self.shutdown.is_cancelled() => {
+                    debug!("topic consumer handler failed after partition release: {err}");
+                    return;
+                }
+                Err(err) => {
+                    let _ = self.errors.send(err);
+                    return;
+                }
+            }
+        }
+    }
+}
+
+impl TopicConsumer {
+    pub(crate) fn new(
+        reader: TopicReader,
+        options: TopicConsumerOptions,
+        dead_letter: Option<DeadLetterWriter>,
+    ) -> YdbResult<Self> {
+        if options.max_concurrent_handlers == 0 || options.max_pending_batches == 0 {
+            return Err(YdbError::custom(
+                "topic consumer max_concurrent_handlers and max_pending_batches must be positive",
+            ));
+        }
+        let events = reader.partition_session_events()?;
+        Ok(Self {
+            reader,
+            events,
+            options,
+            dead_letter,
+        })
+    }
+
+    /// Reads the topic and runs `handler` for every batch until `cancellation` is cancelled.
+    ///
+    /// On cancellation running handlers are awaited, batches waiting for a handler are dropped
+    /// to be re-delivered later, offsets of handled batches are committed and the reader is
+    /// closed.
+    ///
+    /// # Errors
+    ///
+    /// Returns the reader error, the handler error after all retries failed and no
+    /// [`DeadLetterPolicy`] is set, or the dead-letter topic write error. Offsets of batches
+    /// handled before the error are still committed.
+    #[instrument(name = "ydb.TopicConsumer.Run", skip_all, fields(db.system.name = "ydb"), err)]
+    pub async fn run<H, F>(self, handler: H, cancellation: CancellationToken) -> YdbResult<()>
+    where
+        H: Fn(TopicReaderBatch, CancellationToken) -> F + Send + Sync + 'static,
+        F: Future<Output = YdbResult<()>> + Send + 'static,
+    {
+        let Self {
+            mut reader,
+            mut events,
+            options,
+            dead_letter,
+        } = self;
+        let runtime = reader.runtime_handle();
+        let pending_batches = Arc::new(Semaphore::new(options.max_pending_batches));
+        let (errors_tx, mut errors_rx) = mpsc::unbounded_channel();
+        let ctx = Arc::new(WorkerContext {
+            handler,
+            handler_retries: options.handler_retries,
+            handler_retry_backoff: options.handler_retry_backoff,
+            dead_letter,
+            running_handlers: Semaphore::new(options.max_concurrent_handlers),
+            handled: Arc::new(HandledOffsets::default()),
+            shutdown: CancellationToken::new(),
+            errors: errors_tx,
+        });
+
+        let mut workers: HashMap<PartitionKey, PartitionWorker> = HashMap::new();
+        let mut releases = JoinSet::new();
+        let mut commit_interval = tokio::time::interval(options.commit_interval);
+        commit_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        let result = loop {
+            tokio::select! {
+                biased;
+
+                _ = cancellation.cancelled() => break Ok(()),
+
+                Some(err) = errors_rx.recv() => break Err(err),
+
+                event = events.recv() => match event {
+                    Ok(event) => handle_event(event, &mut workers, &mut releases, &ctx.handled, &runtime),
+                    Err(err) => break Err(err),
+                },
+
+                _ = commit_interval.tick() => commit(&runtime, ctx.handled.take_all()),
+
+                batch = next_batch(&mut reader, &pending_batches) => match batch {
+                    Ok((batch, pending)) => dispatch(&ctx, &mut workers, batch, pending),
+                    Err(err) => break Err(err),
+                },
+            }
+        };
+
+        ctx.shutdown.cancel();
+        for worker in workers.into_values() {
+            if let Err(err) = worker.task.await {
+                warn!("topic consumer partition worker failed: {err}");
+            }
+        }
+        while releases.join_next().await.is_some() {}
+
+        commit(&runtime, ctx.handled.take_all());
+        let closed = reader.close(true).await;
+
+        // Workers are finished, so the context is not shared anymore.
+        let dead_letter_stopped = match Arc::try_unwrap(ctx).ok().and_then(|ctx| ctx.dead_letter) {
+            Some(dead_letter) => dead_letter.writer.stop().await,
+            None => Ok(()),
+        };
+
+        result.and(closed).and(dead_letter_stopped)
+    }
+}
+
+async fn next_batch(
+    reader: &mut TopicReader,
+    pending_batches: &Arc<Semaphore>,
+) -> YdbResult<(TopicReaderBatch, OwnedSemaphorePermit)> {
+    let pending = pending_batches
+        .clone()
+        .acquire_owned()
+        .await
+        .map_err(|_| YdbError::custom("topic consumer pending batches semaphore is closed"))?;
+    let batch = reader.read_batch().await?;
+    Ok((batch, pending))
+}
+
+fn dispatch<H, F>(
+    ctx: &Arc<WorkerContext<H>>,
+    workers: &mut HashMap<PartitionKey, PartitionWorker>,
+    batch: TopicReaderBatch,
+    pending: OwnedSemaphorePermit,
+) where
+    H: Fn(TopicReaderBatch, CancellationToken) -> F + Send + Sync + 'static,
+    F: Future<Output = YdbResult<()>> + Send + 'static,
+{
+    let marker = batch.get_commit_marker();
+    let key = (marker.topic, marker.partition_id.into_raw());
+
+    let current = workers
+        .get(&key)
+        .is_some_and(|worker| worker.partition_session_id == marker.partition_session_id);
+    if !current {
+        // Batches of a new partition session: the previous one is gone.
+        if let Some(previous) = workers.remove(&key) {
+            previous.retired.cancel();
+        }
+
+        let (batches_tx, batches_rx) = mpsc::unbounded_channel();
+        let retired = CancellationToken::new();
+        let revoked = retired.child_token();
+        let task = tokio::spawn({
+            let ctx = ctx.clone();
+            let key = key.clone();
+            let retired = retired.clone();
+            let revoked = revoked.clone();
+            async move { ctx.run_partition(key, batches_rx, retired, revoked).await }
+        });
+        workers.insert(
+            key.clone(),
+            PartitionWorker {
+                partition_session_id: marker.partition_session_id,
+                batches: batches_tx,
+                revoked,
+                retired,
+                task,
+            },
+        );
+    }
+
+    if let Some(worker) = workers.get(&key) {
+        // Fails only if the worker stopped after an error, the consumer is stopping then.
+        let _ = worker.batches.send((batch, pending));
+    }
+}
+
+fn handle_event(
+    event: PartitionSessionEvent,
+    workers: &mut HashMap<PartitionKey, PartitionWorker>,
+    releases: &mut JoinSet<()>,
+    handled: &Arc<HandledOffsets>,
+    runtime: &RuntimeHandle,
+) {
+    match event {
+        PartitionSessionEvent::Started(started) => {
+            if let Err(err) = started.confirm() {
+                debug!("topic consumer failed to confirm partition start: {err}");
+            }
+        }
+        PartitionSessionEvent::Stopping(stopping) => {
+            let key = (stopping.topic().to_string(), stopping.partition_id());
+            let worker = workers.remove(&key);
+            if let Some(worker) = &worker {
+                worker.revoked.cancel();
+            }
+            if !stopping.is_graceful() {
+                if let Some(worker) = &worker {
+                    worker.retired.cancel();
+                }
+                handled.remove(&key);
+                return;
+            }
+
+            // Commit what the worker has handled before the partition is released.
+            let handled = handled.clone();
+            let runtime = runtime.clone();
+            releases.spawn(async move {
+                if let Some(worker) = worker {
+                    let partition_session_id = worker.partition_session_id;
+                    let _ = worker.task.await;
+                    commit(&runtime, handled.take(&key, partition_session_id));
+                }
+                if let Err(err) = stopping.confirm() {
+                    debug!("topic consumer failed to confirm partition stop: {err}");
+                }
+            });
+        }
+        PartitionSessionEvent::Ended(ended) => {
+            let key = (ended.topic().to_string(), ended.partition_id());
+            if let Some(worker) = workers.remove(&key) {
+                worker.retired.cancel();
+            }
+            handled.remove(&key);
+        }
+        _ => {}
+    }
+}
+
+fn commit(runtime: &RuntimeHandle, markers: impl IntoIterator<Item = TopicReaderCommitMarker>) {
+    for marker in markers {
+        if let Err(err) = runtime.commit(marker) {
+            debug!("topic consumer commit skipped: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client_topic::topicreader::ids::PartitionId;
+
+    fn marker(
+        partition_session_id: i64,
+        start_offset: i64,
+        end_offset: i64,
+    ) -> TopicReaderCommitMarker {
+        TopicReaderCommitMarker {
+            partition_session_id: PartitionSessionId::from_raw(partition_session_id),
+            partition_id: PartitionId::from_raw(1),
+            start_offset,
+            end_offset,
+            topic: "topic".to_string(),
+            epoch: 0,
+        }
+    }
+
+    fn key() -> PartitionKey {
+        ("topic".to_string(), 1)
+    }
+
+    #[test]
+    fn handled_offsets_merge_batches_of_one_session() {
+        let handled = HandledOffsets::default();
+        let retired = CancellationToken::new();
+
+        handled.record(key(), marker(10, 0, 5), &retired);
+        handled.record(key(), marker(10, 5, 8), &retired);
+
+        let markers = handled.take_all();
+        assert_eq!(markers.len(), 1);
+        assert_eq!((markers[0].start_offset, markers[0].end_offset), (0, 8));
+        assert!(handled.take_all().is_empty());
+    }
+
+    #[test]
+    fn handled_offsets_of_new_session_replace_old_ones() {
+        let handled = HandledOffsets::default();
+        let retired = CancellationToken::new();
+
+        handled.record(key(), marker(10, 0, 5), &retired);
+        handled.record(key(), marker(11, 5, 8), &retired);
+
+        assert!(
+            handled
+                .take(&key(), PartitionSessionId::from_raw(10))
+                .is_none()
+        );
+        let taken = handled
+            .take(&key(), PartitionSessionId::from_raw(11))
+            .expect("new session offsets");
+        assert_eq!((taken.start_offset, taken.end_offset), (5, 8));
+    }
+
+    #[test]
+    fn retired_worker_does_not_record_offsets() {
+        let handled = HandledOffsets::default();
+        let retired = CancellationToken::new();
+        retired.cancel();
+
+        handled.record(key(), marker(10, 0, 5), &retired);
+
+        assert!(handled.take_all().is_empty());
+    }
+}
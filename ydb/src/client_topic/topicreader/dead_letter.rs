@@ -0,0 +1,139 @@
+use std::time::Duration;
+
+use futures_util::future::try_join_all;
+
+use crate::client_topic::topicreader::messages::{TopicReaderBatch, TopicReaderMessage};
+use crate::client_topic::topicwriter::message::TopicWriterMessage;
+use crate::client_topic::topicwriter::writer::TopicWriter;
+use crate::client_topic::topicwriter::writer_options::TopicWriterOptions;
+use crate::{YdbError, YdbResult};
+
+/// Routes batches which a [`crate::TopicConsumer`] handler failed to process to a dead-letter
+/// topic.
+///
+/// The handler is called up to `max_attempts` times. When the last attempt fails, every message
+/// of the batch is written to the dead-letter topic with its original payload and metadata plus
+/// the `METADATA_*` items below, and the batch offsets are committed as handled.
+///
+/// Handlers get whole batches, so all messages of the failed batch are dead-lettered; set
+/// [`crate::TopicReaderOptions`] `batch_size(1)` to dead-letter messages one by one.
+#[derive(bon::Builder, Clone)]
+pub struct DeadLetterPolicy {
+    /// Writer of the dead-letter topic.
+    pub(crate) writer_options: TopicWriterOptions,
+
+    /// Handler calls with the same batch before it is dead-lettered, including the first one.
+    /// Replaces `handler_retries` of the consumer.
+    #[builder(default = 3)]
+    pub(crate) max_attempts: usize,
+
+    /// Delay before the second attempt, doubled for every next attempt.
+    #[builder(default = Duration::from_millis(100))]
+    pub(crate) backoff: Duration,
+}
+
+impl DeadLetterPolicy {
+    /// Metadata key of the source topic path.
+    pub const METADATA_SOURCE_TOPIC: &'static str = "dlq-source-topic";
+    /// Metadata key of the source partition id, decimal.
+    pub const METADATA_SOURCE_PARTITION: &'static str = "dlq-source-partition";
+    /// Metadata key of the source message offset, decimal.
+    pub const METADATA_SOURCE_OFFSET: &'static str = "dlq-source-offset";
+    /// Metadata key of the last handler error text.
+    pub const METADATA_ERROR: &'static str = "dlq-error";
+    /// Metadata key of the number of failed handler attempts, decimal.
+    pub const METADATA_ATTEMPTS: &'static str = "dlq-attempts";
+}
+
+pub(crate) struct DeadLetterWriter {
+    pub(crate) writer: TopicWriter,
+    pub(crate) max_attempts: usize,
+    pub(crate) backoff: Duration,
+}
+
+impl DeadLetterWriter {
+    pub(crate) fn new(policy: &DeadLetterPolicy, writer: TopicWriter) -> YdbResult<Self> {
+        if policy.max_attempts == 0 {
+            return Err(YdbError::custom(
+                "dead letter policy max_attempts must be positive",
+            ));
+        }
+        Ok(Self {
+            writer,
+            max_attempts: policy.max_attempts,
+            backoff: policy.backoff,
+        })
+    }
+
+    // Returns after the dead-letter topic acknowledged all messages of the batch,
+    // so the source offsets may be committed.
+    pub(crate) async fn write(&self, batch: &TopicReaderBatch, error: &YdbError) -> YdbResult<()> {
+        let error = error.to_string();
+        let mut acks = Vec::with_capacity(batch.messages.len());
+        for message in &batch.messages {
+            acks.push(
+                self.writer
+                    .write_with_ack_future(dead_letter_message(message, &error, self.max_attempts))
+                    .await?,
+            );
+        }
+        try_join_all(acks).await?;
+        Ok(())
+    }
+}
+
+fn dead_letter_message(
+    message: &TopicReaderMessage,
+    error: &str,
+    attempts: usize,
+) -> TopicWriterMessage {
+    let mut builder = TopicWriterMessage::builder();
+    for (key, value) in message.metadata() {
+        builder = builder.metadata(key.clone(), value.clone());
+    }
+    builder
+        .metadata(DeadLetterPolicy::METADATA_SOURCE_TOPIC, message.get_topic())
+        .metadata(
+            DeadLetterPolicy::METADATA_SOURCE_PARTITION,
+            message.get_partition_id().to_string(),
+        )
+        .metadata(
+            DeadLetterPolicy::METADATA_SOURCE_OFFSET,
+            message.offset.to_string(),
+        )
+        .metadata(DeadLetterPolicy::METADATA_ERROR, error)
+        .metadata(DeadLetterPolicy::METADATA_ATTEMPTS, attempts.to_string())
+        .data(message.raw_data.clone().unwrap_or_default())
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dead_letter_message_keeps_payload_and_adds_source() {
+        let mut message = TopicReaderMessage::test_message(0, 0);
+        message.offset = 42;
+        message.raw_data = Some(b"payload".to_vec());
+
+        let dead_letter = dead_letter_message(&message, "handler failed", 3);
+
+        assert_eq!(dead_letter.data, b"payload");
+        let metadata: Vec<(&str, &[u8])> = dead_letter
+            .metadata
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_slice()))
+            .collect();
+        assert_eq!(
+            metadata,
+            [
+                (DeadLetterPolicy::METADATA_SOURCE_TOPIC, b"test".as_slice()),
+                (DeadLetterPolicy::METADATA_SOURCE_PARTITION, b"20"),
+                (DeadLetterPolicy::METADATA_SOURCE_OFFSET, b"42"),
+                (DeadLetterPolicy::METADATA_ERROR, b"handler failed"),
+                (DeadLetterPolicy::METADATA_ATTEMPTS, b"3"),
+            ]
+        );
+    }
+}
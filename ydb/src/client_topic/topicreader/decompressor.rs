@@ -134,6 +134,29 @@ async fn schedule_events(
                         .await;
                 }
             }
+            RawFromServer::DirectReadResponse(mut resp) => {
+                // Decompressed as a whole: the runtime acks the direct read id once it has
+                // received all of the response data.
+                let decoders = resp
+                    .read_response
+                    .partition_data
+                    .iter()
+                    .flat_map(|partition_data| partition_data.batches.iter())
+                    .map(|batch| decoder_for_batch(&codec_registry, batch))
+                    .collect::<YdbResult<Vec<_>>>()?;
+                queue
+                    .submit(Box::new(move || {
+                        let mut decoders = decoders.into_iter();
+                        for partition_data in &mut resp.read_response.partition_data {
+                            partition_data.batches = std::mem::take(&mut partition_data.batches)
+                                .into_iter()
+                                .map(|batch| decompress_batch(batch, decoders.next().flatten()))
+                                .collect::<YdbResult<_>>()?;
+                        }
+                        Ok(RawFromServer::DirectReadResponse(resp))
+                    }))
+                    .await;
+            }
             other => {
                 queue.submit(Box::new(move || Ok(other))).await;
             }
@@ -0,0 +1,410 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+
+use secrecy::ExposeSecret;
+use tokio::select;
+use tokio::sync::mpsc;
+use tokio::task::JoinSet;
+use tokio::time;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, warn};
+use ydb_grpc::ydb_proto::topic::stream_direct_read_message::{FromClient, FromServer};
+
+use crate::client_common::TokenCache;
+use crate::client_topic::topicreader::ids::PartitionSessionId;
+use crate::grpc_connection_manager::GrpcConnectionManager;
+use crate::grpc_wrapper::grpc_stream_wrapper::AsyncGrpcStreamWrapper;
+use crate::grpc_wrapper::raw_services::Service;
+use crate::grpc_wrapper::raw_topic_service::client::RawTopicClient;
+use crate::grpc_wrapper::raw_topic_service::common::update_token::RawUpdateTokenRequest;
+use crate::grpc_wrapper::raw_topic_service::stream_direct_read::messages::{
+    RawDirectFromClientOneOf, RawDirectFromServer, RawDirectInitRequest,
+    RawStartDirectReadPartitionSessionRequest,
+};
+use crate::grpc_wrapper::raw_topic_service::stream_read::messages::{
+    RawFromServer, RawPartitionLocation,
+};
+use crate::{YdbError, YdbResult};
+
+use super::auth_token_sender::UPDATE_TOKEN_INTERVAL;
+use super::reconnector;
+
+type DirectStream = AsyncGrpcStreamWrapper<FromClient, FromServer>;
+type NodeMessageRx = mpsc::UnboundedReceiver<(i32, RawDirectFromServer)>;
+type NodeMessageTx = mpsc::UnboundedSender<(i32, RawDirectFromServer)>;
+
+/// Command from the reader runtime to the direct reader of the current connection.
+#[derive(Debug)]
+pub(crate) enum DirectReadCommand {
+    /// Start reading the partition session from the node, or move it to a new location.
+    Start {
+        partition_session_id: PartitionSessionId,
+        location: RawPartitionLocation,
+    },
+    Stop {
+        partition_session_id: PartitionSessionId,
+    },
+}
+
+struct DirectPartition {
+    location: RawPartitionLocation,
+    last_direct_read_id: i64,
+}
+
+/// Reads partition data straight from the partition nodes.
+///
+/// Runs next to [`GrpcStreamer`](super::grpc_streamer::GrpcStreamer) in direct read mode and keeps
+/// one direct read stream per node. Received data goes to the decompressor the same way as data
+/// from the read stream; read credits, commits and acks stay on the read stream. Any direct stream
+/// error fails the task, so the whole connection is reestablished.
+pub(super) struct DirectReader {
+    manager: GrpcConnectionManager,
+    token_cache: TokenCache,
+    init: DirectInit,
+    commands_rx: mpsc::UnboundedReceiver<DirectReadCommand>,
+    decompression_input_tx: mpsc::UnboundedSender<RawFromServer>,
+    cancellation: CancellationToken,
+}
+
+#[derive(Clone)]
+struct DirectInit {
+    session_id: String,
+    topics: Vec<String>,
+    consumer: String,
+}
+
+impl DirectReader {
+    pub(super) fn new(
+        attempt: &reconnector::ConnectionAttempt,
+        session_id: String,
+        commands_rx: mpsc::UnboundedReceiver<DirectReadCommand>,
+        decompression_input_tx: mpsc::UnboundedSender<RawFromServer>,
+    ) -> Self {
+        Self {
+            manager: attempt.manager.clone(),
+            token_cache: attempt.token_cache.clone(),
+            init: DirectInit {
+                session_id,
+                topics: attempt
+                    .options
+                    .topic
+                    .0
+                    .iter()
+                    .map(|selector| selector.path.clone())
+                    .collect(),
+                consumer: attempt.options.consumer.clone(),
+            },
+            commands_rx,
+            decompression_input_tx,
+            cancellation: attempt.cancellation_token.clone(),
+        }
+    }
+
+    pub(super) async fn run(self) -> YdbResult<()> {
+        let cancellation = self.cancellation.child_token();
+        let (node_message_tx, node_message_rx) = mpsc::unbounded_channel();
+
+        let mut state = DirectReadState {
+            manager: self.manager,
+            token_cache: self.token_cache,
+            init: self.init,
+            nodes: HashMap::new(),
+            partitions: HashMap::new(),
+            node_message_tx,
+            receive_tasks: JoinSet::new(),
+            cancellation: cancellation.clone(),
+        };
+
+        let result = select! {
+            _ = cancellation.cancelled() => {
+                debug!("topic reader direct read cancelled, stopping");
+                Ok(())
+            }
+            result = state.run(self.commands_rx, node_message_rx, self.decompression_input_tx) => {
+                let Err(err) = result;
+                Err(err)
+            }
+        };
+
+        cancellation.cancel();
+        state.receive_tasks.shutdown().await;
+        result
+    }
+}
+
+struct DirectReadState {
+    manager: GrpcConnectionManager,
+    token_cache: TokenCache,
+    init: DirectInit,
+    nodes: HashMap<i32, mpsc::UnboundedSender<FromClient>>,
+    partitions: HashMap<PartitionSessionId, DirectPartition>,
+    node_message_tx: NodeMessageTx,
+    receive_tasks: JoinSet<YdbResult<()>>,
+    cancellation: CancellationToken,
+}
+
+impl DirectReadState {
+    async fn run(
+        &mut self,
+        mut commands_rx: mpsc::UnboundedReceiver<DirectReadCommand>,
+        mut node_message_rx: NodeMessageRx,
+        decompression_input_tx: mpsc::UnboundedSender<RawFromServer>,
+    ) -> YdbResult<Infallible> {
+        let mut token_interval = time::interval(UPDATE_TOKEN_INTERVAL);
+        token_interval.tick().await;
+
+        loop {
+            select! {
+                command = commands_rx.recv() => {
+                    let command = command.ok_or_else(|| {
+                        YdbError::Transport("topic reader direct read command channel closed".into())
+                    })?;
+                    self.handle_command(command).await?;
+                }
+                message = node_message_rx.recv() => {
+                    let (node_id, message) = message.ok_or_else(|| {
+                        YdbError::Transport("topic reader direct read node channel closed".into())
+                    })?;
+                    self.handle_node_message(node_id, message, &decompression_input_tx)?;
+                }
+                Some(joined) = self.receive_tasks.join_next(), if !self.receive_tasks.is_empty() => {
+                    return Err(match joined {
+                        Ok(Err(err)) => err,
+                        Ok(Ok(())) => YdbError::Transport(
+                            "topic reader direct read stream finished".into(),
+                        ),
+                        Err(err) => YdbError::custom(format!(
+                            "topic reader direct read task failed: {err}"
+                        )),
+                    });
+                }
+                _ = token_interval.tick() => {
+                    self.update_tokens()?;
+                }
+            }
+        }
+    }
+
+    async fn handle_command(&mut self, command: DirectReadCommand) -> YdbResult<()> {
+        match command {
+            DirectReadCommand::Start {
+                partition_session_id,
+                location,
+            } => {
+                let partition =
+                    self.partitions
+                        .entry(partition_session_id)
+                        .or_insert(DirectPartition {
+                            location,
+                            last_direct_read_id: 0,
+                        });
+                partition.location = location;
+                self.start_partition(partition_session_id).await
+            }
+            DirectReadCommand::Stop {
+                partition_session_id,
+            } => {
+                self.partitions.remove(&partition_session_id);
+                Ok(())
+            }
+        }
+    }
+
+    async fn start_partition(&mut self, partition_session_id: PartitionSessionId) -> YdbResult<()> {
+        let Some(partition) = self.partitions.get(&partition_session_id) else {
+            return Ok(());
+        };
+        let location = partition.location;
+        let request = RawStartDirectReadPartitionSessionRequest {
+            partition_session_id: partition_session_id.into_raw(),
+            last_direct_read_id: partition.last_direct_read_id,
+            generation: location.generation,
+        };
+
+        debug!(
+            %partition_session_id,
+            node_id = location.node_id,
+            generation = location.generation,
+            "topic reader starting direct read partition session"
+        );
+
+        let node = self.node_sender(location.node_id).await?;
+        send_to_node(
+            &node,
+            RawDirectFromClientOneOf::StartDirectReadPartitionSession(request),
+        )
+    }
+
+    async fn node_sender(&mut self, node_id: i32) -> YdbResult<mpsc::UnboundedSender<FromClient>> {
+        if let Some(sender) = self.nodes.get(&node_id) {
+            return Ok(sender.clone());
+        }
+
+        let stream = self.connect(node_id).await?;
+        let sender = stream.clone_sender();
+        self.receive_tasks.spawn(receive_loop(
+            node_id,
+            stream,
+            self.node_message_tx.clone(),
+            self.cancellation.clone(),
+        ));
+        self.nodes.insert(node_id, sender.clone());
+        Ok(sender)
+    }
+
+    async fn connect(&self, node_id: i32) -> YdbResult<DirectStream> {
+        let uri = match u32::try_from(node_id)
+            .ok()
+            .and_then(|node_id| self.manager.node_endpoint(node_id))
+        {
+            Some(uri) => uri,
+            None => {
+                warn!(
+                    node_id,
+                    "topic reader direct read node is not discovered, using any topic endpoint"
+                );
+                self.manager.endpoint(Service::Topic)?
+            }
+        };
+
+        debug!(node_id, %uri, "topic reader connecting direct read stream");
+        let mut topic_service = self
+            .manager
+            .get_auth_service_to_node(RawTopicClient::new, &uri)
+            .await?;
+        let mut stream = topic_service
+            .stream_direct_read(RawDirectInitRequest {
+                session_id: self.init.session_id.clone(),
+                topics: self.init.topics.clone(),
+                consumer: self.init.consumer.clone(),
+            })
+            .await?;
+
+        match stream.receive::<RawDirectFromServer>().await? {
+            RawDirectFromServer::InitResponse => Ok(stream),
+            message => Err(YdbError::custom(format!(
+                "topic reader direct read expected init response, got: {message:?}"
+            ))),
+        }
+    }
+
+    fn handle_node_message(
+        &mut self,
+        node_id: i32,
+        message: RawDirectFromServer,
+        decompression_input_tx: &mpsc::UnboundedSender<RawFromServer>,
+    ) -> YdbResult<()> {
+        match message {
+            RawDirectFromServer::DirectReadResponse(response) => {
+                let partition_session_id =
+                    PartitionSessionId::from_raw(response.partition_session_id);
+                let Some(partition) = self.partitions.get_mut(&partition_session_id) else {
+                    debug!(
+                        %partition_session_id,
+                        "topic reader dropped direct read response for stopped partition session"
+                    );
+                    return Ok(());
+                };
+                partition.last_direct_read_id = response.direct_read_id;
+
+                decompression_input_tx
+                    .send(RawFromServer::DirectReadResponse(response))
+                    .map_err(|_| {
+                        YdbError::Transport(
+                            "topic reader direct read -> decompressor channel closed".to_string(),
+                        )
+                    })
+            }
+            RawDirectFromServer::StopDirectReadPartitionSession(stop) => {
+                let partition_session_id = PartitionSessionId::from_raw(stop.partition_session_id);
+                let restart = self
+                    .partitions
+                    .get(&partition_session_id)
+                    .is_some_and(|partition| {
+                        partition.location.node_id == node_id
+                            && partition.location.generation == stop.generation
+                    });
+                if !restart {
+                    return Ok(());
+                }
+
+                warn!(
+                    %partition_session_id,
+                    node_id,
+                    status = stop.status,
+                    "topic reader direct read partition session stopped by server, restarting"
+                );
+                let Some(node) = self.nodes.get(&node_id).cloned() else {
+                    return Ok(());
+                };
+                let partition = &self.partitions[&partition_session_id];
+                send_to_node(
+                    &node,
+                    RawDirectFromClientOneOf::StartDirectReadPartitionSession(
+                        RawStartDirectReadPartitionSessionRequest {
+                            partition_session_id: partition_session_id.into_raw(),
+                            last_direct_read_id: partition.last_direct_read_id,
+                            generation: partition.location.generation,
+                        },
+                    ),
+                )
+            }
+            RawDirectFromServer::StartDirectReadPartitionSessionResponse(response) => {
+                debug!(
+                    partition_session_id = response.partition_session_id,
+                    generation = response.generation,
+                    "topic reader direct read partition session started"
+                );
+                Ok(())
+            }
+            message @ (RawDirectFromServer::InitResponse
+            | RawDirectFromServer::UpdateTokenResponse) => {
+                debug!(
+                    node_id,
+                    ?message,
+                    "topic reader received direct read message"
+                );
+                Ok(())
+            }
+        }
+    }
+
+    fn update_tokens(&self) -> YdbResult<()> {
+        let token = self.token_cache.token();
+        for node in self.nodes.values() {
+            send_to_node(
+                node,
+                RawDirectFromClientOneOf::UpdateTokenRequest(RawUpdateTokenRequest {
+                    token: token.expose_secret().to_string(),
+                }),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn send_to_node(
+    sender: &mpsc::UnboundedSender<FromClient>,
+    message: RawDirectFromClientOneOf,
+) -> YdbResult<()> {
+    sender
+        .send(message.into())
+        .map_err(|err| YdbError::Transport(format!("topic reader direct read send failed: {err}")))
+}
+
+async fn receive_loop(
+    node_id: i32,
+    mut stream: DirectStream,
+    node_message_tx: NodeMessageTx,
+    cancellation: CancellationToken,
+) -> YdbResult<()> {
+    loop {
+        let message = select! {
+            _ = cancellation.cancelled() => return Ok(()),
+            message = stream.receive::<RawDirectFromServer>() => message?,
+        };
+        node_message_tx.send((node_id, message)).map_err(|_| {
+            YdbError::Transport("topic reader direct read node channel closed".to_string())
+        })?;
+    }
+}
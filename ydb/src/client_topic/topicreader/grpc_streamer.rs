@@ -30,6 +30,7 @@ type GrpcStream = AsyncGrpcStreamWrapper<FromClient, FromServer>;
 
 pub(super) struct GrpcStreamer {
     stream: GrpcStream,
+    session_id: String,
     cancellation: CancellationToken,
     decompression_input_tx: mpsc::UnboundedSender<RawFromServer>,
     client_message_rx: mpsc::UnboundedReceiver<RawFromClientOneOf>,
@@ -42,19 +43,25 @@ impl GrpcStreamer {
         client_message_rx: mpsc::UnboundedReceiver<RawFromClientOneOf>,
     ) -> YdbResult<Self> {
         let mut stream = grpc_connect(&attempt.manager, &attempt.options).await?;
-        handle_init_response(stream.receive::<RawFromServer>().await?)?;
+        let session_id = handle_init_response(stream.receive::<RawFromServer>().await?)?;
 
         Ok(Self {
             stream,
+            session_id,
             cancellation: attempt.cancellation_token.clone(),
             decompression_input_tx,
             client_message_rx,
         })
     }
 
+    pub(super) fn session_id(&self) -> &str {
+        &self.session_id
+    }
+
     pub(super) async fn run(self) -> YdbResult<()> {
         let Self {
             stream,
+            session_id: _,
             cancellation,
             decompression_input_tx,
             client_message_rx,
@@ -81,11 +88,11 @@ impl GrpcStreamer {
     }
 }
 
-fn handle_init_response(message: RawFromServer) -> YdbResult<()> {
+fn handle_init_response(message: RawFromServer) -> YdbResult<String> {
     match message {
         RawFromServer::InitResponse(response) => {
             debug!(?response, "topic reader initialized");
-            Ok(())
+            Ok(response.session_id)
         }
         message => Err(YdbError::custom(format!(
             "topic reader expected init response, got: {message:?}"
@@ -108,6 +115,7 @@ async fn grpc_connect(
         topics_read_settings: options.topic.clone().into_topics_read_settings(),
         consumer: options.consumer.clone(),
         reader_name: "".to_string(),
+        direct_read: options.direct_read,
     };
 
     Ok(topic_service.stream_read(init_request).await?)
@@ -9,7 +9,7 @@ use std::time;
 use std::time::SystemTime;
 
 #[cfg_attr(not(feature = "force-exhaustive-all"), non_exhaustive)]
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct TopicReaderBatch {
     pub messages: Vec<TopicReaderMessage>,
 
@@ -56,6 +56,7 @@ impl TopicReaderBatch {
                         uncompressed_size: message.uncompressed_size,
                         producer_id: raw_batch.producer_id.clone(),
                         raw_data: Some(message.data),
+                        metadata: message.metadata_items,
 
                         commit_marker: TopicReaderCommitMarker {
                             partition_session_id: partition_session.partition_session_id,
@@ -117,7 +118,7 @@ impl TopicReaderBatch {
 }
 
 #[cfg_attr(not(feature = "force-exhaustive-all"), non_exhaustive)]
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct TopicReaderMessage {
     pub seq_no: i64,
     pub created_at: Option<time::SystemTime>,
@@ -125,8 +126,9 @@ pub struct TopicReaderMessage {
     pub written_at: time::SystemTime,
     pub uncompressed_size: i64, // as sent by sender, server/sdk doesn't check the field. It may be empty or wrong.
 
-    producer_id: String,
+    pub(crate) producer_id: String,
     pub(crate) raw_data: Option<Vec<u8>>,
+    pub(crate) metadata: Vec<(String, Vec<u8>)>,
     pub(crate) commit_marker: TopicReaderCommitMarker,
 
     // Non-zero only on the last message of a server ReadResponse; carries the
@@ -142,6 +144,10 @@ impl TopicReaderMessage {
         Ok(self.raw_data.take())
     }
 
+    pub(crate) fn data_len(&self) -> usize {
+        self.raw_data.as_ref().map_or(0, Vec::len)
+    }
+
     pub fn get_producer_id(&self) -> &str {
         self.producer_id.as_str()
     }
@@ -150,6 +156,19 @@ impl TopicReaderMessage {
         self.commit_marker.clone()
     }
 
+    /// Metadata items (headers), set by the writer, in the written order.
+    pub fn metadata(&self) -> &[(String, Vec<u8>)] {
+        &self.metadata
+    }
+
+    /// Value of the first metadata item with the key.
+    pub fn metadata_value(&self, key: &str) -> Option<&[u8]> {
+        self.metadata
+            .iter()
+            .find(|(item_key, _)| item_key == key)
+            .map(|(_, value)| value.as_slice())
+    }
+
     pub fn get_topic(&self) -> &str {
         &self.commit_marker.topic
     }
@@ -172,6 +191,7 @@ impl TopicReaderMessage {
             uncompressed_size: 0,
             producer_id: String::new(),
             raw_data: Some(vec![]),
+            metadata: Vec::new(),
             commit_marker: TopicReaderCommitMarker {
                 partition_session_id: PartitionSessionId::from_raw(10),
                 partition_id: PartitionId::from_raw(20),
@@ -211,6 +231,7 @@ mod tests {
             partition_id: PartitionId::from_raw(456),
             topic: "test-topic".to_string(),
             next_commit_offset_start: 100,
+            location: None,
         };
 
         let raw_batch = RawBatch {
@@ -223,6 +244,7 @@ mod tests {
                 created_at: Some(SystemTime::now().into()),
                 data: vec![1, 2, 3],
                 uncompressed_size: 3,
+                metadata_items: vec![("trace-id".to_string(), b"abc".to_vec())],
                 offset: 100,
                 read_session_size_bytes: 0,
             }],
@@ -244,6 +266,11 @@ mod tests {
         assert_eq!(message_commit_marker.partition_id.into_raw(), 456);
         assert_eq!(message_commit_marker.start_offset, 100);
         assert_eq!(message_commit_marker.end_offset, 101);
+        assert_eq!(
+            batch.messages[0].metadata_value("trace-id"),
+            Some(b"abc".as_slice())
+        );
+        assert_eq!(batch.messages[0].metadata_value("missing"), None);
     }
 
     #[test]
@@ -253,6 +280,7 @@ mod tests {
             partition_id: PartitionId::from_raw(2),
             topic: "t".to_string(),
             next_commit_offset_start: 0,
+            location: None,
         };
         let raw_batch = RawBatch {
             producer_id: "p".to_string(),
@@ -265,6 +293,7 @@ mod tests {
                     created_at: None,
                     data: vec![],
                     uncompressed_size: 0,
+                    metadata_items: vec![],
                     offset: 0,
                     read_session_size_bytes: 0,
                 },
@@ -273,6 +302,7 @@ mod tests {
                     created_at: None,
                     data: vec![],
                     uncompressed_size: 0,
+                    metadata_items: vec![],
                     offset: 1,
                     read_session_size_bytes: 0,
                 },
@@ -289,6 +319,7 @@ mod tests {
             partition_id: PartitionId::from_raw(42),
             topic: "t-from-messages".to_string(),
             next_commit_offset_start: 100,
+            location: None,
         };
         let raw_batch = RawBatch {
             producer_id: "p".to_string(),
@@ -301,6 +332,7 @@ mod tests {
                     created_at: None,
                     data: vec![],
                     uncompressed_size: 0,
+                    metadata_items: vec![],
                     offset: 100 + i,
                     read_session_size_bytes: 0,
                 })
@@ -1,8 +1,13 @@
 mod auth_token_sender;
+pub(crate) mod consumer;
+pub(crate) mod dead_letter;
 mod decompressor;
+mod direct_reader;
 mod grpc_streamer;
 pub(crate) mod ids;
 pub(crate) mod messages;
+pub(crate) mod partition_events;
+pub(crate) mod partition_range;
 pub(crate) mod partition_state;
 pub(crate) mod reader;
 pub(crate) mod reader_options;
@@ -10,3 +15,5 @@ pub(crate) mod reader_tx;
 mod reconnector;
 mod runtime;
 mod task_supervisor;
+pub(crate) mod tx_processor;
+pub(crate) mod typed_reader;
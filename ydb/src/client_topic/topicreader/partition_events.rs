@@ -0,0 +1,249 @@
+use crate::YdbResult;
+use crate::client_topic::topicreader::ids::{PartitionId, PartitionSessionId};
+
+use super::runtime::{RuntimeHandle, WeakRuntimeHandle};
+
+/// Partition session lifecycle event, see [`crate::TopicReader::partition_session_events`].
+#[cfg_attr(not(feature = "force-exhaustive-all"), non_exhaustive)]
+#[derive(Debug)]
+pub enum PartitionSessionEvent {
+    /// Server assigned a partition to the reader. No data is read until the event is confirmed.
+    Started(PartitionSessionStarted),
+
+    /// Server asks to release the partition.
+    Stopping(PartitionSessionStopping),
+
+    /// Server sent all messages of the partition: it was split or merged by autopartitioning.
+    /// Messages of child partitions are delivered once all messages of this one are committed.
+    Finished(PartitionSessionFinished),
+
+    /// Partition session is closed: after stop, or because the reader connection was lost.
+    /// Commit markers of the session aren't valid anymore.
+    Ended(PartitionSessionEnded),
+}
+
+#[derive(Debug)]
+pub(crate) struct EventTarget {
+    pub(crate) runtime: WeakRuntimeHandle,
+    pub(crate) epoch: usize,
+    pub(crate) partition_session_id: PartitionSessionId,
+}
+
+impl EventTarget {
+    fn runtime(&self) -> Option<RuntimeHandle> {
+        self.runtime.upgrade()
+    }
+}
+
+/// Partition assignment. Answer it with [`Self::confirm`] or [`Self::confirm_with_offsets`].
+///
+/// Dropped event is confirmed with default offsets.
+#[derive(Debug)]
+pub struct PartitionSessionStarted {
+    pub(crate) topic: String,
+    pub(crate) partition_id: PartitionId,
+    pub(crate) committed_offset: i64,
+    pub(crate) start_offset: i64,
+    pub(crate) end_offset: i64,
+    pub(crate) target: Option<EventTarget>,
+}
+
+impl PartitionSessionStarted {
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+
+    pub fn partition_id(&self) -> i64 {
+        self.partition_id.into_raw()
+    }
+
+    /// Offset, committed by the consumer on the server.
+    pub fn committed_offset(&self) -> i64 {
+        self.committed_offset
+    }
+
+    /// Partition contains messages with offsets in range `[start_offset, end_offset)`.
+    pub fn start_offset(&self) -> i64 {
+        self.start_offset
+    }
+
+    pub fn end_offset(&self) -> i64 {
+        self.end_offset
+    }
+
+    /// Start reading from the server committed offset.
+    pub fn confirm(self) -> YdbResult<()> {
+        self.confirm_with_offsets(None, None)
+    }
+
+    /// Start reading from `read_offset`, for example from offset stored in an own table.
+    ///
+    /// `commit_offset` tells server, that all messages before it are processed. Server closes
+    /// the stream with error if `read_offset` is less than the committed offset.
+    pub fn confirm_with_offsets(
+        mut self,
+        read_offset: Option<i64>,
+        commit_offset: Option<i64>,
+    ) -> YdbResult<()> {
+        match self.target.take() {
+            Some(target) => match target.runtime() {
+                Some(runtime) => runtime.confirm_partition_start(
+                    target.epoch,
+                    target.partition_session_id,
+                    read_offset,
+                    commit_offset,
+                ),
+                None => Ok(()),
+            },
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for PartitionSessionStarted {
+    fn drop(&mut self) {
+        if let Some(target) = self.target.take()
+            && let Some(runtime) = target.runtime()
+        {
+            let _ = runtime.confirm_partition_start(
+                target.epoch,
+                target.partition_session_id,
+                None,
+                None,
+            );
+        }
+    }
+}
+
+/// Request to release the partition.
+///
+/// Graceful stop waits for [`Self::confirm`], so the reader may process and commit buffered
+/// messages first. Non-graceful stop is already applied, confirming it does nothing.
+/// Dropped event is confirmed.
+#[derive(Debug)]
+pub struct PartitionSessionStopping {
+    pub(crate) topic: String,
+    pub(crate) partition_id: PartitionId,
+    pub(crate) graceful: bool,
+    pub(crate) committed_offset: i64,
+    pub(crate) target: Option<EventTarget>,
+}
+
+impl PartitionSessionStopping {
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+
+    pub fn partition_id(&self) -> i64 {
+        self.partition_id.into_raw()
+    }
+
+    pub fn is_graceful(&self) -> bool {
+        self.graceful
+    }
+
+    /// Offset, committed by the consumer on the server.
+    pub fn committed_offset(&self) -> i64 {
+        self.committed_offset
+    }
+
+    /// Release the partition.
+    pub fn confirm(mut self) -> YdbResult<()> {
+        self.confirm_inner()
+    }
+
+    fn confirm_inner(&mut self) -> YdbResult<()> {
+        match self.target.take() {
+            Some(target) => match target.runtime() {
+                Some(runtime) => runtime.confirm_partition_stop(
+                    target.epoch,
+                    target.partition_session_id,
+                    self.committed_offset,
+                ),
+                None => Ok(()),
+            },
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for PartitionSessionStopping {
+    fn drop(&mut self) {
+        let _ = self.confirm_inner();
+    }
+}
+
+#[derive(Debug)]
+pub struct PartitionSessionEnded {
+    pub(crate) topic: String,
+    pub(crate) partition_id: PartitionId,
+    pub(crate) connection_lost: bool,
+}
+
+impl PartitionSessionEnded {
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+
+    pub fn partition_id(&self) -> i64 {
+        self.partition_id.into_raw()
+    }
+
+    /// Session was closed by reconnect, not by server stop request.
+    pub fn is_connection_lost(&self) -> bool {
+        self.connection_lost
+    }
+}
+
+/// Partition was fully read because of autopartitioning. The session stays open until
+/// the messages of the partition are committed.
+#[derive(Debug)]
+pub struct PartitionSessionFinished {
+    pub(crate) topic: String,
+    pub(crate) partition_id: PartitionId,
+    pub(crate) adjacent_partition_ids: Vec<i64>,
+    pub(crate) child_partition_ids: Vec<i64>,
+}
+
+impl PartitionSessionFinished {
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+
+    pub fn partition_id(&self) -> i64 {
+        self.partition_id.into_raw()
+    }
+
+    /// Partitions, which were merged with this one.
+    pub fn adjacent_partition_ids(&self) -> &[i64] {
+        &self.adjacent_partition_ids
+    }
+
+    /// Partitions, formed by the split or merge.
+    pub fn child_partition_ids(&self) -> &[i64] {
+        &self.child_partition_ids
+    }
+
+    pub fn is_split(&self) -> bool {
+        self.child_partition_ids.len() > 1
+    }
+
+    pub fn is_merge(&self) -> bool {
+        !self.adjacent_partition_ids.is_empty()
+    }
+}
+
+/// Receiver of partition session events, see [`crate::TopicReader::partition_session_events`].
+#[derive(Clone)]
+pub struct PartitionSessionEvents {
+    pub(crate) runtime: RuntimeHandle,
+}
+
+impl PartitionSessionEvents {
+    /// Wait for the next event.
+    ///
+    /// Every event is delivered to one receiver only, if the receiver is cloned.
+    pub async fn recv(&mut self) -> YdbResult<PartitionSessionEvent> {
+        self.runtime.next_partition_event().await
+    }
+}
@@ -0,0 +1,155 @@
+use std::collections::VecDeque;
+use std::time::{Duration, SystemTime};
+
+use futures_util::Stream;
+use tokio::time::{Instant, Interval, MissedTickBehavior};
+
+use crate::client_topic::topicreader::messages::TopicReaderMessage;
+use crate::client_topic::topicreader::partition_events::{
+    PartitionSessionEvent, PartitionSessionEvents,
+};
+use crate::client_topic::topicreader::reader::TopicReader;
+use crate::{TopicClient, YdbResult};
+
+/// Where [`crate::TopicClient::read_partition_range`] starts reading.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "force-exhaustive-all"), non_exhaustive)]
+pub enum PartitionReadStart {
+    /// First message with offset greater or equal to this one.
+    Offset(i64),
+    /// First message written at or after this moment.
+    Time(SystemTime),
+}
+
+impl From<i64> for PartitionReadStart {
+    fn from(offset: i64) -> Self {
+        Self::Offset(offset)
+    }
+}
+
+impl From<SystemTime> for PartitionReadStart {
+    fn from(time: SystemTime) -> Self {
+        Self::Time(time)
+    }
+}
+
+// How often the partition start offset is checked while no messages arrive.
+const RETENTION_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+// Reads one partition up to `end_offset` (exclusive) without a consumer.
+pub(crate) struct PartitionRangeReader {
+    reader: TopicReader,
+    events: PartitionSessionEvents,
+    // Describes the partition to notice the rest of the range deleted by retention.
+    client: TopicClient,
+    path: String,
+    partition_id: i64,
+    retention_check: Interval,
+    // Offset to resume from when the partition session is (re)started.
+    next_offset: Option<i64>,
+    end_offset: i64,
+    buffered: VecDeque<TopicReaderMessage>,
+}
+
+impl PartitionRangeReader {
+    pub(crate) fn new(
+        reader: TopicReader,
+        client: TopicClient,
+        path: String,
+        partition_id: i64,
+        start_offset: Option<i64>,
+        end_offset: i64,
+    ) -> YdbResult<Self> {
+        let events = reader.partition_session_events()?;
+        let mut retention_check = tokio::time::interval_at(
+            Instant::now() + RETENTION_CHECK_INTERVAL,
+            RETENTION_CHECK_INTERVAL,
+        );
+        retention_check.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        Ok(Self {
+            reader,
+            events,
+            client,
+            path,
+            partition_id,
+            retention_check,
+            next_offset: start_offset,
+            end_offset,
+            buffered: VecDeque::new(),
+        })
+    }
+
+    pub(crate) fn into_stream(
+        self,
+    ) -> impl Stream<Item = YdbResult<TopicReaderMessage>> + Send + 'static {
+        futures_util::stream::unfold(Some(self), |range| async move {
+            let mut range = range?;
+            match range.next_message().await {
+                Ok(Some(message)) => Some((Ok(message), Some(range))),
+                Ok(None) => None,
+                Err(err) => Some((Err(err), None)),
+            }
+        })
+    }
+
+    fn is_finished(&self) -> bool {
+        self.next_offset
+            .is_some_and(|next_offset| next_offset >= self.end_offset)
+    }
+
+    // Messages of the range, which are deleted by retention, are never delivered: the range is
+    // over when the partition starts at or after its end.
+    fn check_start_offset(&mut self, start_offset: i64) {
+        if start_offset >= self.end_offset {
+            self.next_offset = Some(self.end_offset);
+        }
+    }
+
+    async fn next_message(&mut self) -> YdbResult<Option<TopicReaderMessage>> {
+        loop {
+            if let Some(message) = self.buffered.pop_front() {
+                return Ok(Some(message));
+            }
+            if self.is_finished() {
+                return Ok(None);
+            }
+
+            tokio::select! {
+                biased;
+
+                event = self.events.recv() => match event? {
+                    // After a reconnect the new session resumes right after the last read message.
+                    PartitionSessionEvent::Started(started) => {
+                        self.check_start_offset(started.start_offset());
+                        started.confirm_with_offsets(self.next_offset, None)?
+                    }
+                    PartitionSessionEvent::Stopping(stopping) => stopping.confirm()?,
+                    _ => {}
+                },
+
+                batch = self.reader.read_batch() => {
+                    for message in batch?.messages {
+                        if message.offset >= self.end_offset {
+                            self.next_offset = Some(self.end_offset);
+                            break;
+                        }
+                        if self.next_offset.is_some_and(|next_offset| message.offset < next_offset) {
+                            continue;
+                        }
+                        self.next_offset = Some(message.offset + 1);
+                        self.buffered.push_back(message);
+                    }
+                    self.retention_check.reset();
+                }
+
+                _ = self.retention_check.tick() => {
+                    let stats = self
+                        .client
+                        .partition_stats(self.path.clone(), self.partition_id)
+                        .await?;
+                    self.check_start_offset(stats.start_offset);
+                }
+            }
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use crate::client_topic::topicreader::ids::{PartitionId, PartitionSessionId};
-use crate::grpc_wrapper::raw_topic_service::stream_read::messages::RawStartPartitionSessionRequest;
+use crate::grpc_wrapper::raw_topic_service::stream_read::messages::{
+    RawPartitionLocation, RawStartPartitionSessionRequest,
+};
 
 pub(crate) struct PartitionSession {
     pub partition_session_id: PartitionSessionId,
@@ -8,6 +10,9 @@ pub(crate) struct PartitionSession {
 
     // Each offset up to and including (committed_offset - 1) was fully processed.
     pub next_commit_offset_start: i64,
+
+    // Node serving the partition, set in direct read mode only.
+    pub location: Option<RawPartitionLocation>,
 }
 
 #[cfg(test)]
@@ -21,6 +26,7 @@ impl PartitionSession {
             partition_id: marker.partition_id,
             topic: marker.topic,
             next_commit_offset_start: marker.end_offset,
+            location: None,
         }
     }
 }
@@ -34,6 +40,7 @@ impl From<RawStartPartitionSessionRequest> for PartitionSession {
             partition_id: PartitionId::from_raw(request.partition_session.partition_id),
             topic: request.partition_session.path,
             next_commit_offset_start: request.committed_offset,
+            location: request.partition_location,
         }
     }
 }
@@ -1,18 +1,21 @@
+use std::collections::VecDeque;
+use std::pin::Pin;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::task::{Context, Poll, ready};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use futures_util::Future;
+use futures_util::{Future, Stream};
 use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
 
-use tracing::instrument;
+use tracing::{debug, instrument};
 
 use crate::client_common::TokenCache;
 use crate::client_query::Transaction;
 use crate::client_topic::compression::Executor;
 use crate::client_topic::topicreader::ids::{PartitionId, PartitionSessionId};
-use crate::client_topic::topicreader::messages::TopicReaderBatch;
+use crate::client_topic::topicreader::messages::{TopicReaderBatch, TopicReaderMessage};
 use crate::client_topic::topicreader::reader_options::TopicReaderOptions;
 use crate::grpc_connection_manager::GrpcConnectionManager;
 use crate::grpc_wrapper::raw_topic_service::client::RawTopicClient;
@@ -25,6 +28,7 @@ use crate::grpc_wrapper::raw_topic_service::update_offsets_in_transaction::{
 use crate::grpc_wrapper::raw_ydb_operation::RawOperationParams;
 use crate::{YdbError, YdbResult};
 
+use super::partition_events::PartitionSessionEvents;
 use super::reader_tx::TopicReaderTx;
 use super::reconnector::{Reconnector, ReconnectorTask};
 use super::runtime::RuntimeHandle;
@@ -35,6 +39,17 @@ pub struct TopicReader {
     reconnect_handle: JoinHandle<YdbResult<()>>,
     runtime: RuntimeHandle,
     cancellation: CancellationToken,
+    stream: StreamState,
+}
+
+type PopBatchFuture = Pin<Box<dyn Future<Output = YdbResult<TopicReaderBatch>> + Send + Sync>>;
+
+// State of the message Stream: messages of the last popped batch not yielded yet.
+#[derive(Default)]
+struct StreamState {
+    buffered: VecDeque<TopicReaderMessage>,
+    pop_batch: Option<PopBatchFuture>,
+    finished: bool,
 }
 
 static READER_ID: AtomicUsize = AtomicUsize::new(0);
@@ -71,6 +86,7 @@ impl TopicReader {
             reconnect_handle: join_handle,
             runtime,
             cancellation: cancellation_token,
+            stream: StreamState::default(),
         })
     }
 
@@ -79,10 +95,73 @@ impl TopicReader {
         self.read_batch_inner().await
     }
 
+    /// Reads a batch of at most `max_messages` messages and `max_bytes` payload bytes, waiting
+    /// up to `wait_timeout` for data.
+    ///
+    /// Returns `Ok(None)` if no messages arrived within `wait_timeout`. A single message larger
+    /// than `max_bytes` is returned alone. All messages of a batch belong to one partition.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `max_messages` is zero or the reader has failed.
+    #[instrument(name = "ydb.TopicReader.ReadBatchWith", skip_all, fields(db.system.name = "ydb"), err)]
+    pub async fn read_batch_with(
+        &mut self,
+        max_messages: usize,
+        max_bytes: usize,
+        wait_timeout: Duration,
+    ) -> YdbResult<Option<TopicReaderBatch>> {
+        if max_messages == 0 {
+            return Err(YdbError::custom(
+                "topic reader read_batch_with called with max_messages=0",
+            ));
+        }
+
+        if !self.stream.buffered.is_empty() {
+            let mut messages = Vec::new();
+            let mut bytes = 0usize;
+            while let Some(message) = self.stream.buffered.front() {
+                bytes = bytes.saturating_add(message.data_len());
+                if messages.len() == max_messages || (!messages.is_empty() && bytes > max_bytes) {
+                    break;
+                }
+                messages.extend(self.stream.buffered.pop_front());
+            }
+            return Ok(Some(TopicReaderBatch::from_messages(messages)));
+        }
+
+        match tokio::time::timeout(
+            wait_timeout,
+            self.runtime.pop_batch_limited(max_messages, max_bytes),
+        )
+        .await
+        {
+            Ok(batch) => batch.map(Some),
+            Err(_elapsed) => Ok(None),
+        }
+    }
+
     pub(super) async fn read_batch_inner(&mut self) -> YdbResult<TopicReaderBatch> {
+        // Messages already taken from the buffer by the message stream go first.
+        if !self.stream.buffered.is_empty() {
+            let messages = self.stream.buffered.drain(..).collect();
+            return Ok(TopicReaderBatch::from_messages(messages));
+        }
         self.runtime.pop_batch(self.options.batch_size).await
     }
 
+    /// Stream of batches, the same as calling [`TopicReader::read_batch`] in a loop.
+    /// The stream ends after the first error.
+    pub fn batches(&mut self) -> impl Stream<Item = YdbResult<TopicReaderBatch>> + Send + '_ {
+        futures_util::stream::unfold(Some(self), |reader| async move {
+            let reader = reader?;
+            match reader.read_batch().await {
+                Ok(batch) => Some((Ok(batch), Some(reader))),
+                Err(err) => Some((Err(err), None)),
+            }
+        })
+    }
+
     /// Read a batch and register consumer offsets via [`UpdateOffsetsInTransaction`]
     /// using the given [`Transaction`].
     ///
@@ -143,6 +222,75 @@ impl TopicReader {
         }
     }
 
+    /// Returns a receiver of partition session lifecycle events: partition assignment,
+    /// release request and session end.
+    ///
+    /// Partition assignment waits until the [`PartitionSessionStarted`] event is confirmed or
+    /// dropped, so the events must be handled concurrently with reading.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reader was created without
+    /// `TopicReaderOptions::partition_session_events`.
+    ///
+    /// [`PartitionSessionStarted`]: crate::PartitionSessionStarted
+    pub fn partition_session_events(&self) -> YdbResult<PartitionSessionEvents> {
+        if !self.options.partition_session_events {
+            return Err(YdbError::custom(
+                "partition session events are disabled, enable them in TopicReaderOptions",
+            ));
+        }
+        Ok(PartitionSessionEvents {
+            runtime: self.runtime.clone(),
+        })
+    }
+
+    /// Closes the reader.
+    ///
+    /// The reader stops requesting new data from the server. With `flush_commits` it then waits
+    /// until all commits sent so far are acknowledged. Finally partition sessions are ended with
+    /// [`PartitionSessionEnded::is_connection_lost`] `== false` and the stream is closed, so
+    /// the server can hand the partitions to other readers without re-delivering committed data.
+    ///
+    /// Messages read but not committed before the close will be delivered again. The wait for
+    /// commit acknowledgements is not bounded, wrap the call into a timeout if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `flush_commits` is set and some of the commits failed, e.g. because
+    /// of a reconnect or a partition session stop.
+    ///
+    /// [`PartitionSessionEnded::is_connection_lost`]: crate::PartitionSessionEnded::is_connection_lost
+    #[instrument(name = "ydb.TopicReader.Close", skip_all, fields(db.system.name = "ydb"), err)]
+    pub async fn close(mut self, flush_commits: bool) -> YdbResult<()> {
+        self.runtime.start_closing();
+
+        let flushed = if flush_commits {
+            match self.runtime.flush_commits() {
+                Ok(flushed) => match flushed.await {
+                    Ok(res) => res,
+                    Err(_) => Err(YdbError::custom(
+                        "commit channel was closed without error message",
+                    )),
+                },
+                Err(err) => Err(err),
+            }
+        } else {
+            Ok(())
+        };
+
+        self.runtime
+            .close(&YdbError::custom("topic reader is closed"))?;
+        self.cancellation.cancel();
+        match (&mut self.reconnect_handle).await {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => debug!("topic reader reconnector finished with error: {err}"),
+            Err(err) => debug!("topic reader reconnector task failed: {err}"),
+        }
+
+        flushed
+    }
+
     pub(super) fn runtime_handle(&self) -> RuntimeHandle {
         self.runtime.clone()
     }
@@ -188,6 +336,42 @@ impl TopicReader {
     }
 }
 
+/// Yields messages one by one; the stream ends after the first error.
+///
+/// Messages still have to be committed with [`TopicReader::commit`] and
+/// [`TopicReaderMessage::get_commit_marker`].
+impl Stream for TopicReader {
+    type Item = YdbResult<TopicReaderMessage>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(message) = this.stream.buffered.pop_front() {
+                return Poll::Ready(Some(Ok(message)));
+            }
+            if this.stream.finished {
+                return Poll::Ready(None);
+            }
+
+            let pop_batch = this.stream.pop_batch.get_or_insert_with(|| {
+                let runtime = this.runtime.clone();
+                let batch_size = this.options.batch_size;
+                Box::pin(async move { runtime.pop_batch(batch_size).await })
+            });
+            let result = ready!(pop_batch.as_mut().poll(cx));
+            this.stream.pop_batch = None;
+
+            match result {
+                Ok(batch) => this.stream.buffered.extend(batch.messages),
+                Err(err) => {
+                    this.stream.finished = true;
+                    return Poll::Ready(Some(Err(err)));
+                }
+            }
+        }
+    }
+}
+
 impl Drop for TopicReader {
     fn drop(&mut self) {
         self.cancellation.cancel();
@@ -214,6 +398,8 @@ pub struct TopicSelector {
     pub path: String,
     pub partition_ids: Option<Vec<i64>>,
     pub read_from: Option<SystemTime>,
+    /// Skip messages, written earlier than `max_lag` before the read moment.
+    pub max_lag: Option<Duration>,
 }
 
 impl TopicSelector {
@@ -222,7 +408,7 @@ impl TopicSelector {
             path: self.path,
             partition_ids: self.partition_ids.unwrap_or_default(),
             read_from: self.read_from.map(|time| time.into()),
-            max_lag: None,
+            max_lag: self.max_lag.map(Into::into),
         }
     }
 }
@@ -233,6 +419,7 @@ impl TopicSelector {
             path: path.into(),
             partition_ids: None,
             read_from: Some(UNIX_EPOCH),
+            max_lag: None,
         }
     }
 }
@@ -264,3 +451,25 @@ pub struct TopicReaderCommitMarker {
     pub(crate) topic: String,
     pub(crate) epoch: usize,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ydb_grpc::ydb_proto::topic::stream_read_message::init_request::TopicReadSettings;
+
+    #[test]
+    fn selector_max_lag_is_sent() {
+        let settings: TopicReadSettings = TopicSelector::builder()
+            .path("topic")
+            .max_lag(Duration::from_secs(300))
+            .build()
+            .into_raw_topic_read_setting()
+            .into();
+        assert_eq!(settings.max_lag.map(|lag| lag.seconds), Some(300));
+
+        let settings: TopicReadSettings = TopicSelector::new("topic")
+            .into_raw_topic_read_setting()
+            .into();
+        assert!(settings.max_lag.is_none());
+    }
+}
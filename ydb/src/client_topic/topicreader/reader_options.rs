@@ -2,6 +2,7 @@ use crate::client_topic::compression::CompressionDecoder;
 use crate::client_topic::topicreader::reader::TopicSelectors;
 use crate::retry::{IndefiniteRetrier, Retry};
 use std::sync::Arc;
+use std::time::Duration;
 
 #[derive(bon::Builder, Clone)]
 pub struct TopicReaderOptions {
@@ -19,6 +20,40 @@ pub struct TopicReaderOptions {
     #[builder(default = 1000)]
     pub(crate) batch_size: usize,
 
+    /// Deliver partition session lifecycle events through
+    /// [`crate::TopicReader::partition_session_events`].
+    ///
+    /// When enabled, partition assignments and graceful releases wait for the user confirmation.
+    #[builder(default = false)]
+    pub(crate) partition_session_events: bool,
+
+    /// Read partition data straight from the partition nodes instead of through the node
+    /// serving the read session.
+    ///
+    /// Takes the proxy node off the data path; transparent for [`crate::TopicReader::read_batch`].
+    #[builder(default = false)]
+    pub(crate) direct_read: bool,
+
+    /// Glue messages split by a writer with `chunk_size` back together.
+    ///
+    /// A reassembled message has the offset and commit marker end of its last chunk. Messages
+    /// of other producers interleaved with the chunks are returned before it, and their offsets
+    /// are committed with it.
+    #[builder(default = false)]
+    pub(crate) reassemble_chunks: bool,
+
+    /// Memory for chunks of incomplete messages per partition, with `reassemble_chunks`.
+    ///
+    /// The oldest incomplete messages are dropped when it is exceeded, so it also limits
+    /// the size of a reassembled message.
+    #[builder(default = 64 * 1024 * 1024)]
+    pub(crate) max_incomplete_chunks_bytes: usize,
+
+    /// Time to wait for the rest of a chunked message before dropping it, with
+    /// `reassemble_chunks`. Checked when the next message of the partition arrives.
+    #[builder(default = Duration::from_secs(60))]
+    pub(crate) incomplete_chunks_timeout: Duration,
+
     #[builder(default = Arc::new(IndefiniteRetrier {}), setters(vis = "pub(crate)"))]
     pub(crate) retrier: Arc<dyn Retry>,
 }
@@ -75,13 +75,19 @@ impl<'a> TopicReaderTx<'a> {
     #[instrument(name = "ydb.TopicReaderTx.ReadBatch", skip_all, fields(db.system.name = "ydb"), err)]
     pub async fn read_batch(&mut self) -> YdbResult<TopicReaderBatch> {
         let batch = self.inner.read_batch_inner().await?;
-        if let Err(err) = self.update_offsets_in_transaction(&batch).await {
+        self.add_batch(&batch).await?;
+        Ok(batch)
+    }
+
+    /// Commits offsets of a batch, read before the transaction, together with the transaction.
+    pub(super) async fn add_batch(&mut self, batch: &TopicReaderBatch) -> YdbResult<()> {
+        if let Err(err) = self.update_offsets_in_transaction(batch).await {
             let _ = self.runtime.force_reconnection(YdbError::custom(
                 "UpdateOffsetsInTransaction failed after reading batch",
             ));
             return Err(err);
         }
-        Ok(batch)
+        Ok(())
     }
 
     async fn update_offsets_in_transaction(&mut self, batch: &TopicReaderBatch) -> YdbResult<()> {
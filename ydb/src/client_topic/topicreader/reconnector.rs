@@ -16,6 +16,7 @@ use crate::{YdbError, YdbResult};
 
 use super::auth_token_sender::AuthTokenSender;
 use super::decompressor::Decompressor;
+use super::direct_reader::DirectReader;
 use super::grpc_streamer::GrpcStreamer;
 use super::reader_options::TopicReaderOptions;
 use super::runtime;
@@ -35,12 +36,13 @@ pub(super) struct ConnectionAttempt {
 
 /// Manages the topic reader connection loop in a background task.
 ///
-/// Each connection runs three sibling tasks for its lifetime:
+/// Each connection runs three sibling tasks (four in direct read mode) for its lifetime:
 /// ```text
 /// connection (one epoch)
 ///   |- GrpcStreamer   (receive_loop, send_loop)
 ///   |- Decompressor   (schedule_loop, forward_loop)
-///   `- Tokenizer
+///   |- Tokenizer
+///   `- DirectReader   (direct read mode only, one stream per partition node)
 /// ```
 ///
 /// Errors bubble up from tasks to the reconnector:
@@ -77,7 +79,16 @@ impl Reconnector {
         cancellation_token: CancellationToken,
         reader_id: usize,
     ) -> Self {
-        let runtime = runtime::RuntimeHandle::new(reader_id);
+        let runtime = runtime::RuntimeHandle::new(
+            reader_id,
+            reader_options.partition_session_events,
+            reader_options
+                .reassemble_chunks
+                .then_some(runtime::ChunkLimits {
+                    max_bytes: reader_options.max_incomplete_chunks_bytes,
+                    timeout: reader_options.incomplete_chunks_timeout,
+                }),
+        );
 
         Self {
             manager,
@@ -231,10 +242,24 @@ impl Reconnector {
         let (outgoing_tx, outgoing_rx) = mpsc::unbounded_channel();
         let (decomp_input_tx, decomp_input_rx) = mpsc::unbounded_channel::<RawFromServer>();
 
-        let grpc = GrpcStreamer::new(attempt_ctx, decomp_input_tx, outgoing_rx).await?;
+        let grpc = GrpcStreamer::new(attempt_ctx, decomp_input_tx.clone(), outgoing_rx).await?;
+
+        let mut connection = runtime::Connection::new(outgoing_tx.clone(), attempt_ctx.epoch);
+        let direct_reader = if attempt_ctx.options.direct_read {
+            let (direct_read_tx, direct_read_rx) = mpsc::unbounded_channel();
+            connection = connection.with_direct_read(direct_read_tx);
+            Some(DirectReader::new(
+                attempt_ctx,
+                grpc.session_id().to_string(),
+                direct_read_rx,
+                decomp_input_tx,
+            ))
+        } else {
+            None
+        };
 
         runtime.install_connection(
-            runtime::Connection::new(outgoing_tx.clone(), attempt_ctx.epoch),
+            connection,
             YdbError::Transport(format!(
                 "topic reader switching to connection epoch {}",
                 attempt_ctx.epoch
@@ -248,6 +273,9 @@ impl Reconnector {
         tasks.spawn(grpc.run());
         tasks.spawn(decompressor.run());
         tasks.spawn(auth_token_sender.run());
+        if let Some(direct_reader) = direct_reader {
+            tasks.spawn(direct_reader.run());
+        }
 
         Ok(tasks)
     }
@@ -0,0 +1,413 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use tracing::warn;
+
+use crate::client_topic::topicreader::messages::TopicReaderMessage;
+use crate::client_topic::topicwriter::chunking::{CHUNK_COUNT_KEY, CHUNK_ID_KEY, CHUNK_INDEX_KEY};
+
+struct ChunkHeader {
+    id: String,
+    index: usize,
+    count: usize,
+}
+
+impl ChunkHeader {
+    fn parse(message: &TopicReaderMessage) -> Option<Self> {
+        let id = message.metadata_value(CHUNK_ID_KEY)?;
+        let number = |key| {
+            std::str::from_utf8(message.metadata_value(key)?)
+                .ok()?
+                .parse::<usize>()
+                .ok()
+        };
+        Some(Self {
+            id: String::from_utf8_lossy(id).into_owned(),
+            index: number(CHUNK_INDEX_KEY)?,
+            count: number(CHUNK_COUNT_KEY)?,
+        })
+    }
+}
+
+/// Bounds on chunks held until their message is complete, per partition session.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ChunkLimits {
+    pub(crate) max_bytes: usize,
+    pub(crate) timeout: Duration,
+}
+
+struct Incomplete {
+    id: String,
+    count: usize,
+    chunks: Vec<TopicReaderMessage>,
+    bytes: usize,
+    started_at: Instant,
+}
+
+impl Incomplete {
+    fn start_offset(&self) -> i64 {
+        self.chunks[0].commit_marker.start_offset
+    }
+}
+
+/// Glues chunked messages of one partition session back together.
+///
+/// Messages are returned as soon as they are complete, so messages interleaved with the chunks
+/// come before the reassembled one. Commit ranges follow the return order and never pass the
+/// first chunk of an incomplete message: messages returned before it is complete get ranges
+/// ending there, and the reassembled message covers the rest.
+///
+/// An incomplete message is dropped when its producer writes anything else before the last
+/// chunk, e.g. after a writer restart, when it waits longer than [`ChunkLimits::timeout`] or
+/// when held chunks exceed [`ChunkLimits::max_bytes`]. Offsets of dropped chunks are committed
+/// with the next returned message.
+pub(super) struct ChunkAssembler {
+    limits: ChunkLimits,
+    // Ordered by the offset of the first chunk.
+    incomplete: VecDeque<Incomplete>,
+    ready: Vec<TopicReaderMessage>,
+    held_bytes: usize,
+    // Commit marker end of the last returned message.
+    returned_end_offset: i64,
+}
+
+impl ChunkAssembler {
+    pub(super) fn new(limits: ChunkLimits, start_offset: i64) -> Self {
+        Self {
+            limits,
+            incomplete: VecDeque::new(),
+            ready: Vec::new(),
+            held_bytes: 0,
+            returned_end_offset: start_offset,
+        }
+    }
+
+    /// Returns read credit of the message if it is a chunk held until its message is complete.
+    pub(super) fn push(&mut self, mut message: TopicReaderMessage) -> i64 {
+        self.drop_expired();
+        self.abandon_previous(&message);
+
+        let Some(header) = ChunkHeader::parse(&message) else {
+            self.ready.push(message);
+            return 0;
+        };
+        let read_credit = std::mem::take(&mut message.bytes_to_release);
+
+        let position = if header.index == 0 {
+            self.incomplete.push_back(Incomplete {
+                id: header.id,
+                count: header.count,
+                chunks: Vec::new(),
+                bytes: 0,
+                started_at: Instant::now(),
+            });
+            self.incomplete.len() - 1
+        } else {
+            let position = self.incomplete.iter().rposition(|incomplete| {
+                incomplete.id == header.id && incomplete.chunks.len() == header.index
+            });
+            let Some(position) = position else {
+                warn!(
+                    chunk_id = header.id,
+                    chunk_index = header.index,
+                    offset = message.offset,
+                    "topic reader skipped chunk without its first chunk"
+                );
+                return read_credit;
+            };
+            position
+        };
+
+        let Some(incomplete) = self.incomplete.get_mut(position) else {
+            return read_credit;
+        };
+        incomplete.bytes += message.data_len();
+        self.held_bytes += message.data_len();
+        incomplete.chunks.push(message);
+        if incomplete.chunks.len() >= incomplete.count
+            && let Some(complete) = self.incomplete.remove(position)
+        {
+            self.held_bytes -= complete.bytes;
+            self.ready.push(assemble(complete.chunks));
+        }
+
+        self.drop_oversized();
+        read_credit
+    }
+
+    /// Messages ready to be read, with commit ranges following each other.
+    pub(super) fn pop_ready(&mut self) -> Vec<TopicReaderMessage> {
+        let barrier = self.incomplete.iter().map(Incomplete::start_offset).min();
+        let mut ready = std::mem::take(&mut self.ready);
+        for message in &mut ready {
+            let marker = &mut message.commit_marker;
+            marker.start_offset = self.returned_end_offset;
+            if let Some(barrier) = barrier {
+                marker.end_offset = marker.end_offset.min(barrier);
+            }
+            marker.end_offset = marker.end_offset.max(marker.start_offset);
+            self.returned_end_offset = marker.end_offset;
+        }
+        ready
+    }
+
+    /// Drops incomplete messages, e.g. when server ended the partition session.
+    pub(super) fn drop_incomplete(&mut self) {
+        while let Some(incomplete) = self.incomplete.pop_front() {
+            self.drop_message(incomplete, "session ended");
+        }
+    }
+
+    // A producer writes chunks of one message in a row, so any other message of the same
+    // producer means the incomplete message will never be finished.
+    fn abandon_previous(&mut self, message: &TopicReaderMessage) {
+        let header = ChunkHeader::parse(message);
+        let (abandoned, kept): (VecDeque<_>, _) = std::mem::take(&mut self.incomplete)
+            .into_iter()
+            .partition(|incomplete| {
+                let continues = header.as_ref().is_some_and(|header| {
+                    header.id == incomplete.id && header.index == incomplete.chunks.len()
+                });
+                !continues && incomplete.chunks[0].get_producer_id() == message.get_producer_id()
+            });
+        self.incomplete = kept;
+        for incomplete in abandoned {
+            self.drop_message(incomplete, "producer wrote another message");
+        }
+    }
+
+    fn drop_expired(&mut self) {
+        let timeout = self.limits.timeout;
+        let (expired, kept): (VecDeque<_>, _) = std::mem::take(&mut self.incomplete)
+            .into_iter()
+            .partition(|incomplete| incomplete.started_at.elapsed() >= timeout);
+        self.incomplete = kept;
+        for incomplete in expired {
+            self.drop_message(incomplete, "timeout");
+        }
+    }
+
+    // Oldest messages go first: they are the least likely to be finished.
+    fn drop_oversized(&mut self) {
+        while self.held_bytes > self.limits.max_bytes {
+            let Some(incomplete) = self.incomplete.pop_front() else {
+                break;
+            };
+            self.drop_message(incomplete, "held chunks exceed max bytes");
+        }
+    }
+
+    fn drop_message(&mut self, incomplete: Incomplete, reason: &str) {
+        warn!(
+            chunk_id = incomplete.id.as_str(),
+            received_chunks = incomplete.chunks.len(),
+            offset = incomplete.chunks[0].offset,
+            reason,
+            "topic reader skipped incomplete chunked message"
+        );
+        self.held_bytes -= incomplete.bytes;
+    }
+}
+
+// The whole message gets the offset and commit marker end of its last chunk.
+fn assemble(chunks: Vec<TopicReaderMessage>) -> TopicReaderMessage {
+    let mut chunks = chunks.into_iter();
+    let mut message = chunks
+        .next()
+        .expect("chunked message has at least one chunk");
+    message.metadata.retain(|(key, _)| {
+        ![CHUNK_ID_KEY, CHUNK_INDEX_KEY, CHUNK_COUNT_KEY].contains(&key.as_str())
+    });
+
+    let mut data = message.raw_data.take().unwrap_or_default();
+    for chunk in chunks {
+        data.extend(chunk.raw_data.unwrap_or_default());
+        message.offset = chunk.offset;
+        message.uncompressed_size += chunk.uncompressed_size;
+        message.commit_marker.end_offset = chunk.commit_marker.end_offset;
+        message.bytes_to_release += chunk.bytes_to_release;
+    }
+    message.raw_data = Some(data);
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assembler() -> ChunkAssembler {
+        ChunkAssembler::new(
+            ChunkLimits {
+                max_bytes: usize::MAX,
+                timeout: Duration::MAX,
+            },
+            0,
+        )
+    }
+
+    fn message(
+        offset: i64,
+        producer_id: &str,
+        chunk: Option<(&str, usize, usize)>,
+    ) -> TopicReaderMessage {
+        let mut message = TopicReaderMessage::test_message(0, 0);
+        message.offset = offset;
+        message.commit_marker.start_offset = offset;
+        message.commit_marker.end_offset = offset + 1;
+        message.raw_data = Some(format!("{offset};").into_bytes());
+        message.producer_id = producer_id.to_string();
+        if let Some((id, index, count)) = chunk {
+            message.metadata = vec![
+                (CHUNK_ID_KEY.to_string(), id.as_bytes().to_vec()),
+                (CHUNK_INDEX_KEY.to_string(), index.to_string().into_bytes()),
+                (CHUNK_COUNT_KEY.to_string(), count.to_string().into_bytes()),
+            ];
+        }
+        message
+    }
+
+    fn summary(messages: &[TopicReaderMessage]) -> Vec<(i64, i64, String)> {
+        messages
+            .iter()
+            .map(|message| {
+                (
+                    message.commit_marker.start_offset,
+                    message.commit_marker.end_offset,
+                    String::from_utf8(message.raw_data.clone().unwrap_or_default()).unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn chunks_are_reassembled() {
+        let mut assembler = assembler();
+
+        assembler.push(message(0, "p", Some(("a", 0, 3))));
+        assembler.push(message(1, "p", Some(("a", 1, 3))));
+        assert!(assembler.pop_ready().is_empty());
+
+        assembler.push(message(2, "p", Some(("a", 2, 3))));
+        let ready = assembler.pop_ready();
+        assert_eq!(summary(&ready), [(0, 3, "0;1;2;".to_string())]);
+        assert_eq!(ready[0].offset, 2);
+        assert!(ready[0].metadata().is_empty());
+    }
+
+    #[test]
+    fn other_producer_messages_are_returned_first_and_committed_with_chunked_message() {
+        let mut assembler = assembler();
+
+        assembler.push(message(0, "p1", None));
+        assembler.push(message(1, "p1", Some(("a", 0, 2))));
+        assembler.push(message(2, "p2", None));
+        assert_eq!(
+            summary(&assembler.pop_ready()),
+            [(0, 1, "0;".to_string()), (1, 1, "2;".to_string())]
+        );
+
+        assembler.push(message(3, "p1", Some(("a", 1, 2))));
+        assembler.push(message(4, "p2", None));
+        assert_eq!(
+            summary(&assembler.pop_ready()),
+            [(1, 4, "1;3;".to_string()), (4, 5, "4;".to_string())]
+        );
+    }
+
+    #[test]
+    fn commit_ranges_stop_at_oldest_incomplete_message() {
+        let mut assembler = assembler();
+
+        assembler.push(message(0, "p1", Some(("a", 0, 2))));
+        assembler.push(message(1, "p2", Some(("b", 0, 2))));
+        assembler.push(message(2, "p1", Some(("a", 1, 2))));
+        assert_eq!(
+            summary(&assembler.pop_ready()),
+            [(0, 1, "0;2;".to_string())]
+        );
+
+        assembler.push(message(3, "p2", Some(("b", 1, 2))));
+        assert_eq!(
+            summary(&assembler.pop_ready()),
+            [(1, 4, "1;3;".to_string())]
+        );
+    }
+
+    #[test]
+    fn abandoned_chunks_are_committed_with_next_message() {
+        let mut assembler = assembler();
+
+        assembler.push(message(0, "p", Some(("a", 0, 3))));
+        assembler.push(message(1, "p", Some(("a", 1, 3))));
+        // Writer restarted and wrote the message again.
+        assembler.push(message(2, "p", Some(("b", 0, 1))));
+
+        assert_eq!(summary(&assembler.pop_ready()), [(0, 3, "2;".to_string())]);
+    }
+
+    #[test]
+    fn chunk_without_first_chunk_is_skipped() {
+        let mut assembler = assembler();
+
+        assembler.push(message(0, "p", Some(("a", 1, 2))));
+        assembler.push(message(1, "p", None));
+
+        assert_eq!(summary(&assembler.pop_ready()), [(0, 2, "1;".to_string())]);
+    }
+
+    #[test]
+    fn expired_message_is_dropped() {
+        let mut assembler = ChunkAssembler::new(
+            ChunkLimits {
+                max_bytes: usize::MAX,
+                timeout: Duration::ZERO,
+            },
+            0,
+        );
+
+        assembler.push(message(0, "p1", Some(("a", 0, 2))));
+        assembler.push(message(1, "p2", None));
+        assembler.push(message(2, "p1", Some(("a", 1, 2))));
+
+        assert_eq!(summary(&assembler.pop_ready()), [(0, 2, "1;".to_string())]);
+        assert_eq!(assembler.held_bytes, 0);
+    }
+
+    #[test]
+    fn oversized_messages_are_dropped_oldest_first() {
+        let mut assembler = ChunkAssembler::new(
+            ChunkLimits {
+                max_bytes: 3,
+                timeout: Duration::MAX,
+            },
+            0,
+        );
+
+        let mut chunk = message(0, "p1", Some(("a", 0, 2)));
+        chunk.bytes_to_release = 10;
+        assert_eq!(assembler.push(chunk), 10);
+        assembler.push(message(1, "p2", Some(("b", 0, 2))));
+        assembler.push(message(2, "p2", Some(("b", 1, 2))));
+
+        assert_eq!(
+            summary(&assembler.pop_ready()),
+            [(0, 3, "1;2;".to_string())]
+        );
+        assert_eq!(assembler.held_bytes, 0);
+    }
+
+    #[test]
+    fn drop_incomplete_releases_commit_ranges() {
+        let mut assembler = assembler();
+
+        assembler.push(message(0, "p1", Some(("a", 0, 2))));
+        assembler.push(message(1, "p2", None));
+        assembler.drop_incomplete();
+        assembler.push(message(2, "p2", None));
+
+        assert_eq!(
+            summary(&assembler.pop_ready()),
+            [(0, 2, "1;".to_string()), (2, 3, "2;".to_string())]
+        );
+    }
+}
@@ -1,10 +1,13 @@
 use tokio::sync::mpsc;
 
+use crate::client_topic::topicreader::direct_reader::DirectReadCommand;
 use crate::grpc_wrapper::raw_topic_service::stream_read::messages::RawFromClientOneOf;
 use crate::{YdbError, YdbResult};
 
 pub(crate) struct Connection {
     outgoing_tx: mpsc::UnboundedSender<RawFromClientOneOf>,
+    // Present in direct read mode only.
+    direct_read_tx: Option<mpsc::UnboundedSender<DirectReadCommand>>,
     epoch: usize,
 }
 
@@ -13,7 +16,19 @@ impl Connection {
         outgoing_tx: mpsc::UnboundedSender<RawFromClientOneOf>,
         epoch: usize,
     ) -> Self {
-        Self { outgoing_tx, epoch }
+        Self {
+            outgoing_tx,
+            direct_read_tx: None,
+            epoch,
+        }
+    }
+
+    pub(crate) fn with_direct_read(
+        mut self,
+        direct_read_tx: mpsc::UnboundedSender<DirectReadCommand>,
+    ) -> Self {
+        self.direct_read_tx = Some(direct_read_tx);
+        self
     }
 
     pub(crate) fn epoch(&self) -> usize {
@@ -25,4 +40,15 @@ impl Connection {
             .send(message)
             .map_err(|err| YdbError::Transport(format!("topic reader commit send failed: {err}")))
     }
+
+    pub(crate) fn send_direct_read(&self, command: DirectReadCommand) -> YdbResult<()> {
+        let Some(direct_read_tx) = &self.direct_read_tx else {
+            return Ok(());
+        };
+        direct_read_tx.send(command).map_err(|err| {
+            YdbError::Transport(format!(
+                "topic reader direct read command send failed: {err}"
+            ))
+        })
+    }
 }
@@ -1,13 +1,12 @@
 use std::collections::{HashMap, VecDeque};
 
-#[cfg(test)]
-use crate::client_topic::topicreader::ids::PartitionId;
-use crate::client_topic::topicreader::ids::PartitionSessionId;
+use crate::client_topic::topicreader::ids::{PartitionId, PartitionSessionId};
 use crate::client_topic::topicreader::messages::{TopicReaderBatch, TopicReaderMessage};
 use crate::client_topic::topicreader::partition_state::PartitionSession;
 use crate::grpc_wrapper::raw_topic_service::stream_read::messages::RawBatch;
 use crate::{YdbError, YdbResult};
 
+use super::chunk_assembler::{ChunkAssembler, ChunkLimits};
 use super::round_robin::RoundRobin;
 
 pub(super) struct BufferedBatch {
@@ -19,24 +18,59 @@ pub(super) struct BufferedBatch {
 struct PartitionEntry {
     session: PartitionSession,
     queue: VecDeque<TopicReaderMessage>,
+    // Offset acknowledged by server as committed.
+    committed_offset: i64,
+    // Commit marker end of the last read message.
+    read_end_offset: i64,
+    // Set when server ended the partition session because of split or merge.
+    child_partition_ids: Option<Vec<PartitionId>>,
+    // Chunks of incomplete messages; dropped with the session, the server re-sends them
+    // to the next one because they are never committed.
+    chunks: Option<ChunkAssembler>,
 }
 
 impl PartitionEntry {
-    fn new(session: PartitionSession) -> Self {
+    fn new(session: PartitionSession, chunk_limits: Option<ChunkLimits>) -> Self {
+        let start_offset = session.next_commit_offset_start;
         Self {
             session,
             queue: VecDeque::new(),
+            committed_offset: start_offset,
+            read_end_offset: start_offset,
+            child_partition_ids: None,
+            chunks: chunk_limits.map(|limits| ChunkAssembler::new(limits, start_offset)),
         }
     }
+
+    fn is_parent_of(&self, session: &PartitionSession) -> bool {
+        self.session.topic == session.topic
+            && self
+                .child_partition_ids
+                .as_ref()
+                .is_some_and(|children| children.contains(&session.partition_id))
+    }
+
+    // All messages received so far were read and their commits acknowledged.
+    fn is_committed(&self) -> bool {
+        self.queue.is_empty() && self.committed_offset >= self.read_end_offset
+    }
 }
 
 #[derive(Default)]
 pub(super) struct MessageBuffer {
     entries: HashMap<PartitionSessionId, PartitionEntry>,
     round_robin: RoundRobin,
+    chunk_limits: Option<ChunkLimits>,
 }
 
 impl MessageBuffer {
+    pub(super) fn new(chunk_limits: Option<ChunkLimits>) -> Self {
+        Self {
+            chunk_limits,
+            ..Self::default()
+        }
+    }
+
     pub(super) fn start(&mut self, session: PartitionSession) -> YdbResult<()> {
         let psid = session.partition_session_id;
         if self.entries.contains_key(&psid) {
@@ -45,7 +79,8 @@ impl MessageBuffer {
             )));
         }
 
-        self.entries.insert(psid, PartitionEntry::new(session));
+        self.entries
+            .insert(psid, PartitionEntry::new(session, self.chunk_limits));
         self.round_robin.push(psid);
         Ok(())
     }
@@ -55,17 +90,73 @@ impl MessageBuffer {
         self.entries.remove(&partition_session_id).is_some()
     }
 
+    /// Marks the session as fully read by server. Buffered messages of its child partitions
+    /// are held back until all messages of the session are committed. Incomplete chunked
+    /// messages of the session are dropped: the rest of their chunks will never come.
+    pub(super) fn end(
+        &mut self,
+        partition_session_id: PartitionSessionId,
+        child_partition_ids: Vec<PartitionId>,
+    ) -> bool {
+        match self.entries.get_mut(&partition_session_id) {
+            Some(entry) => {
+                entry.child_partition_ids = Some(child_partition_ids);
+                if let Some(chunks) = &mut entry.chunks {
+                    chunks.drop_incomplete();
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Records the committed offset acknowledged by server. Returns true if the session is
+    /// ended and fully committed now, so messages of its children may be read.
+    pub(super) fn ack_commit(
+        &mut self,
+        partition_session_id: PartitionSessionId,
+        committed_offset: i64,
+    ) -> bool {
+        let Some(entry) = self.entries.get_mut(&partition_session_id) else {
+            return false;
+        };
+        entry.committed_offset = entry.committed_offset.max(committed_offset);
+        entry.child_partition_ids.is_some() && entry.is_committed()
+    }
+
+    fn has_uncommitted_parent(&self, session: &PartitionSession) -> bool {
+        self.entries
+            .values()
+            .any(|entry| entry.is_parent_of(session) && !entry.is_committed())
+    }
+
     pub(super) fn is_active_session(&self, partition_session_id: PartitionSessionId) -> bool {
         self.entries.contains_key(&partition_session_id)
     }
 
+    pub(super) fn session(
+        &self,
+        partition_session_id: PartitionSessionId,
+    ) -> Option<&PartitionSession> {
+        self.entries
+            .get(&partition_session_id)
+            .map(|entry| &entry.session)
+    }
+
+    pub(super) fn sessions(&self) -> impl Iterator<Item = &PartitionSession> {
+        self.entries.values().map(|entry| &entry.session)
+    }
+
+    /// Returns read credit of chunks held until their message is complete, to be given back to
+    /// the server right away: a chunked message may be larger than the whole read buffer.
+    /// Memory of held chunks is bounded by [`ChunkLimits`] instead.
     pub(super) fn push_raw_batch(
         &mut self,
         batch: RawBatch,
         partition_session_id: PartitionSessionId,
         reader_id: usize,
         epoch: usize,
-    ) -> YdbResult<()> {
+    ) -> YdbResult<i64> {
         if batch.message_data.is_empty() {
             return Err(YdbError::custom(format!(
                 "topic reader received empty batch for partition session {partition_session_id}"
@@ -84,46 +175,74 @@ impl MessageBuffer {
         if let Some(last) = messages.last_mut() {
             last.bytes_to_release = batch_bytes;
         }
-        partition_entry.queue.extend(messages);
-        Ok(())
+
+        let Some(chunks) = &mut partition_entry.chunks else {
+            partition_entry.queue.extend(messages);
+            return Ok(0);
+        };
+        let held_bytes = messages
+            .into_iter()
+            .map(|message| chunks.push(message))
+            .sum();
+        partition_entry.queue.extend(chunks.pop_ready());
+        Ok(held_bytes)
     }
 
     #[cfg(test)]
     pub(super) fn push_batch(&mut self, messages: Vec<TopicReaderMessage>) {
         for message in messages {
             let partition_session_id = message.get_commit_marker().partition_session_id;
-            let partition_entry = self
-                .entries
-                .entry(partition_session_id)
-                .or_insert_with(|| PartitionEntry::new(PartitionSession::from_message(&message)));
+            let partition_entry = self.entries.entry(partition_session_id).or_insert_with(|| {
+                PartitionEntry::new(PartitionSession::from_message(&message), None)
+            });
             partition_entry.queue.push_back(message);
             self.round_robin.push(partition_session_id);
         }
     }
 
-    pub(super) fn pop_batch(&mut self, cap: usize) -> YdbResult<Option<BufferedBatch>> {
+    /// Pops up to `cap` messages of one partition session, stopping before the payload size
+    /// exceeds `max_bytes`. The first message is always taken, even if it is larger.
+    pub(super) fn pop_batch(
+        &mut self,
+        cap: usize,
+        max_bytes: usize,
+    ) -> YdbResult<Option<BufferedBatch>> {
         for _ in 0..self.round_robin.len() {
             let Some(psid) = self.round_robin.next() else {
                 return Ok(None);
             };
-            let Some(partition_entry) = self.entries.get_mut(&psid) else {
+            let Some(partition_entry) = self.entries.get(&psid) else {
                 return Err(YdbError::custom(format!(
                     "topic reader round robin contains unknown partition session {psid}"
                 )));
             };
 
-            if partition_entry.queue.is_empty() {
+            if partition_entry.queue.is_empty()
+                || self.has_uncommitted_parent(&partition_entry.session)
+            {
                 continue;
             }
+            let Some(partition_entry) = self.entries.get_mut(&psid) else {
+                continue;
+            };
 
             let take = cap.min(partition_entry.queue.len());
             let mut out = Vec::with_capacity(take);
             let mut bytes = 0;
+            let mut data_bytes = 0usize;
             for _ in 0..take {
+                let Some(next) = partition_entry.queue.front() else {
+                    break;
+                };
+                data_bytes = data_bytes.saturating_add(next.data_len());
+                if !out.is_empty() && data_bytes > max_bytes {
+                    break;
+                }
                 let Some(message) = partition_entry.queue.pop_front() else {
                     break;
                 };
                 bytes += message.bytes_to_release;
+                partition_entry.read_end_offset = message.commit_marker.end_offset;
                 out.push(message);
             }
 
@@ -147,11 +266,14 @@ impl MessageBuffer {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::client_topic::topicwriter::chunking::{
+        CHUNK_COUNT_KEY, CHUNK_ID_KEY, CHUNK_INDEX_KEY,
+    };
     use crate::grpc_wrapper::raw_common_types::Timestamp;
     use crate::grpc_wrapper::raw_topic_service::common::codecs::RawCodec;
     use crate::grpc_wrapper::raw_topic_service::stream_read::messages::RawMessageData;
     use std::collections::HashMap;
-    use std::time::UNIX_EPOCH;
+    use std::time::{Duration, UNIX_EPOCH};
     use ydb_grpc::ydb_proto::topic::Codec;
 
     fn session(partition_session_id: i64, partition_id: i64) -> PartitionSession {
@@ -160,6 +282,7 @@ mod tests {
             partition_id: PartitionId::from_raw(partition_id),
             topic: String::new(),
             next_commit_offset_start: 0,
+            location: None,
         }
     }
 
@@ -178,13 +301,59 @@ mod tests {
                     seq_no: offset,
                     created_at: None,
                     uncompressed_size: 0,
-                    data: Vec::new(),
+                    metadata_items: vec![],
+                    data: vec![0; read_session_size_bytes as usize],
                     read_session_size_bytes,
                 })
                 .collect(),
         }
     }
 
+    fn chunk(mut batch: RawBatch, index: usize, count: usize) -> RawBatch {
+        for message in &mut batch.message_data {
+            message.metadata_items = vec![
+                (CHUNK_ID_KEY.to_string(), b"id".to_vec()),
+                (CHUNK_INDEX_KEY.to_string(), index.to_string().into_bytes()),
+                (CHUNK_COUNT_KEY.to_string(), count.to_string().into_bytes()),
+            ];
+        }
+        batch
+    }
+
+    #[test]
+    fn incomplete_chunks_are_held_and_their_read_credit_released() {
+        let mut buffer = MessageBuffer::new(Some(ChunkLimits {
+            max_bytes: usize::MAX,
+            timeout: Duration::MAX,
+        }));
+        buffer.start(session(1, 1)).unwrap();
+
+        let held = buffer
+            .push_raw_batch(
+                chunk(raw_batch([(0, 3)]), 0, 2),
+                PartitionSessionId::from_raw(1),
+                0,
+                0,
+            )
+            .unwrap();
+        assert_eq!(held, 3);
+        assert!(buffer.pop_batch(10, usize::MAX).unwrap().is_none());
+
+        let held = buffer
+            .push_raw_batch(
+                chunk(raw_batch([(1, 4)]), 1, 2),
+                PartitionSessionId::from_raw(1),
+                0,
+                0,
+            )
+            .unwrap();
+        assert_eq!(held, 4);
+        let batch = buffer.pop_batch(10, usize::MAX).unwrap().unwrap();
+        assert_eq!(batch.messages.len(), 1);
+        assert_eq!(batch.messages[0].data_len(), 7);
+        assert_eq!(batch.bytes_to_release, 0);
+    }
+
     #[test]
     fn pop_batch_round_robins_between_sessions() {
         let mut buffer = MessageBuffer::default();
@@ -202,9 +371,9 @@ mod tests {
             .push_raw_batch(raw_batch([(0, 1)]), PartitionSessionId::from_raw(2), 0, 0)
             .unwrap();
 
-        let first = buffer.pop_batch(1).unwrap().unwrap();
-        let second = buffer.pop_batch(1).unwrap().unwrap();
-        let third = buffer.pop_batch(1).unwrap().unwrap();
+        let first = buffer.pop_batch(1, usize::MAX).unwrap().unwrap();
+        let second = buffer.pop_batch(1, usize::MAX).unwrap().unwrap();
+        let third = buffer.pop_batch(1, usize::MAX).unwrap().unwrap();
 
         assert_eq!(
             first.messages[0].get_commit_marker().partition_session_id,
@@ -220,6 +389,63 @@ mod tests {
         );
     }
 
+    #[test]
+    fn pop_batch_holds_child_partition_until_parent_is_committed() {
+        let mut buffer = MessageBuffer::default();
+        buffer.start(session(1, 1)).unwrap();
+        buffer.start(session(2, 2)).unwrap();
+        buffer
+            .push_raw_batch(
+                raw_batch([(0, 1), (1, 1)]),
+                PartitionSessionId::from_raw(1),
+                0,
+                0,
+            )
+            .unwrap();
+        buffer
+            .push_raw_batch(raw_batch([(0, 1)]), PartitionSessionId::from_raw(2), 0, 0)
+            .unwrap();
+        assert!(buffer.end(
+            PartitionSessionId::from_raw(1),
+            vec![PartitionId::from_raw(2)]
+        ));
+
+        let popped: Vec<_> = std::iter::from_fn(|| buffer.pop_batch(1, usize::MAX).unwrap())
+            .map(|batch| batch.messages[0].get_commit_marker().partition_session_id)
+            .collect();
+        assert_eq!(popped, [1, 1].map(PartitionSessionId::from_raw).to_vec());
+
+        assert!(!buffer.ack_commit(PartitionSessionId::from_raw(1), 1));
+        assert!(buffer.pop_batch(1, usize::MAX).unwrap().is_none());
+
+        assert!(buffer.ack_commit(PartitionSessionId::from_raw(1), 2));
+        let child = buffer.pop_batch(1, usize::MAX).unwrap().unwrap();
+        assert_eq!(
+            child.messages[0].get_commit_marker().partition_session_id,
+            PartitionSessionId::from_raw(2)
+        );
+    }
+
+    #[test]
+    fn pop_batch_respects_max_bytes() {
+        let mut buffer = MessageBuffer::default();
+        buffer.start(session(1, 1)).unwrap();
+        buffer
+            .push_raw_batch(
+                raw_batch([(0, 1), (1, 1), (2, 1)]),
+                PartitionSessionId::from_raw(1),
+                0,
+                0,
+            )
+            .unwrap();
+
+        let first = buffer.pop_batch(10, 1).unwrap().unwrap();
+        assert_eq!(first.messages.len(), 1);
+
+        let rest = buffer.pop_batch(10, usize::MAX).unwrap().unwrap();
+        assert_eq!(rest.messages.len(), 2);
+    }
+
     #[test]
     fn unopened_session_batch_returns_error() {
         let mut buffer = MessageBuffer::default();
@@ -234,7 +460,7 @@ mod tests {
                 if message
                     == "topic reader received messages for unopened partition session 1"
         ));
-        assert!(buffer.pop_batch(1).unwrap().is_none());
+        assert!(buffer.pop_batch(1, usize::MAX).unwrap().is_none());
     }
 
     #[test]
@@ -251,6 +477,6 @@ mod tests {
             YdbError::Custom(message)
                 if message == "topic reader received empty batch for partition session 1"
         ));
-        assert!(buffer.pop_batch(1).unwrap().is_none());
+        assert!(buffer.pop_batch(1, usize::MAX).unwrap().is_none());
     }
 }
@@ -1,8 +1,10 @@
+mod chunk_assembler;
 mod connection;
 mod message_buffer;
 mod pending_commits;
 mod round_robin;
 mod runtime_handle;
 
+pub(super) use chunk_assembler::ChunkLimits;
 pub(super) use connection::Connection;
-pub(super) use runtime_handle::RuntimeHandle;
+pub(super) use runtime_handle::{RuntimeHandle, WeakRuntimeHandle};
@@ -13,6 +13,8 @@ type PartitionPendingCommits = BTreeMap<Reverse<i64>, CommitAckSender>;
 #[derive(Default)]
 pub(super) struct PendingCommits {
     sessions: HashMap<PartitionSessionId, PartitionPendingCommits>,
+    // Resolved once no commits are pending, with the first commit failure seen meanwhile.
+    flush_waiters: Vec<(CommitAckSender, Option<YdbError>)>,
 }
 
 impl PendingCommits {
@@ -29,6 +31,18 @@ impl PendingCommits {
         receiver
     }
 
+    pub(super) fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+    }
+
+    /// Returns a receiver resolved when all currently pending commits are settled.
+    pub(super) fn flush(&mut self) -> CommitAckReceiver {
+        let (sender, receiver) = oneshot::channel();
+        self.flush_waiters.push((sender, None));
+        self.resolve_flush_waiters();
+        receiver
+    }
+
     pub(super) fn ack(
         &mut self,
         committed_offsets: impl IntoIterator<Item = (PartitionSessionId, i64)>,
@@ -36,17 +50,23 @@ impl PendingCommits {
         for (psid, offset) in committed_offsets {
             self.ack_partition(psid, offset);
         }
+        self.resolve_flush_waiters();
     }
 
     pub(super) fn fail_all(&mut self, reason: &YdbError) {
         let sessions = std::mem::take(&mut self.sessions);
+        if !sessions.is_empty() {
+            self.record_flush_error(reason);
+        }
         for session in sessions.into_values() {
             Self::fail_commits(session, reason);
         }
+        self.resolve_flush_waiters();
     }
 
     fn fail_session(&mut self, psid: PartitionSessionId, reason: &YdbError) {
         if let Some(session) = self.sessions.remove(&psid) {
+            self.record_flush_error(reason);
             Self::fail_commits(session, reason);
         }
     }
@@ -62,10 +82,16 @@ impl PendingCommits {
         };
         if let Some(sender) = session.remove(&Reverse(committed_offset)) {
             let _ = sender.send(Err(reason.clone()));
+            self.record_flush_error(reason);
         }
-        if session.is_empty() {
+        if self
+            .sessions
+            .get(&psid)
+            .is_some_and(|session| session.is_empty())
+        {
             self.sessions.remove(&psid);
         }
+        self.resolve_flush_waiters();
     }
 
     pub(super) fn stop(
@@ -78,6 +104,7 @@ impl PendingCommits {
             self.ack_partition(psid, offset);
         }
         self.fail_session(psid, reason);
+        self.resolve_flush_waiters();
     }
 
     fn ack_partition(&mut self, psid: PartitionSessionId, committed_offset: i64) {
@@ -94,6 +121,24 @@ impl PendingCommits {
         }
     }
 
+    fn record_flush_error(&mut self, reason: &YdbError) {
+        for (_, error) in &mut self.flush_waiters {
+            error.get_or_insert_with(|| reason.clone());
+        }
+    }
+
+    fn resolve_flush_waiters(&mut self) {
+        if !self.sessions.is_empty() {
+            return;
+        }
+        for (sender, error) in self.flush_waiters.drain(..) {
+            let _ = sender.send(match error {
+                Some(err) => Err(err),
+                None => Ok(()),
+            });
+        }
+    }
+
     fn ack_commits(commits: PartitionPendingCommits) {
         for sender in commits.into_values() {
             let _ = sender.send(Ok(()));
@@ -139,4 +184,31 @@ mod tests {
         drop(pending);
         assert!(matches!(ack0_2.try_recv(), Err(TryRecvError::Closed)));
     }
+
+    #[test]
+    fn flush_resolves_when_commits_are_settled() {
+        let mut pending = PendingCommits::default();
+        assert!(matches!(pending.flush().try_recv(), Ok(Ok(()))));
+
+        pending.push(psid(0), 1);
+        pending.push(psid(1), 1);
+        let mut flushed = pending.flush();
+
+        pending.ack([(psid(0), 1)]);
+        assert!(matches!(flushed.try_recv(), Err(TryRecvError::Empty)));
+
+        pending.ack([(psid(1), 1)]);
+        assert!(matches!(flushed.try_recv(), Ok(Ok(()))));
+    }
+
+    #[test]
+    fn flush_reports_failed_commits() {
+        let mut pending = PendingCommits::default();
+        pending.push(psid(0), 1);
+        pending.push(psid(0), 2);
+        let mut flushed = pending.flush();
+
+        pending.stop(psid(0), Some(1), &YdbError::custom("stopped"));
+        assert!(matches!(flushed.try_recv(), Ok(Err(_))));
+    }
 }
@@ -1,66 +1,164 @@
-use std::sync::{Arc, Mutex};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
 
 use tokio::sync::Notify;
 use tokio::sync::futures::Notified;
 use tracing::{debug, warn};
 
-use crate::client_topic::topicreader::ids::PartitionSessionId;
+use crate::client_topic::topicreader::direct_reader::DirectReadCommand;
+use crate::client_topic::topicreader::ids::{PartitionId, PartitionSessionId};
 use crate::client_topic::topicreader::messages::TopicReaderBatch;
 #[cfg(test)]
 use crate::client_topic::topicreader::messages::TopicReaderMessage;
+use crate::client_topic::topicreader::partition_events::{
+    EventTarget, PartitionSessionEnded, PartitionSessionEvent, PartitionSessionFinished,
+    PartitionSessionStarted, PartitionSessionStopping,
+};
 use crate::client_topic::topicreader::partition_state::PartitionSession;
 use crate::client_topic::topicreader::reader::TopicReaderCommitMarker;
 use crate::grpc_wrapper::raw_topic_service::common::partition::RawOffsetsRange;
+use crate::grpc_wrapper::raw_topic_service::stream_direct_read::messages::RawDirectReadResponse;
 use crate::grpc_wrapper::raw_topic_service::stream_read::messages::{
-    PartitionCommitOffset, RawCommitOffsetRequest, RawCommitOffsetResponse, RawFromClientOneOf,
-    RawFromServer, RawReadRequest, RawReadResponse, RawStartPartitionSessionRequest,
+    PartitionCommitOffset, RawCommitOffsetRequest, RawCommitOffsetResponse, RawDirectReadAck,
+    RawEndPartitionSession, RawFromClientOneOf, RawFromServer, RawPartitionLocation,
+    RawReadRequest, RawReadResponse, RawStartPartitionSessionRequest,
     RawStartPartitionSessionResponse, RawStopPartitionSessionRequest,
-    RawStopPartitionSessionResponse,
+    RawStopPartitionSessionResponse, RawUpdatePartitionSession,
 };
 use crate::{YdbError, YdbResult};
 
+use super::chunk_assembler::ChunkLimits;
 use super::connection::Connection;
 use super::message_buffer::{BufferedBatch, MessageBuffer};
 use super::pending_commits::{CommitAckReceiver, PendingCommits};
 
 const RUNTIME_HANDLE_POISONED: &str = "topic reader runtime handle mutex poisoned";
 
+#[derive(Default)]
+struct DirectReadProgress {
+    // Id of the last direct read response received for the session.
+    last_direct_read_id: i64,
+    // Graceful stop waiting for the data up to its `last_direct_read_id`.
+    delayed_stop: Option<RawStopPartitionSessionRequest>,
+}
+
 struct Active {
     buffer: MessageBuffer,
     pending_commits: PendingCommits,
+    // Started by server, but not confirmed by user yet (partition session events only).
+    pending_starts: HashMap<PartitionSessionId, PartitionSession>,
+    // Direct read mode only.
+    direct_reads: HashMap<PartitionSessionId, DirectReadProgress>,
     connection: Connection,
 }
 
 impl Active {
-    fn new(connection: Connection) -> Self {
+    fn new(connection: Connection, chunk_limits: Option<ChunkLimits>) -> Self {
         Self {
-            buffer: MessageBuffer::default(),
+            buffer: MessageBuffer::new(chunk_limits),
             pending_commits: PendingCommits::default(),
+            pending_starts: HashMap::new(),
+            direct_reads: HashMap::new(),
             connection,
         }
     }
 
+    fn is_known_session(&self, partition_session_id: PartitionSessionId) -> bool {
+        self.buffer.is_active_session(partition_session_id)
+            || self.pending_starts.contains_key(&partition_session_id)
+    }
+
+    fn ended_sessions_events(&self, connection_lost: bool) -> Vec<PartitionSessionEvent> {
+        self.buffer
+            .sessions()
+            .chain(self.pending_starts.values())
+            .map(|session| ended_event(session, connection_lost))
+            .collect()
+    }
+
+    fn stop_session(
+        &mut self,
+        partition_session_id: PartitionSessionId,
+        graceful: bool,
+        committed_offset: i64,
+    ) -> YdbResult<()> {
+        if !self.buffer.stop(partition_session_id) {
+            warn!(
+                %partition_session_id,
+                "topic reader received stop for unknown partition session"
+            );
+        }
+        self.direct_reads.remove(&partition_session_id);
+        self.connection.send_direct_read(DirectReadCommand::Stop {
+            partition_session_id,
+        })?;
+
+        self.pending_commits.stop(
+            partition_session_id,
+            Some(committed_offset),
+            &YdbError::custom(format!(
+                "partition session stopped by server: {partition_session_id}"
+            )),
+        );
+        self.connection
+            .send(RawFromClientOneOf::StopPartitionSessionResponse(
+                RawStopPartitionSessionResponse {
+                    partition_session_id: partition_session_id.into_raw(),
+                    graceful,
+                },
+            ))
+    }
+
+    fn start_direct_read(
+        &self,
+        partition_session_id: PartitionSessionId,
+        location: Option<RawPartitionLocation>,
+    ) -> YdbResult<()> {
+        let Some(location) = location else {
+            return Ok(());
+        };
+        self.connection.send_direct_read(DirectReadCommand::Start {
+            partition_session_id,
+            location,
+        })
+    }
+
     #[cfg(test)]
     fn push_batch(&mut self, messages: Vec<TopicReaderMessage>) {
         self.buffer.push_batch(messages);
     }
 
-    fn pop_batch(&mut self, cap: usize) -> YdbResult<Option<BufferedBatch>> {
-        self.buffer.pop_batch(cap)
+    fn pop_batch(&mut self, cap: usize, max_bytes: usize) -> YdbResult<Option<BufferedBatch>> {
+        self.buffer.pop_batch(cap, max_bytes)
     }
 }
 
 enum State {
     Reconnecting,
-    Active(Active),
+    Active(Box<Active>),
     Failed(YdbError),
 }
 
+#[derive(Default)]
+struct PartitionEventQueue {
+    events: Mutex<VecDeque<PartitionSessionEvent>>,
+    available: Notify,
+}
+
 struct Inner {
     state: Mutex<State>,
     messages_available: Notify,
     reader_id: usize,
     reconnect_notify: Notify,
+    // None if partition sessions are confirmed by runtime itself.
+    partition_events: Option<PartitionEventQueue>,
+    // Set by graceful close: read credit is not returned to the server anymore.
+    closing: AtomicBool,
+    // None if chunked messages are not reassembled.
+    chunk_limits: Option<ChunkLimits>,
+    // Error of commits failed by a reconnect since the last flush_commits call.
+    lost_commits: Mutex<Option<YdbError>>,
 }
 
 #[derive(Clone)]
@@ -68,26 +166,72 @@ pub(crate) struct RuntimeHandle {
     inner: Arc<Inner>,
 }
 
+// Held by partition session events, which are stored inside the runtime.
+#[derive(Clone, Debug)]
+pub(crate) struct WeakRuntimeHandle {
+    inner: Weak<Inner>,
+}
+
+impl WeakRuntimeHandle {
+    pub(crate) fn upgrade(&self) -> Option<RuntimeHandle> {
+        self.inner.upgrade().map(|inner| RuntimeHandle { inner })
+    }
+}
+
 impl RuntimeHandle {
-    pub(crate) fn new(reader_id: usize) -> Self {
+    pub(crate) fn new(
+        reader_id: usize,
+        partition_events: bool,
+        chunk_limits: Option<ChunkLimits>,
+    ) -> Self {
         Self {
             inner: Arc::new(Inner {
                 state: Mutex::new(State::Reconnecting),
                 messages_available: Notify::new(),
                 reader_id,
                 reconnect_notify: Notify::new(),
+                partition_events: partition_events.then(PartitionEventQueue::default),
+                closing: AtomicBool::new(false),
+                chunk_limits,
+                lost_commits: Mutex::new(None),
             }),
         }
     }
 
+    fn downgrade(&self) -> WeakRuntimeHandle {
+        WeakRuntimeHandle {
+            inner: Arc::downgrade(&self.inner),
+        }
+    }
+
     #[cfg(test)]
     pub(crate) fn with_connection(connection: Connection) -> Self {
         Self {
             inner: Arc::new(Inner {
-                state: Mutex::new(State::Active(Active::new(connection))),
+                state: Mutex::new(State::Active(Box::new(Active::new(connection, None)))),
+                messages_available: Notify::new(),
+                reader_id: 0,
+                reconnect_notify: Notify::new(),
+                partition_events: None,
+                closing: AtomicBool::new(false),
+                chunk_limits: None,
+                lost_commits: Mutex::new(None),
+            }),
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn with_partition_events(connection: Connection) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                state: Mutex::new(State::Active(Box::new(Active::new(connection, None)))),
                 messages_available: Notify::new(),
                 reader_id: 0,
                 reconnect_notify: Notify::new(),
+                partition_events: Some(PartitionEventQueue::default()),
+                closing: AtomicBool::new(false),
+                chunk_limits: None,
+                lost_commits: Mutex::new(None),
             }),
         }
     }
@@ -100,8 +244,14 @@ impl RuntimeHandle {
                 self.handle_start_partition_session(req)
             }
             RawFromServer::StopPartitionSessionRequest(req) => {
-                self.handle_stop_partition_session(req)
+                self.handle_stop_partition_session(req)?;
+                // Child partitions of the stopped session are not held back anymore.
+                self.inner.messages_available.notify_one();
+                Ok(())
             }
+            RawFromServer::UpdatePartitionSession(req) => self.handle_update_partition_session(req),
+            RawFromServer::EndPartitionSession(req) => self.handle_end_partition_session(req),
+            RawFromServer::DirectReadResponse(resp) => self.handle_direct_read_response(resp),
             RawFromServer::InitResponse(response) => {
                 warn!(?response, "topic reader received unexpected init response");
                 Err(YdbError::custom(format!(
@@ -129,16 +279,21 @@ impl RuntimeHandle {
             let reader_id = self.inner.reader_id;
             let epoch = active.connection.epoch();
 
+            let mut held_bytes = 0;
             for partition_data in resp.partition_data {
                 let partition_session_id =
                     PartitionSessionId::from_raw(partition_data.partition_session_id);
                 for batch in partition_data.batches {
-                    active
-                        .buffer
-                        .push_raw_batch(batch, partition_session_id, reader_id, epoch)?;
+                    held_bytes += active.buffer.push_raw_batch(
+                        batch,
+                        partition_session_id,
+                        reader_id,
+                        epoch,
+                    )?;
                     pushed = true;
                 }
             }
+            self.release_held_bytes(active, held_bytes);
         }
 
         if pushed {
@@ -147,19 +302,47 @@ impl RuntimeHandle {
         Ok(())
     }
 
+    // Gives back read credit of chunks held until their message is complete.
+    fn release_held_bytes(&self, active: &Active, held_bytes: i64) {
+        if held_bytes > 0 && !self.inner.closing.load(Ordering::Acquire) {
+            let _ = active
+                .connection
+                .send(RawFromClientOneOf::ReadRequest(RawReadRequest {
+                    bytes_size: held_bytes,
+                }));
+        }
+    }
+
     fn handle_commit_offset_response(&self, resp: RawCommitOffsetResponse) -> YdbResult<()> {
-        let committed_offsets = resp.partitions_committed_offsets.into_iter().map(|offset| {
-            (
-                PartitionSessionId::from_raw(offset.partition_session_id),
-                offset.committed_offset,
-            )
-        });
+        let committed_offsets: Vec<_> = resp
+            .partitions_committed_offsets
+            .into_iter()
+            .map(|offset| {
+                (
+                    PartitionSessionId::from_raw(offset.partition_session_id),
+                    offset.committed_offset,
+                )
+            })
+            .collect();
 
-        let mut state = self.lock_state()?;
-        match &mut *state {
-            State::Active(active) => active.pending_commits.ack(committed_offsets),
-            State::Reconnecting => {}
-            State::Failed(err) => return Err(err.clone()),
+        let mut parent_committed = false;
+        {
+            let mut state = self.lock_state()?;
+            match &mut *state {
+                State::Active(active) => {
+                    for &(psid, offset) in &committed_offsets {
+                        parent_committed |= active.buffer.ack_commit(psid, offset);
+                    }
+                    active.pending_commits.ack(committed_offsets);
+                }
+                State::Reconnecting => {}
+                State::Failed(err) => return Err(err.clone()),
+            }
+        }
+
+        // Messages of child partitions were held back until now.
+        if parent_committed {
+            self.inner.messages_available.notify_one();
         }
         Ok(())
     }
@@ -173,24 +356,59 @@ impl RuntimeHandle {
             return Ok(());
         };
 
+        let committed_offset = req.committed_offset;
+        let partition_offsets = req.partition_offsets.clone();
         let session = PartitionSession::from(req);
         let partition_session_id = session.partition_session_id;
-        active.buffer.start(session)?;
-        active
-            .connection
-            .send(RawFromClientOneOf::StartPartitionSessionResponse(
-                RawStartPartitionSessionResponse {
-                    partition_session_id: partition_session_id.into_raw(),
-                },
-            ))?;
-        Ok(())
+
+        if self.inner.partition_events.is_none() {
+            let location = session.location;
+            active.buffer.start(session)?;
+            active
+                .connection
+                .send(RawFromClientOneOf::StartPartitionSessionResponse(
+                    RawStartPartitionSessionResponse {
+                        partition_session_id: partition_session_id.into_raw(),
+                        read_offset: None,
+                        commit_offset: None,
+                    },
+                ))?;
+            return active.start_direct_read(partition_session_id, location);
+        }
+
+        if active.is_known_session(partition_session_id) {
+            return Err(YdbError::custom(format!(
+                "topic reader duplicate start partition session {partition_session_id}"
+            )));
+        }
+        let event = PartitionSessionEvent::Started(PartitionSessionStarted {
+            topic: session.topic.clone(),
+            partition_id: session.partition_id,
+            committed_offset,
+            start_offset: partition_offsets.start,
+            end_offset: partition_offsets.end,
+            target: Some(EventTarget {
+                runtime: self.downgrade(),
+                epoch: active.connection.epoch(),
+                partition_session_id,
+            }),
+        });
+        active.pending_starts.insert(partition_session_id, session);
+        drop(state);
+
+        self.push_partition_events([event])
     }
 
     fn handle_stop_partition_session(&self, req: RawStopPartitionSessionRequest) -> YdbResult<()> {
+        if self.delay_stop_until_direct_read(&req)? {
+            return Ok(());
+        }
+
         let RawStopPartitionSessionRequest {
             partition_session_id,
             graceful,
             committed_offset,
+            last_direct_read_id: _,
         } = req;
         let partition_session_id = PartitionSessionId::from_raw(partition_session_id);
 
@@ -204,30 +422,345 @@ impl RuntimeHandle {
             return Ok(());
         };
 
-        if !active.buffer.stop(partition_session_id) {
-            warn!(
-                %partition_session_id,
-                "topic reader received stop for unknown partition session"
-            );
+        if self.inner.partition_events.is_none() {
+            return active.stop_session(partition_session_id, graceful, committed_offset);
         }
 
-        active.pending_commits.stop(
+        let session = match active.pending_starts.remove(&partition_session_id) {
+            Some(session) => Some(session),
+            None => active
+                .buffer
+                .session(partition_session_id)
+                .map(|session| PartitionSession {
+                    partition_session_id,
+                    partition_id: session.partition_id,
+                    topic: session.topic.clone(),
+                    next_commit_offset_start: session.next_commit_offset_start,
+                    location: session.location,
+                }),
+        };
+        let Some(session) = session else {
+            return active.stop_session(partition_session_id, graceful, committed_offset);
+        };
+
+        let epoch = active.connection.epoch();
+        let mut events = Vec::with_capacity(2);
+        if graceful && active.buffer.is_active_session(partition_session_id) {
+            events.push(PartitionSessionEvent::Stopping(PartitionSessionStopping {
+                topic: session.topic,
+                partition_id: session.partition_id,
+                graceful,
+                committed_offset,
+                target: Some(EventTarget {
+                    runtime: self.downgrade(),
+                    epoch,
+                    partition_session_id,
+                }),
+            }));
+        } else {
+            active.stop_session(partition_session_id, graceful, committed_offset)?;
+            events.push(PartitionSessionEvent::Stopping(PartitionSessionStopping {
+                topic: session.topic.clone(),
+                partition_id: session.partition_id,
+                graceful,
+                committed_offset,
+                target: None,
+            }));
+            events.push(ended_event(&session, false));
+        }
+        drop(state);
+
+        self.push_partition_events(events)
+    }
+
+    fn delay_stop_until_direct_read(
+        &self,
+        req: &RawStopPartitionSessionRequest,
+    ) -> YdbResult<bool> {
+        let partition_session_id = PartitionSessionId::from_raw(req.partition_session_id);
+        let mut state = self.lock_state()?;
+        let State::Active(active) = &mut *state else {
+            return Ok(false);
+        };
+
+        if !req.graceful || !active.buffer.is_active_session(partition_session_id) {
+            return Ok(false);
+        }
+        let progress = active.direct_reads.entry(partition_session_id).or_default();
+        let received_id = progress.last_direct_read_id;
+        if req.last_direct_read_id <= received_id {
+            return Ok(false);
+        }
+
+        debug!(
+            %partition_session_id,
+            received_id,
+            last_direct_read_id = req.last_direct_read_id,
+            "topic reader delays graceful stop until direct read data is received"
+        );
+        progress.delayed_stop = Some(req.clone());
+        Ok(true)
+    }
+
+    fn handle_update_partition_session(&self, req: RawUpdatePartitionSession) -> YdbResult<()> {
+        let partition_session_id = PartitionSessionId::from_raw(req.partition_session_id);
+        debug!(
+            %partition_session_id,
+            location = ?req.partition_location,
+            "topic reader received update partition session"
+        );
+
+        let mut state = self.lock_state()?;
+        let State::Active(active) = &mut *state else {
+            return Ok(());
+        };
+
+        if let Some(session) = active.pending_starts.get_mut(&partition_session_id) {
+            session.location = req.partition_location;
+            return Ok(());
+        }
+        if !active.buffer.is_active_session(partition_session_id) {
+            return Ok(());
+        }
+        active.start_direct_read(partition_session_id, req.partition_location)
+    }
+
+    fn handle_direct_read_response(&self, resp: RawDirectReadResponse) -> YdbResult<()> {
+        let RawDirectReadResponse {
             partition_session_id,
-            Some(committed_offset),
-            &YdbError::custom(format!(
-                "partition session stopped by server: {partition_session_id}"
-            )),
+            direct_read_id,
+            read_response,
+        } = resp;
+        let partition_session_id = PartitionSessionId::from_raw(partition_session_id);
+
+        let mut pushed = false;
+        let delayed_stop = {
+            let mut state = self.lock_state()?;
+            let State::Active(active) = &mut *state else {
+                return Ok(());
+            };
+
+            if !active.buffer.is_active_session(partition_session_id) {
+                debug!(
+                    %partition_session_id,
+                    direct_read_id,
+                    "topic reader dropped direct read response for stopped partition session"
+                );
+                // Data will never reach the user, give its read credit back.
+                if read_response.bytes_size > 0 {
+                    let _ =
+                        active
+                            .connection
+                            .send(RawFromClientOneOf::ReadRequest(RawReadRequest {
+                                bytes_size: read_response.bytes_size,
+                            }));
+                }
+                return Ok(());
+            }
+
+            let reader_id = self.inner.reader_id;
+            let epoch = active.connection.epoch();
+            let mut held_bytes = 0;
+            for partition_data in read_response.partition_data {
+                for batch in partition_data.batches {
+                    held_bytes += active.buffer.push_raw_batch(
+                        batch,
+                        partition_session_id,
+                        reader_id,
+                        epoch,
+                    )?;
+                    pushed = true;
+                }
+            }
+            self.release_held_bytes(active, held_bytes);
+
+            active
+                .connection
+                .send(RawFromClientOneOf::DirectReadAck(RawDirectReadAck {
+                    partition_session_id: partition_session_id.into_raw(),
+                    direct_read_id,
+                }))?;
+
+            let progress = active.direct_reads.entry(partition_session_id).or_default();
+            progress.last_direct_read_id = direct_read_id;
+            progress
+                .delayed_stop
+                .take_if(|stop| stop.last_direct_read_id <= direct_read_id)
+        };
+
+        if pushed {
+            self.inner.messages_available.notify_one();
+        }
+        match delayed_stop {
+            Some(stop) => self.handle_stop_partition_session(stop),
+            None => Ok(()),
+        }
+    }
+
+    fn handle_end_partition_session(&self, req: RawEndPartitionSession) -> YdbResult<()> {
+        let RawEndPartitionSession {
+            partition_session_id,
+            adjacent_partition_ids,
+            child_partition_ids,
+        } = req;
+        let partition_session_id = PartitionSessionId::from_raw(partition_session_id);
+
+        debug!(
+            %partition_session_id,
+            ?adjacent_partition_ids,
+            ?child_partition_ids,
+            "topic reader received end partition session"
         );
+
+        let event = {
+            let mut state = self.lock_state()?;
+            let State::Active(active) = &mut *state else {
+                return Ok(());
+            };
+
+            let children = child_partition_ids
+                .iter()
+                .copied()
+                .map(PartitionId::from_raw)
+                .collect();
+            if !active.buffer.end(partition_session_id, children) {
+                warn!(
+                    %partition_session_id,
+                    "topic reader received end for unknown partition session"
+                );
+                return Ok(());
+            }
+
+            active.buffer.session(partition_session_id).map(|session| {
+                PartitionSessionEvent::Finished(PartitionSessionFinished {
+                    topic: session.topic.clone(),
+                    partition_id: session.partition_id,
+                    adjacent_partition_ids,
+                    child_partition_ids,
+                })
+            })
+        };
+
+        self.push_partition_events(event)
+    }
+
+    pub(crate) fn confirm_partition_start(
+        &self,
+        epoch: usize,
+        partition_session_id: PartitionSessionId,
+        read_offset: Option<i64>,
+        commit_offset: Option<i64>,
+    ) -> YdbResult<()> {
+        let mut state = self.lock_state()?;
+        let State::Active(active) = &mut *state else {
+            return Ok(());
+        };
+        if active.connection.epoch() != epoch {
+            return Ok(());
+        }
+        let Some(mut session) = active.pending_starts.remove(&partition_session_id) else {
+            return Ok(());
+        };
+
+        if let Some(commit_offset) = commit_offset {
+            session.next_commit_offset_start = commit_offset;
+        }
+        let location = session.location;
+        active.buffer.start(session)?;
         active
             .connection
-            .send(RawFromClientOneOf::StopPartitionSessionResponse(
-                RawStopPartitionSessionResponse {
+            .send(RawFromClientOneOf::StartPartitionSessionResponse(
+                RawStartPartitionSessionResponse {
                     partition_session_id: partition_session_id.into_raw(),
+                    read_offset,
+                    commit_offset,
                 },
             ))?;
+        active.start_direct_read(partition_session_id, location)
+    }
+
+    pub(crate) fn confirm_partition_stop(
+        &self,
+        epoch: usize,
+        partition_session_id: PartitionSessionId,
+        committed_offset: i64,
+    ) -> YdbResult<()> {
+        let mut state = self.lock_state()?;
+        let State::Active(active) = &mut *state else {
+            return Ok(());
+        };
+        if active.connection.epoch() != epoch {
+            return Ok(());
+        }
+        let Some(event) = active
+            .buffer
+            .session(partition_session_id)
+            .map(|session| ended_event(session, false))
+        else {
+            return Ok(());
+        };
+
+        active.stop_session(partition_session_id, true, committed_offset)?;
+        drop(state);
+
+        self.inner.messages_available.notify_one();
+        self.push_partition_events([event])
+    }
+
+    pub(crate) async fn next_partition_event(&self) -> YdbResult<PartitionSessionEvent> {
+        let Some(queue) = &self.inner.partition_events else {
+            return Err(YdbError::custom(
+                "partition session events are disabled, enable them in TopicReaderOptions",
+            ));
+        };
+
+        loop {
+            let notified = queue.available.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            if let Some(event) = Self::lock_events(queue)?.pop_front() {
+                return Ok(event);
+            }
+            if let State::Failed(err) = &*self.lock_state()? {
+                return Err(err.clone());
+            }
+
+            notified.await;
+        }
+    }
+
+    fn push_partition_events(
+        &self,
+        events: impl IntoIterator<Item = PartitionSessionEvent>,
+    ) -> YdbResult<()> {
+        let Some(queue) = &self.inner.partition_events else {
+            return Ok(());
+        };
+
+        let mut pushed = 0;
+        {
+            let mut queue_events = Self::lock_events(queue)?;
+            for event in events {
+                queue_events.push_back(event);
+                pushed += 1;
+            }
+        }
+        for _ in 0..pushed {
+            queue.available.notify_one();
+        }
         Ok(())
     }
 
+    fn lock_events(
+        queue: &PartitionEventQueue,
+    ) -> YdbResult<std::sync::MutexGuard<'_, VecDeque<PartitionSessionEvent>>> {
+        queue
+            .events
+            .lock()
+            .map_err(|_| YdbError::custom(RUNTIME_HANDLE_POISONED))
+    }
+
     #[cfg(test)]
     pub(crate) fn push_batch(&self, messages: Vec<TopicReaderMessage>) -> YdbResult<()> {
         let pushed = {
@@ -250,6 +783,16 @@ impl RuntimeHandle {
     }
 
     pub(crate) async fn pop_batch(&self, cap: usize) -> YdbResult<TopicReaderBatch> {
+        self.pop_batch_limited(cap, usize::MAX).await
+    }
+
+    /// Like [`Self::pop_batch`], but also limits the batch payload size. A single message larger
+    /// than `max_bytes` is still returned alone.
+    pub(crate) async fn pop_batch_limited(
+        &self,
+        cap: usize,
+        max_bytes: usize,
+    ) -> YdbResult<TopicReaderBatch> {
         if cap == 0 {
             return Err(YdbError::Custom(
                 "topic reader pop_batch called with cap=0".into(),
@@ -267,7 +810,7 @@ impl RuntimeHandle {
                 let mut guard = self.lock_state()?;
                 match &mut *guard {
                     State::Reconnecting => None,
-                    State::Active(active) => active.pop_batch(cap)?,
+                    State::Active(active) => active.pop_batch(cap, max_bytes)?,
                     State::Failed(err) => return Err(err.clone()),
                 }
             };
@@ -310,6 +853,10 @@ impl RuntimeHandle {
                 let receiver = active
                     .pending_commits
                     .push(commit_marker.partition_session_id, commit_marker.end_offset);
+                // Nothing to send for an empty range, it is acked with the commit covering it.
+                if commit_marker.start_offset >= commit_marker.end_offset {
+                    return Ok(receiver);
+                }
                 let commit_message =
                     RawFromClientOneOf::CommitOffsetRequest(RawCommitOffsetRequest {
                         commit_offsets: vec![PartitionCommitOffset {
@@ -343,7 +890,10 @@ impl RuntimeHandle {
         let state = self.lock_state()?;
         match &*state {
             State::Active(active) => {
-                if bytes_to_release > 0 && active.connection.epoch() == epoch {
+                if bytes_to_release > 0
+                    && active.connection.epoch() == epoch
+                    && !self.inner.closing.load(Ordering::Acquire)
+                {
                     // Read credit belongs to the current grpc attempt. If its channel
                     // is already closed, the attempt is dying and GrpcStreamer will
                     // drive reconnect; buffered messages must still be returned.
@@ -394,11 +944,13 @@ impl RuntimeHandle {
 
     fn enter_reconnecting_inner(&self, err: YdbError) -> YdbResult<bool> {
         let mut pending_commits = PendingCommits::default();
+        let mut lost_sessions = Vec::new();
         let changed = {
             let mut state = self.lock_state()?;
             match &mut *state {
                 State::Active(active) => {
                     std::mem::swap(&mut pending_commits, &mut active.pending_commits);
+                    lost_sessions = active.ended_sessions_events(true);
                     *state = State::Reconnecting;
                     true
                 }
@@ -408,8 +960,9 @@ impl RuntimeHandle {
         };
 
         if changed {
-            pending_commits.fail_all(&err);
+            self.fail_lost_commits(pending_commits, &err)?;
         }
+        self.push_lost_sessions(lost_sessions)?;
         self.inner.messages_available.notify_waiters();
 
         Ok(changed)
@@ -421,36 +974,111 @@ impl RuntimeHandle {
         err: YdbError,
     ) -> YdbResult<()> {
         let mut pending_commits = PendingCommits::default();
+        let mut lost_sessions = Vec::new();
         {
             let mut state = self.lock_state()?;
             match &mut *state {
                 State::Active(active) => {
                     std::mem::swap(&mut pending_commits, &mut active.pending_commits);
+                    lost_sessions = active.ended_sessions_events(true);
                 }
                 State::Reconnecting => {}
                 State::Failed(err) => return Err(err.clone()),
             }
-            *state = State::Active(Active::new(connection));
+            *state = State::Active(Box::new(Active::new(connection, self.inner.chunk_limits)));
         }
-        pending_commits.fail_all(&err);
+        self.fail_lost_commits(pending_commits, &err)?;
+        self.push_lost_sessions(lost_sessions)?;
         self.inner.messages_available.notify_waiters();
         Ok(())
     }
 
+    // Commits of a lost connection are reported by the next flush_commits as well, even if
+    // they were pending before it was called.
+    fn fail_lost_commits(
+        &self,
+        mut pending_commits: PendingCommits,
+        err: &YdbError,
+    ) -> YdbResult<()> {
+        if !pending_commits.is_empty() {
+            self.inner
+                .lost_commits
+                .lock()
+                .map_err(|_| YdbError::custom(RUNTIME_HANDLE_POISONED))?
+                .get_or_insert_with(|| err.clone());
+        }
+        pending_commits.fail_all(err);
+        Ok(())
+    }
+
+    /// Stops returning read credit to the server, so it stops sending new data.
+    pub(crate) fn start_closing(&self) {
+        self.inner.closing.store(true, Ordering::Release);
+    }
+
+    /// Returns a receiver resolved when all commits sent so far are acknowledged by the server.
+    ///
+    /// The receiver gets an error if any of these commits fails meanwhile, e.g. on reconnect.
+    /// Fails at once if commits were lost by a reconnect since the previous call.
+    pub(crate) fn flush_commits(&self) -> YdbResult<CommitAckReceiver> {
+        let lost_commits = self
+            .inner
+            .lost_commits
+            .lock()
+            .map_err(|_| YdbError::custom(RUNTIME_HANDLE_POISONED))?
+            .take();
+        if let Some(err) = lost_commits {
+            return Err(err);
+        }
+
+        let mut state = self.lock_state()?;
+        match &mut *state {
+            State::Active(active) => Ok(active.pending_commits.flush()),
+            State::Reconnecting => Ok(PendingCommits::default().flush()),
+            State::Failed(err) => Err(err.clone()),
+        }
+    }
+
+    /// Permanently stops the runtime on user request. Partition sessions end with
+    /// `connection_lost = false`, pending commits fail with `err`.
+    pub(crate) fn close(&self, err: &YdbError) -> YdbResult<()> {
+        self.finish(err, false)
+    }
+
     pub(crate) fn fail(&self, err: &YdbError) -> YdbResult<()> {
+        self.finish(err, true)
+    }
+
+    fn finish(&self, err: &YdbError, connection_lost: bool) -> YdbResult<()> {
         let mut pending_commits = PendingCommits::default();
+        let mut lost_sessions = Vec::new();
         {
             let mut state = self.lock_state()?;
+            if let State::Failed(_) = &*state {
+                return Ok(());
+            }
             if let State::Active(active) = &mut *state {
                 std::mem::swap(&mut pending_commits, &mut active.pending_commits);
+                lost_sessions = active.ended_sessions_events(connection_lost);
             }
             *state = State::Failed(err.clone());
         }
         pending_commits.fail_all(err);
+        self.push_lost_sessions(lost_sessions)?;
         self.inner.messages_available.notify_waiters();
+        if let Some(queue) = &self.inner.partition_events {
+            queue.available.notify_waiters();
+        }
         Ok(())
     }
 
+    fn push_lost_sessions(&self, events: Vec<PartitionSessionEvent>) -> YdbResult<()> {
+        if events.is_empty() {
+            return Ok(());
+        }
+        self.push_partition_events(events)
+    }
+
     pub(crate) fn reconnection_notifier<'a>(&'a self) -> Notified<'a> {
         self.inner.reconnect_notify.notified()
     }
@@ -463,6 +1091,14 @@ impl RuntimeHandle {
     }
 }
 
+fn ended_event(session: &PartitionSession, connection_lost: bool) -> PartitionSessionEvent {
+    PartitionSessionEvent::Ended(PartitionSessionEnded {
+        topic: session.topic.clone(),
+        partition_id: session.partition_id,
+        connection_lost,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use tokio::sync::mpsc;
@@ -470,7 +1106,7 @@ mod tests {
     use super::*;
     use crate::client_topic::topicreader::messages::TopicReaderMessage;
     use crate::grpc_wrapper::raw_topic_service::stream_read::messages::{
-        RawInitResponse, RawReadRequest,
+        RawInitResponse, RawPartitionCommittedOffset, RawPartitionSession, RawReadRequest,
     };
     use ydb_grpc::ydb_proto::topic::stream_read_message;
 
@@ -518,6 +1154,27 @@ mod tests {
         assert!(runtime.commit(commit_marker(1)).is_ok());
     }
 
+    #[test]
+    fn flush_commits_reports_commits_lost_by_reconnect() {
+        let (runtime, _outgoing_rx) = runtime_with_epoch(0);
+        runtime
+            .commit(commit_marker(0))
+            .expect("commit should be registered");
+
+        runtime
+            .force_reconnection(YdbError::custom("test reconnect"))
+            .expect("force_reconnection should succeed");
+
+        assert!(runtime.flush_commits().is_err());
+        assert!(matches!(
+            runtime
+                .flush_commits()
+                .expect("flush should succeed")
+                .try_recv(),
+            Ok(Ok(()))
+        ));
+    }
+
     #[test]
     fn install_connection_routes_commits_to_new_channel() {
         let (runtime, _old_rx) = runtime_with_epoch(0);
@@ -559,7 +1216,7 @@ mod tests {
 
     #[test]
     fn reconnecting_runtime_installs_first_connection() {
-        let runtime = RuntimeHandle::new(0);
+        let runtime = RuntimeHandle::new(0, false, None);
         assert!(runtime.commit(commit_marker(0)).is_err());
 
         let (outgoing_tx, _outgoing_rx) = mpsc::unbounded_channel();
@@ -658,6 +1315,370 @@ mod tests {
         assert_eq!(batch.messages.len(), 1);
     }
 
+    #[tokio::test]
+    async fn partition_start_waits_for_event_confirmation() {
+        let (outgoing_tx, mut outgoing_rx) = mpsc::unbounded_channel();
+        let runtime = RuntimeHandle::with_partition_events(Connection::new(outgoing_tx, 1));
+
+        runtime
+            .handle_from_server(RawFromServer::StartPartitionSessionRequest(start_request(
+                10, 5,
+            )))
+            .expect("start should be handled");
+        assert!(outgoing_rx.try_recv().is_err());
+
+        let PartitionSessionEvent::Started(started) = runtime
+            .next_partition_event()
+            .await
+            .expect("event should be received")
+        else {
+            panic!("expected started event");
+        };
+        assert_eq!(started.partition_id(), 20);
+        assert_eq!(started.committed_offset(), 5);
+        assert_eq!(started.end_offset(), 100);
+        started
+            .confirm_with_offsets(Some(7), Some(7))
+            .expect("confirm should succeed");
+
+        let Ok(RawFromClientOneOf::StartPartitionSessionResponse(response)) =
+            outgoing_rx.try_recv()
+        else {
+            panic!("expected start partition session response");
+        };
+        assert_eq!(response.partition_session_id, 10);
+        assert_eq!(response.read_offset, Some(7));
+        assert_eq!(response.commit_offset, Some(7));
+    }
+
+    #[tokio::test]
+    async fn graceful_stop_waits_for_event_confirmation() {
+        let (outgoing_tx, mut outgoing_rx) = mpsc::unbounded_channel();
+        let runtime = RuntimeHandle::with_partition_events(Connection::new(outgoing_tx, 1));
+        runtime
+            .handle_from_server(RawFromServer::StartPartitionSessionRequest(start_request(
+                10, 0,
+            )))
+            .expect("start should be handled");
+        drop(runtime.next_partition_event().await);
+        assert!(matches!(
+            outgoing_rx.try_recv(),
+            Ok(RawFromClientOneOf::StartPartitionSessionResponse(_))
+        ));
+
+        runtime
+            .handle_from_server(RawFromServer::StopPartitionSessionRequest(
+                RawStopPartitionSessionRequest {
+                    partition_session_id: 10,
+                    graceful: true,
+                    committed_offset: 3,
+                    last_direct_read_id: 0,
+                },
+            ))
+            .expect("stop should be handled");
+        assert!(outgoing_rx.try_recv().is_err());
+
+        let PartitionSessionEvent::Stopping(stopping) = runtime
+            .next_partition_event()
+            .await
+            .expect("event should be received")
+        else {
+            panic!("expected stopping event");
+        };
+        assert!(stopping.is_graceful());
+        stopping.confirm().expect("confirm should succeed");
+
+        assert!(matches!(
+            outgoing_rx.try_recv(),
+            Ok(RawFromClientOneOf::StopPartitionSessionResponse(
+                RawStopPartitionSessionResponse {
+                    partition_session_id: 10,
+                    graceful: true,
+                }
+            ))
+        ));
+        assert!(matches!(
+            runtime.next_partition_event().await,
+            Ok(PartitionSessionEvent::Ended(ended)) if !ended.is_connection_lost()
+        ));
+    }
+
+    #[tokio::test]
+    async fn reconnect_ends_partition_sessions() {
+        let (outgoing_tx, _outgoing_rx) = mpsc::unbounded_channel();
+        let runtime = RuntimeHandle::with_partition_events(Connection::new(outgoing_tx, 1));
+        runtime
+            .handle_from_server(RawFromServer::StartPartitionSessionRequest(start_request(
+                10, 0,
+            )))
+            .expect("start should be handled");
+        drop(runtime.next_partition_event().await);
+
+        runtime
+            .enter_reconnecting(YdbError::Transport("test reconnect".to_string()))
+            .expect("runtime should enter reconnecting state");
+
+        assert!(matches!(
+            runtime.next_partition_event().await,
+            Ok(PartitionSessionEvent::Ended(ended)) if ended.is_connection_lost()
+        ));
+    }
+
+    #[tokio::test]
+    async fn end_partition_session_produces_finished_event() {
+        let (outgoing_tx, _outgoing_rx) = mpsc::unbounded_channel();
+        let runtime = RuntimeHandle::with_partition_events(Connection::new(outgoing_tx, 1));
+        runtime
+            .handle_from_server(RawFromServer::StartPartitionSessionRequest(start_request(
+                10, 0,
+            )))
+            .expect("start should be handled");
+        drop(runtime.next_partition_event().await);
+
+        runtime
+            .handle_from_server(RawFromServer::EndPartitionSession(RawEndPartitionSession {
+                partition_session_id: 10,
+                adjacent_partition_ids: vec![],
+                child_partition_ids: vec![21, 22],
+            }))
+            .expect("end should be handled");
+
+        let PartitionSessionEvent::Finished(finished) = runtime
+            .next_partition_event()
+            .await
+            .expect("event should be received")
+        else {
+            panic!("expected finished event");
+        };
+        assert_eq!(finished.partition_id(), 20);
+        assert_eq!(finished.child_partition_ids(), [21, 22]);
+        assert!(finished.is_split());
+        assert!(!finished.is_merge());
+    }
+
+    #[tokio::test]
+    async fn direct_read_starts_on_partition_node() {
+        let (outgoing_tx, mut outgoing_rx) = mpsc::unbounded_channel();
+        let (direct_read_tx, mut direct_read_rx) = mpsc::unbounded_channel();
+        let runtime = RuntimeHandle::with_connection(
+            Connection::new(outgoing_tx, 1).with_direct_read(direct_read_tx),
+        );
+        let location = RawPartitionLocation {
+            node_id: 3,
+            generation: 4,
+        };
+
+        runtime
+            .handle_from_server(RawFromServer::StartPartitionSessionRequest(
+                RawStartPartitionSessionRequest {
+                    partition_location: Some(location),
+                    ..start_request(10, 0)
+                },
+            ))
+            .expect("start should be handled");
+
+        assert!(matches!(
+            outgoing_rx.try_recv(),
+            Ok(RawFromClientOneOf::StartPartitionSessionResponse(_))
+        ));
+        let Ok(DirectReadCommand::Start {
+            partition_session_id,
+            location: started_location,
+        }) = direct_read_rx.try_recv()
+        else {
+            panic!("expected direct read start command");
+        };
+        assert_eq!(partition_session_id, PartitionSessionId::from_raw(10));
+        assert_eq!(started_location, location);
+
+        runtime
+            .handle_from_server(RawFromServer::DirectReadResponse(direct_read_response(
+                10, 1,
+            )))
+            .expect("direct read response should be handled");
+        assert!(matches!(
+            outgoing_rx.try_recv(),
+            Ok(RawFromClientOneOf::DirectReadAck(RawDirectReadAck {
+                partition_session_id: 10,
+                direct_read_id: 1,
+            }))
+        ));
+
+        let batch = runtime.pop_batch(10).await.expect("pop should succeed");
+        assert_eq!(batch.messages.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn graceful_stop_waits_for_last_direct_read() {
+        let (outgoing_tx, mut outgoing_rx) = mpsc::unbounded_channel();
+        let (direct_read_tx, mut direct_read_rx) = mpsc::unbounded_channel();
+        let runtime = RuntimeHandle::with_connection(
+            Connection::new(outgoing_tx, 1).with_direct_read(direct_read_tx),
+        );
+        runtime
+            .handle_from_server(RawFromServer::StartPartitionSessionRequest(
+                RawStartPartitionSessionRequest {
+                    partition_location: Some(RawPartitionLocation {
+                        node_id: 3,
+                        generation: 4,
+                    }),
+                    ..start_request(10, 0)
+                },
+            ))
+            .expect("start should be handled");
+        let _ = outgoing_rx.try_recv();
+        let _ = direct_read_rx.try_recv();
+
+        runtime
+            .handle_from_server(RawFromServer::StopPartitionSessionRequest(
+                RawStopPartitionSessionRequest {
+                    partition_session_id: 10,
+                    graceful: true,
+                    committed_offset: 0,
+                    last_direct_read_id: 2,
+                },
+            ))
+            .expect("stop should be handled");
+        assert!(outgoing_rx.try_recv().is_err());
+
+        for direct_read_id in 1..=2 {
+            runtime
+                .handle_from_server(RawFromServer::DirectReadResponse(direct_read_response(
+                    10,
+                    direct_read_id,
+                )))
+                .expect("direct read response should be handled");
+            assert!(matches!(
+                outgoing_rx.try_recv(),
+                Ok(RawFromClientOneOf::DirectReadAck(_))
+            ));
+        }
+
+        assert!(matches!(
+            outgoing_rx.try_recv(),
+            Ok(RawFromClientOneOf::StopPartitionSessionResponse(
+                RawStopPartitionSessionResponse {
+                    partition_session_id: 10,
+                    graceful: true,
+                }
+            ))
+        ));
+        assert!(matches!(
+            direct_read_rx.try_recv(),
+            Ok(DirectReadCommand::Stop { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn closing_runtime_stops_requesting_data() {
+        let (outgoing_tx, mut outgoing_rx) = mpsc::unbounded_channel();
+        let runtime = RuntimeHandle::with_connection(Connection::new(outgoing_tx, 1));
+        runtime
+            .push_batch(vec![TopicReaderMessage::test_message(1, 15)])
+            .expect("push should succeed");
+
+        runtime.start_closing();
+        let batch = runtime.pop_batch(10).await.expect("pop should succeed");
+
+        assert_eq!(batch.messages.len(), 1);
+        assert!(outgoing_rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn flush_commits_waits_for_commit_ack() {
+        let (runtime, _outgoing_rx) = runtime_with_epoch(1);
+        runtime
+            .commit(commit_marker(1))
+            .expect("commit should be registered");
+
+        let mut flushed = runtime.flush_commits().expect("flush should succeed");
+        assert!(flushed.try_recv().is_err());
+
+        runtime
+            .handle_from_server(RawFromServer::CommitOffsetResponse(
+                RawCommitOffsetResponse {
+                    partitions_committed_offsets: vec![RawPartitionCommittedOffset {
+                        partition_session_id: 10,
+                        committed_offset: 40,
+                    }],
+                },
+            ))
+            .expect("commit response should be handled");
+
+        assert!(matches!(flushed.await, Ok(Ok(()))));
+    }
+
+    #[tokio::test]
+    async fn close_ends_partition_sessions_gracefully() {
+        let (outgoing_tx, _outgoing_rx) = mpsc::unbounded_channel();
+        let runtime = RuntimeHandle::with_partition_events(Connection::new(outgoing_tx, 1));
+        runtime
+            .handle_from_server(RawFromServer::StartPartitionSessionRequest(start_request(
+                10, 0,
+            )))
+            .expect("start should be handled");
+        drop(runtime.next_partition_event().await);
+
+        runtime
+            .close(&YdbError::custom("closed"))
+            .expect("close should succeed");
+
+        assert!(matches!(
+            runtime.next_partition_event().await,
+            Ok(PartitionSessionEvent::Ended(ended)) if !ended.is_connection_lost()
+        ));
+        assert!(runtime.next_partition_event().await.is_err());
+        assert!(runtime.pop_batch(10).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn partition_events_are_disabled_by_default() {
+        let (runtime, _outgoing_rx) = runtime_with_epoch(0);
+        assert!(runtime.next_partition_event().await.is_err());
+    }
+
+    fn start_request(
+        partition_session_id: i64,
+        committed_offset: i64,
+    ) -> RawStartPartitionSessionRequest {
+        RawStartPartitionSessionRequest {
+            partition_session: RawPartitionSession {
+                partition_session_id,
+                path: "test-topic".to_string(),
+                partition_id: 20,
+            },
+            committed_offset,
+            partition_offsets: RawOffsetsRange { start: 0, end: 100 },
+            partition_location: None,
+        }
+    }
+
+    fn direct_read_response(
+        partition_session_id: i64,
+        direct_read_id: i64,
+    ) -> RawDirectReadResponse {
+        use ydb_grpc::ydb_proto::topic::stream_direct_read_message;
+        use ydb_grpc::ydb_proto::topic::stream_read_message::read_response;
+
+        stream_direct_read_message::DirectReadResponse {
+            partition_session_id,
+            direct_read_id,
+            partition_data: Some(read_response::PartitionData {
+                partition_session_id,
+                batches: vec![read_response::Batch {
+                    message_data: vec![read_response::MessageData {
+                        offset: direct_read_id,
+                        data: b"data".to_vec(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+            }),
+            bytes_size: 4,
+        }
+        .into()
+    }
+
     fn runtime_with_epoch(
         epoch: usize,
     ) -> (RuntimeHandle, mpsc::UnboundedReceiver<RawFromClientOneOf>) {
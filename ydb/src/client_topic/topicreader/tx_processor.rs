@@ -0,0 +1,168 @@
+use std::time::Duration;
+
+use futures_util::future::BoxFuture;
+use tokio_util::sync::CancellationToken;
+use tracing::instrument;
+
+use crate::async_closure::AsyncFnMut;
+use crate::async_closure::with_lifetime::{MutWithLifetime, OwnedWithLifetime};
+use crate::errors::YdbResultWithCustomerErr;
+use crate::{QueryClient, RetryTxAttempt, TopicReader, TopicReaderBatch, Transaction, TxMode};
+
+/// Arguments of a [`TopicTxProcessor`] handler: the read batch and the transaction
+/// to make table changes in.
+///
+/// ```no_run
+/// # use ydb::{closure, TopicReaderBatch, TopicTxProcessor, Transaction, YdbResultWithCustomerErr};
+/// # async fn run(mut processor: TopicTxProcessor) -> YdbResultWithCustomerErr<()> {
+/// processor
+///     .process_batch(&mut closure!(
+///         async |(batch, tx): (TopicReaderBatch, &mut Transaction)| {
+///             for message in batch.messages {
+///                 tx.exec("UPSERT INTO events (offset) VALUES ($offset)")
+///                     .param("$offset", message.offset)
+///                     .await?;
+///             }
+///             Ok(())
+///         }
+///     ))
+///     .await
+/// # }
+/// ```
+pub type TopicTxHandlerArgs = (
+    OwnedWithLifetime<TopicReaderBatch>,
+    MutWithLifetime<Transaction>,
+);
+
+/// Processes topic batches in interactive transactions, exactly once.
+///
+/// Every batch is handled inside a [`QueryClient::retry_tx`] transaction, and the batch offsets
+/// are committed together with that transaction. If the transaction is aborted, the reader
+/// reconnects and the same batch is read again for the next attempt, so table changes and
+/// consumer offsets never diverge.
+pub struct TopicTxProcessor {
+    reader: TopicReader,
+    query_client: QueryClient,
+    tx_mode: TxMode,
+    timeout: Option<Duration>,
+}
+
+impl TopicTxProcessor {
+    pub fn new(reader: TopicReader, query_client: QueryClient) -> Self {
+        Self {
+            reader,
+            query_client,
+            tx_mode: TxMode::SerializableReadWrite,
+            timeout: None,
+        }
+    }
+
+    /// Transaction isolation mode (default: [`TxMode::SerializableReadWrite`]).
+    pub fn with_mode(mut self, mode: TxMode) -> Self {
+        self.tx_mode = mode;
+        self
+    }
+
+    /// Wall-clock limit for processing one batch, including all transaction retries.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Waits for the next batch and handles it in a retried transaction.
+    ///
+    /// The batch is read before the first transaction attempt, so waiting for data does not
+    /// keep a transaction open.
+    ///
+    /// # Errors
+    ///
+    /// Returns a non-retryable transaction error or the handler error. The batch is not
+    /// committed then and will be read again.
+    #[instrument(name = "ydb.TopicTxProcessor.ProcessBatch", skip_all, fields(db.system.name = "ydb"), err)]
+    pub async fn process_batch<H>(&mut self, handler: &mut H) -> YdbResultWithCustomerErr<()>
+    where
+        H: AsyncFnMut<TopicTxHandlerArgs, Output = YdbResultWithCustomerErr<()>>,
+    {
+        let batch = self.reader.read_batch().await?;
+        self.process(batch, handler).await
+    }
+
+    /// Handles batches one by one until `cancellation` is cancelled or an error occurs.
+    ///
+    /// Cancellation is checked while waiting for the next batch, a batch in progress is
+    /// completed.
+    #[instrument(name = "ydb.TopicTxProcessor.Run", skip_all, fields(db.system.name = "ydb"), err)]
+    pub async fn run<H>(
+        &mut self,
+        mut handler: H,
+        cancellation: CancellationToken,
+    ) -> YdbResultWithCustomerErr<()>
+    where
+        H: AsyncFnMut<TopicTxHandlerArgs, Output = YdbResultWithCustomerErr<()>>,
+    {
+        loop {
+            let batch = tokio::select! {
+                biased;
+                _ = cancellation.cancelled() => return Ok(()),
+                batch = self.reader.read_batch() => batch?,
+            };
+            self.process(batch, &mut handler).await?;
+        }
+    }
+
+    pub fn into_reader(self) -> TopicReader {
+        self.reader
+    }
+
+    async fn process<H>(
+        &mut self,
+        batch: TopicReaderBatch,
+        handler: &mut H,
+    ) -> YdbResultWithCustomerErr<()>
+    where
+        H: AsyncFnMut<TopicTxHandlerArgs, Output = YdbResultWithCustomerErr<()>>,
+    {
+        let mut retry = self.query_client.retry_tx(ProcessAttempt {
+            reader: &mut self.reader,
+            prefetched: Some(batch),
+            handler,
+        });
+        retry = retry.with_mode(self.tx_mode);
+        if let Some(timeout) = self.timeout {
+            retry = retry.timeout(timeout);
+        }
+        retry.await
+    }
+}
+
+struct ProcessAttempt<'r, H> {
+    reader: &'r mut TopicReader,
+    // Read before the first attempt. Aborted attempts make the reader reconnect,
+    // so later attempts read the same messages again inside the transaction.
+    prefetched: Option<TopicReaderBatch>,
+    handler: &'r mut H,
+}
+
+impl<H> RetryTxAttempt<()> for ProcessAttempt<'_, H>
+where
+    H: AsyncFnMut<TopicTxHandlerArgs, Output = YdbResultWithCustomerErr<()>>,
+{
+    fn attempt<'a>(
+        &'a mut self,
+        tx: &'a mut Transaction,
+    ) -> BoxFuture<'a, YdbResultWithCustomerErr<()>> {
+        Box::pin(async move {
+            let mut reader_tx = self.reader.tx_reader(tx).await?;
+            let batch = match self.prefetched.take() {
+                Some(batch) => {
+                    reader_tx.add_batch(&batch).await?;
+                    batch
+                }
+                None => reader_tx.read_batch().await?,
+            };
+            drop(reader_tx);
+
+            self.handler.call((batch, tx)).await
+        })
+    }
+}
@@ -0,0 +1,246 @@
+use std::collections::BTreeSet;
+use std::marker::PhantomData;
+
+use crate::client_topic::payload_format::{
+    CONTENT_TYPE_METADATA_KEY, PayloadDecoder, SCHEMA_VERSION_METADATA_KEY,
+};
+use crate::client_topic::topicreader::messages::{TopicReaderBatch, TopicReaderMessage};
+use crate::client_topic::topicreader::reader::{TopicReader, TopicReaderCommitMarker};
+use crate::{YdbError, YdbResult};
+
+/// Message read by [`TypedTopicReader`]. The payload is moved into `value`.
+#[derive(Debug)]
+pub struct TypedTopicMessage<T> {
+    pub value: T,
+    pub schema_version: u32,
+    message: TopicReaderMessage,
+}
+
+impl<T> TypedTopicMessage<T> {
+    /// Offsets, metadata and other message attributes.
+    pub fn message(&self) -> &TopicReaderMessage {
+        &self.message
+    }
+
+    pub fn get_commit_marker(&self) -> TopicReaderCommitMarker {
+        self.message.get_commit_marker()
+    }
+}
+
+/// Message [`TypedTopicReader`] could not decode. The payload is left in the message.
+#[derive(Debug)]
+pub struct RejectedTopicMessage {
+    pub error: YdbError,
+    message: Box<TopicReaderMessage>,
+}
+
+impl RejectedTopicMessage {
+    pub fn message(&self) -> &TopicReaderMessage {
+        &self.message
+    }
+
+    pub fn into_message(self) -> TopicReaderMessage {
+        *self.message
+    }
+
+    pub fn get_commit_marker(&self) -> TopicReaderCommitMarker {
+        self.message.get_commit_marker()
+    }
+}
+
+#[derive(Debug)]
+pub struct TypedTopicBatch<T> {
+    /// Messages in the batch order, the rejected ones in place.
+    pub messages: Vec<Result<TypedTopicMessage<T>, RejectedTopicMessage>>,
+    commit_marker: TopicReaderCommitMarker,
+}
+
+impl<T> TypedTopicBatch<T> {
+    pub fn get_commit_marker(&self) -> TopicReaderCommitMarker {
+        self.commit_marker.clone()
+    }
+}
+
+/// Reads values of `T` written by [`crate::TypedTopicWriter`] with the payload format `F`.
+///
+/// Messages with another content type, a schema version not in the supported set or a payload
+/// that fails to decode are returned as [`RejectedTopicMessage`] with a [`YdbError::Convert`];
+/// the rest of the batch is decoded as usual and the batch commit marker covers both.
+pub struct TypedTopicReader<T, F> {
+    reader: TopicReader,
+    format: F,
+    schema_versions: BTreeSet<u32>,
+    _value: PhantomData<fn() -> T>,
+}
+
+impl<T, F: PayloadDecoder<T>> TypedTopicReader<T, F> {
+    pub fn new(
+        reader: TopicReader,
+        format: F,
+        schema_versions: impl IntoIterator<Item = u32>,
+    ) -> Self {
+        Self {
+            reader,
+            format,
+            schema_versions: schema_versions.into_iter().collect(),
+            _value: PhantomData,
+        }
+    }
+
+    pub async fn read_batch(&mut self) -> YdbResult<TypedTopicBatch<T>> {
+        let batch = self.reader.read_batch().await?;
+        self.decode_batch(batch)
+    }
+
+    pub fn commit(&mut self, commit_marker: TopicReaderCommitMarker) -> YdbResult<()> {
+        self.reader.commit(commit_marker)
+    }
+
+    pub fn into_inner(self) -> TopicReader {
+        self.reader
+    }
+
+    fn decode_batch(&self, batch: TopicReaderBatch) -> YdbResult<TypedTopicBatch<T>> {
+        let commit_marker = batch.get_commit_marker();
+        let messages = batch
+            .messages
+            .into_iter()
+            .map(|message| decode_message(&self.format, &self.schema_versions, message))
+            .collect();
+        Ok(TypedTopicBatch {
+            messages,
+            commit_marker,
+        })
+    }
+}
+
+fn decode_message<T, F: PayloadDecoder<T>>(
+    format: &F,
+    schema_versions: &BTreeSet<u32>,
+    mut message: TopicReaderMessage,
+) -> Result<TypedTopicMessage<T>, RejectedTopicMessage> {
+    let decoded = decode_value(format, schema_versions, &message);
+    let (value, schema_version) = match decoded {
+        Ok(decoded) => decoded,
+        Err(reason) => {
+            let error = YdbError::Convert(format!(
+                "typed topic reader: message at offset {} of partition {}: {reason}",
+                message.offset,
+                message.get_partition_id()
+            ));
+            return Err(RejectedTopicMessage {
+                error,
+                message: Box::new(message),
+            });
+        }
+    };
+    message.raw_data = None;
+    Ok(TypedTopicMessage {
+        value,
+        schema_version,
+        message,
+    })
+}
+
+fn decode_value<T, F: PayloadDecoder<T>>(
+    format: &F,
+    schema_versions: &BTreeSet<u32>,
+    message: &TopicReaderMessage,
+) -> Result<(T, u32), String> {
+    let content_type = message.metadata_value(CONTENT_TYPE_METADATA_KEY);
+    if content_type != Some(format.content_type().as_bytes()) {
+        return Err(format!(
+            "content type {:?}, expected {}",
+            content_type.map(String::from_utf8_lossy),
+            format.content_type()
+        ));
+    }
+
+    let schema_version = message
+        .metadata_value(SCHEMA_VERSION_METADATA_KEY)
+        .and_then(|version| std::str::from_utf8(version).ok()?.parse::<u32>().ok())
+        .ok_or_else(|| "no schema version".to_string())?;
+    if !schema_versions.contains(&schema_version) {
+        return Err(format!("unsupported schema version {schema_version}"));
+    }
+
+    let data = message.raw_data.as_deref().unwrap_or_default();
+    let value = format.decode(data).map_err(|err| err.to_string())?;
+    Ok((value, schema_version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client_topic::payload_format::JsonPayload;
+
+    fn message(metadata: &[(&str, &str)], data: &str) -> TopicReaderMessage {
+        let mut message = TopicReaderMessage::test_message(0, 0);
+        message.metadata = metadata
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.as_bytes().to_vec()))
+            .collect();
+        message.raw_data = Some(data.as_bytes().to_vec());
+        message
+    }
+
+    fn decode(message: TopicReaderMessage) -> Result<TypedTopicMessage<u32>, RejectedTopicMessage> {
+        decode_message(&JsonPayload, &BTreeSet::from([1, 2]), message)
+    }
+
+    #[test]
+    fn supported_version_is_decoded() {
+        let decoded = decode(message(
+            &[
+                (CONTENT_TYPE_METADATA_KEY, "application/json"),
+                (SCHEMA_VERSION_METADATA_KEY, "2"),
+            ],
+            "42",
+        ))
+        .unwrap();
+
+        assert_eq!((decoded.value, decoded.schema_version), (42, 2));
+    }
+
+    #[test]
+    fn unknown_version_is_rejected() {
+        let result = decode(message(
+            &[
+                (CONTENT_TYPE_METADATA_KEY, "application/json"),
+                (SCHEMA_VERSION_METADATA_KEY, "3"),
+            ],
+            "42",
+        ));
+
+        let rejected = result.unwrap_err();
+        assert!(matches!(&rejected.error, YdbError::Convert(err) if err.contains("version 3")));
+        assert_eq!(
+            rejected.message().raw_data.as_deref(),
+            Some(b"42".as_slice())
+        );
+    }
+
+    #[test]
+    fn untyped_message_is_rejected() {
+        assert!(matches!(
+            decode(message(&[], "42")),
+            Err(RejectedTopicMessage {
+                error: YdbError::Convert(_),
+                ..
+            })
+        ));
+        assert!(matches!(
+            decode(message(
+                &[
+                    (CONTENT_TYPE_METADATA_KEY, "application/msgpack"),
+                    (SCHEMA_VERSION_METADATA_KEY, "1"),
+                ],
+                "42",
+            )),
+            Err(RejectedTopicMessage {
+                error: YdbError::Convert(_),
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,92 @@
+use crate::client_topic::topicwriter::message::TopicWriterMessage;
+
+// Metadata items of a chunk; readers with `reassemble_chunks` glue chunks back by them.
+pub(crate) const CHUNK_ID_KEY: &str = "ydb-chunk-id";
+pub(crate) const CHUNK_INDEX_KEY: &str = "ydb-chunk-index";
+pub(crate) const CHUNK_COUNT_KEY: &str = "ydb-chunk-count";
+
+/// Splits the message into sequential chunks of at most `chunk_size` payload bytes.
+///
+/// Messages which fit are returned as is. The first chunk keeps the message metadata.
+pub(crate) fn split_message(
+    mut message: TopicWriterMessage,
+    chunk_size: usize,
+) -> Vec<TopicWriterMessage> {
+    if message.data.len() <= chunk_size {
+        return vec![message];
+    }
+
+    let chunk_id = uuid::Uuid::new_v4().to_string();
+    let count = message.data.len().div_ceil(chunk_size);
+    let data = std::mem::take(&mut message.data);
+    let mut metadata = std::mem::take(&mut message.metadata);
+
+    data.chunks(chunk_size)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let mut chunk_metadata = std::mem::take(&mut metadata);
+            chunk_metadata.extend([
+                (CHUNK_ID_KEY.to_string(), chunk_id.clone().into_bytes()),
+                (CHUNK_INDEX_KEY.to_string(), index.to_string().into_bytes()),
+                (CHUNK_COUNT_KEY.to_string(), count.to_string().into_bytes()),
+            ]);
+            TopicWriterMessage {
+                metadata: chunk_metadata,
+                data: chunk.to_vec(),
+                seq_no: None,
+                created_at: message.created_at,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value<'a>(message: &'a TopicWriterMessage, key: &str) -> Option<&'a [u8]> {
+        message
+            .metadata
+            .iter()
+            .find(|(item_key, _)| item_key == key)
+            .map(|(_, value)| value.as_slice())
+    }
+
+    #[test]
+    fn small_message_is_not_split() {
+        let message = TopicWriterMessage::builder().data(vec![1, 2, 3]).build();
+
+        let chunks = split_message(message, 3);
+
+        assert_eq!(chunks.len(), 1);
+        assert!(chunks[0].metadata.is_empty());
+    }
+
+    #[test]
+    fn large_message_is_split_into_ordered_chunks() {
+        let message = TopicWriterMessage::builder()
+            .metadata("trace-id", "abc")
+            .data((0..10).collect())
+            .build();
+
+        let chunks = split_message(message, 4);
+
+        assert_eq!(
+            chunks
+                .iter()
+                .map(|chunk| chunk.data.clone())
+                .collect::<Vec<_>>(),
+            [vec![0, 1, 2, 3], vec![4, 5, 6, 7], vec![8, 9]]
+        );
+        assert_eq!(value(&chunks[0], "trace-id"), Some(b"abc".as_slice()));
+        assert_eq!(value(&chunks[1], "trace-id"), None);
+        for (index, chunk) in chunks.iter().enumerate() {
+            assert_eq!(value(chunk, CHUNK_ID_KEY), value(&chunks[0], CHUNK_ID_KEY));
+            assert_eq!(
+                value(chunk, CHUNK_INDEX_KEY),
+                Some(index.to_string().as_bytes())
+            );
+            assert_eq!(value(chunk, CHUNK_COUNT_KEY), Some(b"3".as_slice()));
+        }
+    }
+}
@@ -0,0 +1,334 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures_util::future::{join_all, try_join_all};
+use tokio::sync::{Mutex, OnceCell, RwLock};
+use tracing::{debug, instrument};
+
+use crate::client_topic::client::{DescribeTopicOptionsBuilder, TopicClient};
+use crate::client_topic::topicwriter::message::TopicWriterMessage;
+use crate::client_topic::topicwriter::message_write_status::MessageWriteStatus;
+use crate::client_topic::topicwriter::partitioning::PartitioningStrategy;
+use crate::client_topic::topicwriter::writer::{AckFuture, TopicWriter};
+use crate::client_topic::topicwriter::writer_options::TopicWriterOptions;
+use crate::{YdbError, YdbResult};
+
+#[derive(bon::Builder, Clone)]
+pub struct KeyedTopicWriterOptions {
+    /// Template for the per-partition writers.
+    ///
+    /// `producer_id` (random if unset) is used as a prefix: the writer for partition `N`
+    /// gets producer id `{producer_id}-{N}`. `partitioning` is ignored.
+    pub(crate) writer_options: TopicWriterOptions,
+
+    /// How often the partition list is re-read with `describe_topic` to pick up
+    /// added or split partitions.
+    #[builder(default = Duration::from_secs(60))]
+    pub(crate) partitions_refresh_interval: Duration,
+}
+
+/// Writes each message to a partition chosen by its key.
+///
+/// The key is hashed onto the active partitions of the topic, so messages with the same key
+/// go to the same partition and keep their order while the partition set is unchanged.
+/// When partitions are added the mapping is recomputed and a key may move to another partition.
+///
+/// Every partition gets its own [`TopicWriter`] with its own producer id and seq_no, created on
+/// the first write to that partition.
+pub struct KeyedTopicWriter {
+    client: TopicClient,
+    options: KeyedTopicWriterOptions,
+    producer_id_prefix: String,
+    state: RwLock<KeyedWriterState>,
+    // Makes partition refresh single-flight.
+    refresh: Mutex<()>,
+}
+
+// Writer of a partition is created outside the state lock, on the first write to it.
+type WriterCell = Arc<OnceCell<Arc<TopicWriter>>>;
+
+struct KeyedWriterState {
+    // Sorted ids of the active partitions.
+    partitions: Vec<i64>,
+    refreshed_at: Instant,
+    writers: HashMap<i64, WriterCell>,
+    // Writers of partitions which are not active anymore, kept to be stopped gracefully.
+    retired: Vec<WriterCell>,
+}
+
+impl KeyedTopicWriter {
+    pub(crate) async fn new(
+        options: KeyedTopicWriterOptions,
+        mut client: TopicClient,
+    ) -> YdbResult<Self> {
+        let producer_id_prefix = options
+            .writer_options
+            .producer_id
+            .clone()
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+        let partitions = active_partitions(&mut client, &options.writer_options.topic_path).await?;
+
+        Ok(Self {
+            client,
+            options,
+            producer_id_prefix,
+            state: RwLock::new(KeyedWriterState {
+                partitions,
+                refreshed_at: Instant::now(),
+                writers: HashMap::new(),
+                retired: Vec::new(),
+            }),
+            refresh: Mutex::new(()),
+        })
+    }
+
+    #[instrument(name = "ydb.KeyedTopicWriter.Write", skip_all, fields(db.system.name = "ydb"), err)]
+    pub async fn write(&self, key: impl AsRef<[u8]>, message: TopicWriterMessage) -> YdbResult<()> {
+        self.writer_for_key(key.as_ref())
+            .await?
+            .write(message)
+            .await
+    }
+
+    #[instrument(name = "ydb.KeyedTopicWriter.WriteWithAck", skip_all, fields(db.system.name = "ydb"), err)]
+    pub async fn write_with_ack(
+        &self,
+        key: impl AsRef<[u8]>,
+        message: TopicWriterMessage,
+    ) -> YdbResult<MessageWriteStatus> {
+        self.writer_for_key(key.as_ref())
+            .await?
+            .write_with_ack(message)
+            .await
+    }
+
+    #[instrument(name = "ydb.KeyedTopicWriter.WriteWithAckFuture", skip_all, fields(db.system.name = "ydb"), err)]
+    pub async fn write_with_ack_future(
+        &self,
+        key: impl AsRef<[u8]>,
+        message: TopicWriterMessage,
+    ) -> YdbResult<AckFuture> {
+        self.writer_for_key(key.as_ref())
+            .await?
+            .write_with_ack_future(message)
+            .await
+    }
+
+    /// Partition the key is currently routed to.
+    pub async fn partition_for_key(&self, key: impl AsRef<[u8]>) -> YdbResult<i64> {
+        self.refresh_if_expired().await?;
+        let state = self.state.read().await;
+        Ok(partition_for_key(&state.partitions, key.as_ref()))
+    }
+
+    /// Re-reads the topic partitions now instead of waiting for the refresh interval.
+    pub async fn refresh_partitions(&self) -> YdbResult<()> {
+        let _refresh = self.refresh.lock().await;
+        self.refresh_partitions_locked().await
+    }
+
+    async fn refresh_partitions_locked(&self) -> YdbResult<()> {
+        let partitions = active_partitions(
+            &mut self.client.clone(),
+            &self.options.writer_options.topic_path,
+        )
+        .await?;
+
+        let mut state = self.state.write().await;
+        if state.partitions != partitions {
+            debug!(
+                "keyed writer partitions changed: {:?} -> {:?}",
+                state.partitions, partitions
+            );
+            let retired: Vec<i64> = state
+                .writers
+                .keys()
+                .copied()
+                .filter(|partition_id| partitions.binary_search(partition_id).is_err())
+                .collect();
+            for partition_id in retired {
+                if let Some(writer) = state.writers.remove(&partition_id) {
+                    state.retired.push(writer);
+                }
+            }
+            state.partitions = partitions;
+        }
+        state.refreshed_at = Instant::now();
+
+        Ok(())
+    }
+
+    #[instrument(name = "ydb.KeyedTopicWriter.Flush", skip_all, fields(db.system.name = "ydb"), err)]
+    pub async fn flush(&self) -> YdbResult<()> {
+        let writers: Vec<Arc<TopicWriter>> = {
+            let state = self.state.read().await;
+            state
+                .writers
+                .values()
+                .chain(state.retired.iter())
+                .filter_map(|cell| cell.get().cloned())
+                .collect()
+        };
+
+        try_join_all(writers.iter().map(|writer| writer.flush())).await?;
+        Ok(())
+    }
+
+    #[instrument(name = "ydb.KeyedTopicWriter.Stop", skip_all, fields(db.system.name = "ydb"), err)]
+    pub async fn stop(self) -> YdbResult<()> {
+        let state = self.state.into_inner();
+
+        let results = join_all(state.writers.into_values().chain(state.retired).map(
+            |cell| async move {
+                let writer = match Arc::try_unwrap(cell) {
+                    Ok(cell) => cell.into_inner(),
+                    Err(_) => {
+                        return Err(YdbError::custom("stop: partition writer is still in use"));
+                    }
+                };
+                match writer.map(Arc::try_unwrap) {
+                    None => Ok(()),
+                    Some(Ok(writer)) => writer.stop().await,
+                    Some(Err(_)) => Err(YdbError::custom("stop: partition writer is still in use")),
+                }
+            },
+        ))
+        .await;
+
+        results.into_iter().collect()
+    }
+
+    async fn writer_for_key(&self, key: &[u8]) -> YdbResult<Arc<TopicWriter>> {
+        self.refresh_if_expired().await?;
+
+        let (partition_id, cell) = {
+            let state = self.state.read().await;
+            let partition_id = partition_for_key(&state.partitions, key);
+            (partition_id, state.writers.get(&partition_id).cloned())
+        };
+        let cell = match cell {
+            Some(cell) => cell,
+            None => self
+                .state
+                .write()
+                .await
+                .writers
+                .entry(partition_id)
+                .or_default()
+                .clone(),
+        };
+
+        // Connecting doesn't hold the state lock: writes to other partitions go on, and a
+        // failed connect leaves the cell empty for the next write to retry.
+        cell.get_or_try_init(|| async {
+            let mut writer_options = self.options.writer_options.clone();
+            writer_options.producer_id =
+                Some(format!("{}-{partition_id}", self.producer_id_prefix));
+            writer_options.partitioning = PartitioningStrategy::PartitionId(partition_id);
+
+            let writer = self
+                .client
+                .clone()
+                .create_writer_with_params(writer_options)
+                .await?;
+            Ok(Arc::new(writer))
+        })
+        .await
+        .cloned()
+    }
+
+    async fn refresh_if_expired(&self) -> YdbResult<()> {
+        if !self.is_expired().await {
+            return Ok(());
+        }
+        let _refresh = self.refresh.lock().await;
+        // Refreshed by a concurrent call while waiting for the lock.
+        if !self.is_expired().await {
+            return Ok(());
+        }
+        self.refresh_partitions_locked().await
+    }
+
+    async fn is_expired(&self) -> bool {
+        self.state.read().await.refreshed_at.elapsed() >= self.options.partitions_refresh_interval
+    }
+}
+
+async fn active_partitions(client: &mut TopicClient, topic_path: &str) -> YdbResult<Vec<i64>> {
+    let description = client
+        .describe_topic(
+            topic_path.to_string(),
+            DescribeTopicOptionsBuilder::default().build()?,
+        )
+        .await?;
+
+    let mut partitions: Vec<i64> = description
+        .partitions
+        .into_iter()
+        .filter(|partition| partition.active)
+        .map(|partition| partition.partition_id)
+        .collect();
+    partitions.sort_unstable();
+
+    if partitions.is_empty() {
+        return Err(YdbError::custom(format!(
+            "topic {topic_path} has no active partitions"
+        )));
+    }
+
+    Ok(partitions)
+}
+
+fn partition_for_key(partitions: &[i64], key: &[u8]) -> i64 {
+    partitions[(key_hash(key) % partitions.len() as u64) as usize]
+}
+
+// FNV-1a: stable across processes and releases, unlike the std hasher.
+fn key_hash(key: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    key.iter().fold(OFFSET_BASIS, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_hash_is_stable() {
+        assert_eq!(key_hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(key_hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn same_key_same_partition() {
+        let partitions = [0, 1, 2, 3];
+        for key in ["user-1", "user-2", "order-42"] {
+            let partition = partition_for_key(&partitions, key.as_bytes());
+            assert!(partitions.contains(&partition));
+            assert_eq!(partition_for_key(&partitions, key.as_bytes()), partition);
+        }
+    }
+
+    #[test]
+    fn keys_are_spread_over_partitions() {
+        let partitions = [0, 1, 2, 3];
+        let mut counts: HashMap<i64, usize> = HashMap::new();
+        for i in 0..1000 {
+            *counts
+                .entry(partition_for_key(
+                    &partitions,
+                    format!("key-{i}").as_bytes(),
+                ))
+                .or_default() += 1;
+        }
+
+        assert_eq!(counts.len(), partitions.len());
+        assert!(counts.values().all(|&count| count > 150));
+    }
+}
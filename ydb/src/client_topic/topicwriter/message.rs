@@ -1,11 +1,16 @@
 use std::time::{self, UNIX_EPOCH};
 
+use ydb_grpc::ydb_proto::topic::MetadataItem;
 use ydb_grpc::ydb_proto::topic::stream_write_message::write_request::MessageData;
 
 use crate::YdbError;
 
 #[derive(bon::Builder)]
 pub struct TopicWriterMessage {
+    // `field` attrs must come first (bon constraint)
+    #[builder(field)]
+    pub(crate) metadata: Vec<(String, Vec<u8>)>,
+
     // required
     pub(crate) data: Vec<u8>,
 
@@ -15,6 +20,15 @@ pub struct TopicWriterMessage {
     pub(crate) created_at: time::SystemTime,
 }
 
+impl<S: topic_writer_message_builder::State> TopicWriterMessageBuilder<S> {
+    /// Add message metadata item (header), available to readers with
+    /// [`crate::TopicReaderMessage::metadata`]. Keys may repeat.
+    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<Vec<u8>>) -> Self {
+        self.metadata.push((key.into(), value.into()));
+        self
+    }
+}
+
 impl TryFrom<TopicWriterMessage> for MessageData {
     type Error = YdbError;
 
@@ -33,10 +47,44 @@ impl TryFrom<TopicWriterMessage> for MessageData {
                 seconds: created_at.as_secs() as i64,
                 nanos: created_at.subsec_nanos() as i32,
             }),
-            metadata_items: vec![],
+            metadata_items: value
+                .metadata
+                .into_iter()
+                .map(|(key, value)| MetadataItem { key, value })
+                .collect(),
             data: value.data,
             uncompressed_size: data_size,
             partitioning: None,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metadata_items_are_sent_in_order() -> crate::YdbResult<()> {
+        let message = TopicWriterMessage::builder()
+            .metadata("trace-id", "abc")
+            .data(vec![1, 2, 3])
+            .metadata("content-type", b"application/json".to_vec())
+            .seq_no(1)
+            .build();
+
+        let data = MessageData::try_from(message)?;
+        let items: Vec<(&str, &[u8])> = data
+            .metadata_items
+            .iter()
+            .map(|item| (item.key.as_str(), item.value.as_slice()))
+            .collect();
+        assert_eq!(
+            items,
+            [
+                ("trace-id", b"abc".as_slice()),
+                ("content-type", b"application/json".as_slice()),
+            ]
+        );
+        Ok(())
+    }
+}
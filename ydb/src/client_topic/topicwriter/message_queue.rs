@@ -11,6 +11,8 @@ pub(crate) struct MessageQueue {
     sent_messages: VecDeque<MessageData>,
     // Sequence number of the last message that has been added to the queue
     last_added_seq_no: Option<i64>,
+    // Messages up to this seq_no were sent before a reconnect and may be written already.
+    resent_up_to_seq_no: Option<i64>,
 }
 
 #[derive(Debug)]
@@ -26,6 +28,7 @@ impl MessageQueue {
             messages: VecDeque::new(),
             sent_messages: VecDeque::new(),
             last_added_seq_no: None,
+            resent_up_to_seq_no: None,
         }
     }
 
@@ -90,7 +93,33 @@ impl MessageQueue {
         Ok(())
     }
 
+    // Removes the oldest message which was never sent. Messages returned to pending by
+    // reset_progress are kept: the server may have written them before the reconnect.
+    pub(crate) fn pop_pending_message(&mut self) -> Option<MessageData> {
+        let resent_up_to_seq_no = self.resent_up_to_seq_no;
+        let index = self.messages.iter().position(|message| {
+            resent_up_to_seq_no.is_none_or(|resent_up_to| message.seq_no > resent_up_to)
+        })?;
+        let message = self.messages.remove(index)?;
+        if index == self.messages.len() {
+            // Nobody should wait for an ack of the dropped message.
+            self.last_added_seq_no = self
+                .messages
+                .back()
+                .or(self.sent_messages.back())
+                .map(|message| message.seq_no);
+        }
+        Some(message)
+    }
+
+    pub(crate) fn in_flight_len(&self) -> usize {
+        self.sent_messages.len()
+    }
+
     pub(crate) fn reset_progress(&mut self) {
+        if let Some(last_sent) = self.sent_messages.back() {
+            self.resent_up_to_seq_no = self.resent_up_to_seq_no.max(Some(last_sent.seq_no));
+        }
         self.sent_messages.append(&mut self.messages);
         swap(&mut self.messages, &mut self.sent_messages);
     }
@@ -284,4 +313,20 @@ mod tests {
         assert_eq!(q.messages[3].seq_no, 4);
         assert_eq!(q.messages[4].seq_no, 5);
     }
+
+    #[test]
+    fn pop_pending_message_keeps_messages_sent_before_reset() {
+        let mut q = MessageQueue::new();
+        for i in 1..=3 {
+            q.add_message(create_message(i, vec![])).unwrap();
+        }
+        move_all_pending_to_sent(&mut q);
+        q.add_message(create_message(4, vec![])).unwrap();
+        q.reset_progress();
+
+        assert_eq!(q.pop_pending_message().map(|m| m.seq_no), Some(4));
+        assert_eq!(q.last_added_seq_no, Some(3));
+        assert!(q.pop_pending_message().is_none());
+        assert_eq!(q.messages.len(), 3);
+    }
 }
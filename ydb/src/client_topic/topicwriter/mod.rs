@@ -1,4 +1,6 @@
+pub(crate) mod chunking;
 pub mod connection;
+pub mod keyed_writer;
 pub mod message;
 pub mod message_queue;
 pub mod message_write_status;
@@ -7,8 +9,10 @@ pub mod queue;
 pub mod reception_queue;
 pub mod reconnector;
 pub mod stream_writer;
+pub mod typed_writer;
 pub mod writer;
 pub mod writer_options;
+pub mod writer_stats;
 pub mod writer_tx;
 pub mod writer_tx_options;
 
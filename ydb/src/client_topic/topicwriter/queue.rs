@@ -12,15 +12,28 @@ use crate::client_topic::topicwriter::message_write_status::{
     MessageWriteStatus, MessageWriteStatusValidator, WriteAck,
 };
 use crate::client_topic::topicwriter::reception_queue::{ReceptionQueue, ReceptionTicket};
+use crate::client_topic::topicwriter::writer_options::BufferOverflowPolicy;
+use crate::client_topic::topicwriter::writer_stats::{TopicWriterStats, WriterStatsCollector};
 use crate::{YdbError, YdbResult};
 
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct BufferLimits {
+    pub(crate) max_bytes: Option<usize>,
+    pub(crate) max_messages: Option<usize>,
+    pub(crate) overflow_policy: BufferOverflowPolicy,
+}
+
 #[derive(Clone)]
 pub(crate) struct Queue {
     inner: Arc<Mutex<QueueInner>>,
+    limits: BufferLimits,
+    stats: Arc<WriterStatsCollector>,
 
     new_message_added: Arc<Notify>,
     last_acknowledged_seq_no: Arc<RwLock<Option<i64>>>,
     message_acknowledged: Arc<Notify>,
+    // Wakes writes blocked on the buffer limit.
+    buffer_space_freed: Arc<Notify>,
 }
 
 impl Queue {
@@ -32,23 +45,70 @@ impl Queue {
     }
 
     pub(crate) fn new_with_status_validator(status_validator: MessageWriteStatusValidator) -> Self {
+        let stats = Arc::new(WriterStatsCollector::default());
         Self {
-            inner: Arc::new(Mutex::new(QueueInner::new(status_validator))),
+            inner: Arc::new(Mutex::new(QueueInner::new(status_validator, stats.clone()))),
+            limits: BufferLimits::default(),
+            stats,
             new_message_added: Arc::new(Notify::new()),
             last_acknowledged_seq_no: Arc::new(RwLock::new(None)),
             message_acknowledged: Arc::new(Notify::new()),
+            buffer_space_freed: Arc::new(Notify::new()),
         }
     }
 
+    pub(crate) fn with_buffer_limits(mut self, limits: BufferLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub(crate) fn stats(&self) -> TopicWriterStats {
+        self.stats.snapshot()
+    }
+
     pub(crate) async fn add_message(
         &self,
         message: MessageData,
         ack_sender: Option<oneshot::Sender<YdbResult<MessageWriteStatus>>>,
     ) -> YdbResult<()> {
-        let mut inner = self.inner.lock().await;
-        inner.add_message(message, ack_sender)?;
-        self.new_message_added.notify_one();
-        Ok(())
+        let size = message.data.len();
+        loop {
+            // Register before checking the buffer, so a release between the check
+            // and the wait is not missed.
+            let space_freed = self.buffer_space_freed.notified();
+            tokio::pin!(space_freed);
+            space_freed.as_mut().enable();
+
+            {
+                let mut inner = self.inner.lock().await;
+                if !inner.is_open_for_new_messages || inner.has_room(size, &self.limits) {
+                    inner.add_message(message, ack_sender)?;
+                    self.new_message_added.notify_one();
+                    return Ok(());
+                }
+
+                match self.limits.overflow_policy {
+                    BufferOverflowPolicy::Block => {}
+                    BufferOverflowPolicy::FailFast => {
+                        return Err(YdbError::TopicWriterBufferOverflow(format!(
+                            "topic writer buffer is full: buffered_bytes={}, buffered_messages={}, message_size={size}",
+                            inner.buffered_bytes,
+                            inner.reception_queue.len(),
+                        )));
+                    }
+                    BufferOverflowPolicy::DropOldest => {
+                        while !inner.has_room(size, &self.limits) && inner.drop_oldest_pending() {}
+                        if inner.has_room(size, &self.limits) {
+                            inner.add_message(message, ack_sender)?;
+                            self.new_message_added.notify_one();
+                            return Ok(());
+                        }
+                    }
+                }
+            }
+
+            space_freed.await;
+        }
     }
 
     pub(crate) async fn acknowledge_message(&self, write_ack: WriteAck) -> YdbResult<()> {
@@ -58,6 +118,7 @@ impl Queue {
 
         *self.last_acknowledged_seq_no.write().await = Some(seq_no);
         self.message_acknowledged.notify_one();
+        self.buffer_space_freed.notify_waiters();
 
         Ok(())
     }
@@ -87,9 +148,11 @@ impl Queue {
         threshold: usize,
     ) -> AppendMessageToSendBufferResult {
         let mut inner = self.inner.lock().await;
-        inner
+        let result = inner
             .message_queue
-            .append_message_to_send_buffer(send_buffer, threshold)
+            .append_message_to_send_buffer(send_buffer, threshold);
+        inner.publish_buffer_stats();
+        result
     }
 
     pub(crate) async fn get_messages_to_send(
@@ -130,16 +193,21 @@ impl Queue {
     pub(crate) async fn notify_reception_tickets(&self, error: YdbError) {
         let mut inner = self.inner.lock().await;
         inner.reception_queue.send_error_to_tickets_and_clear(error);
+        inner.buffered_bytes = 0;
+        inner.publish_buffer_stats();
+        self.buffer_space_freed.notify_waiters();
     }
 
     pub(crate) async fn close_for_new_messages(&self) {
         let mut inner = self.inner.lock().await;
         inner.is_open_for_new_messages = false;
+        self.buffer_space_freed.notify_waiters();
     }
 
     pub(crate) async fn reset_progress(&self) {
         let mut inner = self.inner.lock().await;
         inner.message_queue.reset_progress();
+        inner.publish_buffer_stats();
     }
 
     pub(crate) async fn flush(&self) -> YdbResult<()> {
@@ -160,16 +228,65 @@ struct QueueInner {
     reception_queue: ReceptionQueue,
     is_open_for_new_messages: bool,
     status_validator: MessageWriteStatusValidator,
+    // Payload bytes of messages which have a reception ticket.
+    buffered_bytes: usize,
+    stats: Arc<WriterStatsCollector>,
 }
 
 impl QueueInner {
-    fn new(status_validator: MessageWriteStatusValidator) -> Self {
+    fn new(
+        status_validator: MessageWriteStatusValidator,
+        stats: Arc<WriterStatsCollector>,
+    ) -> Self {
         Self {
             message_queue: MessageQueue::new(),
             reception_queue: ReceptionQueue::new(),
             is_open_for_new_messages: true,
             status_validator,
+            buffered_bytes: 0,
+            stats,
+        }
+    }
+
+    fn has_room(&self, size: usize, limits: &BufferLimits) -> bool {
+        let buffered_messages = self.reception_queue.len();
+        if buffered_messages == 0 {
+            return true;
         }
+
+        limits
+            .max_messages
+            .is_none_or(|max_messages| buffered_messages < max_messages)
+            && limits
+                .max_bytes
+                .is_none_or(|max_bytes| self.buffered_bytes + size <= max_bytes)
+    }
+
+    // Returns false if there is no unsent message to drop.
+    fn drop_oldest_pending(&mut self) -> bool {
+        let Some(message) = self.message_queue.pop_pending_message() else {
+            return false;
+        };
+
+        if let Some(ticket) = self.reception_queue.remove_ticket(message.seq_no) {
+            self.buffered_bytes -= ticket.size();
+            ticket.send_error_if_needed(YdbError::TopicWriterBufferOverflow(format!(
+                "message dropped from full topic writer buffer: seq_no={}",
+                message.seq_no
+            )));
+        }
+        self.stats.record_drop();
+        self.publish_buffer_stats();
+
+        true
+    }
+
+    fn publish_buffer_stats(&self) {
+        self.stats.set_buffer(
+            self.buffered_bytes,
+            self.reception_queue.len(),
+            self.message_queue.in_flight_len(),
+        );
     }
 
     fn add_message(
@@ -182,11 +299,14 @@ impl QueueInner {
         }
 
         let seq_no = message.seq_no;
+        let size = message.data.len();
 
         self.message_queue.add_message(message)?;
 
         self.reception_queue
-            .add_ticket(ReceptionTicket::new(seq_no, ack_sender));
+            .add_ticket(ReceptionTicket::new(seq_no, size, ack_sender));
+        self.buffered_bytes += size;
+        self.publish_buffer_stats();
 
         Ok(())
     }
@@ -212,6 +332,10 @@ impl QueueInner {
                 "reception ticket is missing after message queue ack",
             ));
         };
+        self.buffered_bytes -= ticket.size();
+        self.stats.record_ack(ticket.age());
+        self.publish_buffer_stats();
+
         let status_result = (self.status_validator)(write_ack.status);
         let flush_error = status_result.as_ref().err().cloned();
         ticket.send_result_if_needed(status_result);
@@ -564,4 +688,159 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    fn limited_queue(
+        max_bytes: Option<usize>,
+        max_messages: Option<usize>,
+        overflow_policy: BufferOverflowPolicy,
+    ) -> Queue {
+        Queue::new().with_buffer_limits(BufferLimits {
+            max_bytes,
+            max_messages,
+            overflow_policy,
+        })
+    }
+
+    #[tokio::test]
+    async fn fail_fast_rejects_message_over_limit() {
+        let q = limited_queue(Some(10), None, BufferOverflowPolicy::FailFast);
+        q.add_message(create_message(1, vec![0; 6]), None)
+            .await
+            .unwrap();
+
+        let err = q
+            .add_message(create_message(2, vec![0; 6]), None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, YdbError::TopicWriterBufferOverflow(_)));
+
+        q.add_message(create_message(3, vec![0; 4]), None)
+            .await
+            .unwrap();
+        assert_eq!(q.stats().buffered_bytes, 10);
+        assert_eq!(q.stats().buffered_messages, 2);
+    }
+
+    #[tokio::test]
+    async fn oversized_message_is_accepted_into_empty_buffer() {
+        let q = limited_queue(Some(10), None, BufferOverflowPolicy::FailFast);
+        q.add_message(create_message(1, vec![0; 100]), None)
+            .await
+            .unwrap();
+        assert_eq!(q.stats().buffered_bytes, 100);
+    }
+
+    #[tokio::test]
+    async fn block_waits_until_ack_frees_space() {
+        let q = Arc::new(limited_queue(None, Some(1), BufferOverflowPolicy::Block));
+        q.add_message(create_message(1, vec![]), None)
+            .await
+            .unwrap();
+
+        let q_add = Arc::clone(&q);
+        let mut add_handle =
+            tokio::spawn(async move { q_add.add_message(create_message(2, vec![]), None).await });
+        assert!(
+            timeout(Duration::from_millis(50), &mut add_handle)
+                .await
+                .is_err(),
+            "add must block while the buffer is full"
+        );
+
+        let messages = q.get_messages_to_send(10, Duration::ZERO).await;
+        assert_eq!(messages.len(), 1);
+        q.acknowledge_message(write_ack(1)).await.unwrap();
+
+        timeout(Duration::from_millis(100), add_handle)
+            .await
+            .expect("add must finish after ack")
+            .unwrap()
+            .unwrap();
+        assert_eq!(q.stats().buffered_messages, 1);
+    }
+
+    #[tokio::test]
+    async fn block_returns_error_when_queue_closed() {
+        let q = Arc::new(limited_queue(None, Some(1), BufferOverflowPolicy::Block));
+        q.add_message(create_message(1, vec![]), None)
+            .await
+            .unwrap();
+
+        let q_add = Arc::clone(&q);
+        let add_handle =
+            tokio::spawn(async move { q_add.add_message(create_message(2, vec![]), None).await });
+        tokio::task::yield_now().await;
+
+        q.close_for_new_messages().await;
+
+        let result = timeout(Duration::from_millis(100), add_handle)
+            .await
+            .expect("add must finish after close")
+            .unwrap();
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn drop_oldest_drops_unsent_messages_and_fails_their_acks() {
+        let q = limited_queue(None, Some(2), BufferOverflowPolicy::DropOldest);
+        let (tx1, rx1) = oneshot::channel();
+        let (tx2, rx2) = oneshot::channel();
+        q.add_message(create_message(1, vec![]), Some(tx1))
+            .await
+            .unwrap();
+        let sent = q.get_messages_to_send(10, Duration::ZERO).await;
+        assert_eq!(sent.len(), 1);
+        q.add_message(create_message(2, vec![]), Some(tx2))
+            .await
+            .unwrap();
+
+        // Message 1 is in flight, so message 2 is the one to drop.
+        q.add_message(create_message(3, vec![]), None)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            rx2.await.unwrap(),
+            Err(YdbError::TopicWriterBufferOverflow(_))
+        ));
+        let stats = q.stats();
+        assert_eq!(stats.dropped_messages, 1);
+        assert_eq!(stats.buffered_messages, 2);
+        assert_eq!(stats.in_flight_messages, 1);
+
+        q.acknowledge_message(write_ack(1)).await.unwrap();
+        assert!(rx1.await.unwrap().is_ok());
+        let msgs = q.get_messages_to_send(10, Duration::ZERO).await;
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(msgs[0].seq_no, 3);
+        q.acknowledge_message(write_ack(3)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn stats_track_buffer_and_ack_latency() {
+        let q = Queue::new();
+        q.add_message(create_message(1, vec![0; 3]), None)
+            .await
+            .unwrap();
+        q.add_message(create_message(2, vec![0; 5]), None)
+            .await
+            .unwrap();
+        let _ = q.get_messages_to_send(1, Duration::ZERO).await;
+
+        let stats = q.stats();
+        assert_eq!(stats.buffered_bytes, 8);
+        assert_eq!(stats.buffered_messages, 2);
+        assert_eq!(stats.in_flight_messages, 1);
+        assert!(stats.last_ack_latency.is_none());
+
+        q.acknowledge_message(write_ack(1)).await.unwrap();
+
+        let stats = q.stats();
+        assert_eq!(stats.buffered_bytes, 5);
+        assert_eq!(stats.buffered_messages, 1);
+        assert_eq!(stats.in_flight_messages, 0);
+        assert_eq!(stats.acked_messages, 1);
+        assert!(stats.last_ack_latency.is_some());
+        assert_eq!(stats.mean_ack_latency, stats.last_ack_latency);
+    }
 }
@@ -1,4 +1,5 @@
 use std::collections::VecDeque;
+use std::time::{Duration, Instant};
 
 use tokio::sync::oneshot;
 
@@ -7,21 +8,38 @@ use crate::{YdbError, YdbResult};
 
 pub(crate) struct ReceptionTicket {
     seq_no: i64,
+    // Payload size, counted against the writer buffer limit until the ticket is popped.
+    size: usize,
+    created_at: Instant,
     ack_sender: Option<oneshot::Sender<YdbResult<MessageWriteStatus>>>,
 }
 
 impl ReceptionTicket {
     pub(crate) fn new(
         seq_no: i64,
+        size: usize,
         ack_sender: Option<oneshot::Sender<YdbResult<MessageWriteStatus>>>,
     ) -> Self {
-        Self { seq_no, ack_sender }
+        Self {
+            seq_no,
+            size,
+            created_at: Instant::now(),
+            ack_sender,
+        }
     }
 
     pub(crate) fn seq_no(&self) -> i64 {
         self.seq_no
     }
 
+    pub(crate) fn size(&self) -> usize {
+        self.size
+    }
+
+    pub(crate) fn age(&self) -> Duration {
+        self.created_at.elapsed()
+    }
+
     pub(crate) fn send_result_if_needed(self, write_result: YdbResult<MessageWriteStatus>) {
         if let Some(sender) = self.ack_sender {
             let _ = sender.send(write_result);
@@ -110,6 +128,19 @@ impl ReceptionQueue {
         }
     }
 
+    // Removes a ticket of a message dropped before it was sent.
+    pub(crate) fn remove_ticket(&mut self, seq_no: i64) -> Option<ReceptionTicket> {
+        let index = self
+            .ticket_queue
+            .iter()
+            .position(|ticket| ticket.seq_no == seq_no)?;
+        self.ticket_queue.remove(index)
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.ticket_queue.len()
+    }
+
     pub(crate) fn add_ticket(&mut self, reception_ticket: ReceptionTicket) {
         self.ticket_queue.push_back(reception_ticket);
     }
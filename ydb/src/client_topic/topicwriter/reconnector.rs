@@ -9,14 +9,16 @@ use tracing::{error, trace};
 use ydb_grpc::ydb_proto::topic::stream_write_message;
 
 use crate::client_topic::compression::Executor;
+use crate::client_topic::topicwriter::chunking::split_message;
 use crate::client_topic::topicwriter::connection::ConnectionInfo;
 use crate::client_topic::topicwriter::message::TopicWriterMessage;
 use crate::client_topic::topicwriter::message_write_status::{
     MessageWriteStatus, MessageWriteStatusValidator,
 };
-use crate::client_topic::topicwriter::queue::Queue;
+use crate::client_topic::topicwriter::queue::{BufferLimits, Queue};
 use crate::client_topic::topicwriter::stream_writer::StreamWriter;
-use crate::client_topic::topicwriter::writer_options::TopicWriterOptions;
+use crate::client_topic::topicwriter::writer_options::{BufferOverflowPolicy, TopicWriterOptions};
+use crate::client_topic::topicwriter::writer_stats::TopicWriterStats;
 use crate::errors::NeedRetry;
 use crate::grpc_connection_manager::GrpcConnectionManager;
 use crate::grpc_wrapper::grpc_stream_wrapper::AsyncGrpcStreamWrapper;
@@ -66,15 +68,35 @@ pub(crate) struct Reconnector {
     reconnect_loop: JoinHandle<()>,
     queue: Queue,
     auto_seq_no: bool,
+    chunk_size: Option<usize>,
     flush_timeout: Duration,
     status_rx: watch::Receiver<ReconnectorStatus>,
 }
 
 impl Reconnector {
     pub(crate) async fn new(params: ReconnectorParams) -> YdbResult<Self> {
-        let queue = Queue::new_with_status_validator(params.status_validator);
+        let queue = Queue::new_with_status_validator(params.status_validator).with_buffer_limits(
+            BufferLimits {
+                max_bytes: params.writer_options.max_buffered_bytes,
+                max_messages: params.writer_options.max_buffered_messages,
+                overflow_policy: params.writer_options.buffer_overflow_policy,
+            },
+        );
         let cancellation_token = params.cancellation_token;
         let auto_seq_no = params.writer_options.auto_seq_no;
+        let chunk_size = params.writer_options.chunk_size;
+        if chunk_size == Some(0) {
+            return Err(YdbError::custom("topic writer chunk_size must be positive"));
+        }
+        // Chunks are queued one by one: failing or dropping one of them would leave
+        // a partial message in the topic.
+        if chunk_size.is_some()
+            && params.writer_options.buffer_overflow_policy != BufferOverflowPolicy::Block
+        {
+            return Err(YdbError::custom(
+                "topic writer chunking requires BufferOverflowPolicy::Block",
+            ));
+        }
 
         let (init_tx, init_rx) = oneshot::channel();
         let (status_tx, status_rx) = watch::channel(ReconnectorStatus::Working);
@@ -111,6 +133,7 @@ impl Reconnector {
             reconnect_loop,
             queue,
             auto_seq_no,
+            chunk_size,
             flush_timeout: params.flush_timeout,
             status_rx,
         })
@@ -131,11 +154,17 @@ impl Reconnector {
 
     pub(crate) async fn add_message(
         &self,
-        mut message: TopicWriterMessage,
-        ack_sender: Option<oneshot::Sender<YdbResult<MessageWriteStatus>>>,
+        message: TopicWriterMessage,
+        mut ack_sender: Option<oneshot::Sender<YdbResult<MessageWriteStatus>>>,
     ) -> YdbResult<()> {
         self.check_working()?;
 
+        if self.auto_seq_no && message.seq_no.is_some() {
+            return Err(YdbError::custom(
+                "explicitly specifying message.seq_no is only allowed if auto_seq_no is disabled",
+            ));
+        }
+
         // Here we take a lock across the whole function.
         // Updating last_seq_no_assigned and putting a new message to the queue must be transactional.
         //
@@ -144,25 +173,64 @@ impl Reconnector {
         // 2. Thread2: last_seq_no_assigned = 2
         // 3. Thread2: add message<seq_no=2> to queue
         // 4. Thread1: add message<seq_no=1> to queue <--- ERROR: Message seq_no order is violated.
+        // Chunks of one message are added under the same lock, so they stay contiguous.
         let mut state_guard = self.state.lock().await;
 
-        if self.auto_seq_no {
-            if message.seq_no.is_some() {
-                return Err(YdbError::custom(
-                    "explicitly specifying message.seq_no is only allowed if auto_seq_no is disabled",
-                ));
+        let messages = match self.chunk_size {
+            Some(chunk_size) if message.data.len() > chunk_size => {
+                if !self.auto_seq_no {
+                    return Err(YdbError::custom(
+                        "topic writer chunking requires auto_seq_no",
+                    ));
+                }
+                split_message(message, chunk_size)
+            }
+            _ => vec![message],
+        };
+
+        let last = messages.len() - 1;
+        for (index, mut message) in messages.into_iter().enumerate() {
+            if self.auto_seq_no {
+                let last_seq_no_assigned = state_guard.connection_info.last_seq_no_assigned;
+                message.seq_no = Some(last_seq_no_assigned + 1);
             }
-            let last_seq_no_assigned = state_guard.connection_info.last_seq_no_assigned;
-            message.seq_no = Some(last_seq_no_assigned + 1);
+
+            let Some(message_seq_no) = message.seq_no else {
+                return Err(YdbError::custom("empty message seq_no is provided"));
+            };
+            state_guard.connection_info.last_seq_no_assigned = message_seq_no;
+
+            // The last chunk is acknowledged after all previous ones.
+            let ack_sender = if index == last {
+                ack_sender.take()
+            } else {
+                None
+            };
+            self.add_to_queue(message.try_into()?, ack_sender).await?;
         }
+        Ok(())
+    }
 
-        let Some(message_seq_no) = message.seq_no else {
-            return Err(YdbError::custom("empty message seq_no is provided"));
-        };
-        state_guard.connection_info.last_seq_no_assigned = message_seq_no;
+    async fn add_to_queue(
+        &self,
+        message: stream_write_message::write_request::MessageData,
+        ack_sender: Option<oneshot::Sender<YdbResult<MessageWriteStatus>>>,
+    ) -> YdbResult<()> {
+        // A write blocked on a full buffer must not outlive the reconnection loop.
+        let mut status_rx = self.status_rx.clone();
+        tokio::select! {
+            result = self.queue.add_message(message, ack_sender) => result,
+            _ = status_rx.wait_for(|status| !matches!(status, ReconnectorStatus::Working)) => {
+                Err(self
+                    .check_working()
+                    .err()
+                    .unwrap_or_else(|| YdbError::custom("topic writer is stopped")))
+            }
+        }
+    }
 
-        let message = message.try_into()?;
-        self.queue.add_message(message, ack_sender).await
+    pub(crate) fn stats(&self) -> TopicWriterStats {
+        self.queue.stats()
     }
 
     pub(crate) async fn flush(&self) -> YdbResult<()> {
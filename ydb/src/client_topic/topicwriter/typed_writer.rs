@@ -0,0 +1,60 @@
+use std::marker::PhantomData;
+
+use crate::YdbResult;
+use crate::client_topic::payload_format::{
+    CONTENT_TYPE_METADATA_KEY, PayloadEncoder, SCHEMA_VERSION_METADATA_KEY,
+};
+use crate::client_topic::topicwriter::message::TopicWriterMessage;
+use crate::client_topic::topicwriter::message_write_status::MessageWriteStatus;
+use crate::client_topic::topicwriter::writer::TopicWriter;
+
+/// Writes values of `T` serialized by the payload format `F`.
+///
+/// Every message gets `content-type` and `schema-version` metadata items, which
+/// [`crate::TypedTopicReader`] checks before deserializing.
+pub struct TypedTopicWriter<T, F> {
+    writer: TopicWriter,
+    format: F,
+    schema_version: u32,
+    _value: PhantomData<fn(&T)>,
+}
+
+impl<T, F: PayloadEncoder<T>> TypedTopicWriter<T, F> {
+    pub fn new(writer: TopicWriter, format: F, schema_version: u32) -> Self {
+        Self {
+            writer,
+            format,
+            schema_version,
+            _value: PhantomData,
+        }
+    }
+
+    pub async fn write(&self, value: &T) -> YdbResult<()> {
+        self.writer.write(self.message(value)?).await
+    }
+
+    pub async fn write_with_ack(&self, value: &T) -> YdbResult<MessageWriteStatus> {
+        self.writer.write_with_ack(self.message(value)?).await
+    }
+
+    /// Serialized message with the format metadata, e.g. for a transactional or keyed writer.
+    pub fn message(&self, value: &T) -> YdbResult<TopicWriterMessage> {
+        Ok(TopicWriterMessage::builder()
+            .metadata(CONTENT_TYPE_METADATA_KEY, self.format.content_type())
+            .metadata(SCHEMA_VERSION_METADATA_KEY, self.schema_version.to_string())
+            .data(self.format.encode(value)?)
+            .build())
+    }
+
+    pub async fn flush(&self) -> YdbResult<()> {
+        self.writer.flush().await
+    }
+
+    pub async fn stop(self) -> YdbResult<()> {
+        self.writer.stop().await
+    }
+
+    pub fn into_inner(self) -> TopicWriter {
+        self.writer
+    }
+}
@@ -1,7 +1,9 @@
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
-use std::task::{Context, Poll};
+use std::task::{Context, Poll, ready};
+
+use futures_util::Sink;
 
 use tokio::sync::{RwLock, oneshot};
 use tokio::task::JoinHandle;
@@ -15,6 +17,7 @@ use crate::client_topic::topicwriter::message_write_status::{
 };
 use crate::client_topic::topicwriter::reconnector::{Reconnector, ReconnectorParams};
 use crate::client_topic::topicwriter::writer_options::TopicWriterOptions;
+use crate::client_topic::topicwriter::writer_stats::TopicWriterStats;
 use crate::grpc_connection_manager::GrpcConnectionManager;
 use crate::{YdbError, YdbResult};
 use ydb_grpc::ydb_proto::topic::TransactionIdentity;
@@ -24,10 +27,20 @@ use ydb_grpc::ydb_proto::topic::TransactionIdentity;
 pub struct TopicWriter {
     fatal_error: Arc<RwLock<Option<YdbError>>>,
     wait_for_fatal_error_handle: JoinHandle<()>,
-    reconnector: Reconnector,
+    // Shared with in-progress Sink operations only.
+    reconnector: Arc<Reconnector>,
+    sink: SinkState,
     _cancel_on_drop: DropGuard,
 }
 
+type SinkFuture = Pin<Box<dyn Future<Output = YdbResult<()>> + Send + Sync>>;
+
+#[derive(Default)]
+struct SinkState {
+    write: Option<SinkFuture>,
+    flush: Option<SinkFuture>,
+}
+
 pub struct AckFuture {
     receiver: oneshot::Receiver<YdbResult<MessageWriteStatus>>,
 }
@@ -116,7 +129,8 @@ impl TopicWriter {
         Ok(Self {
             fatal_error,
             wait_for_fatal_error_handle,
-            reconnector,
+            reconnector: Arc::new(reconnector),
+            sink: SinkState::default(),
             _cancel_on_drop: cancel_on_drop,
         })
     }
@@ -164,15 +178,29 @@ impl TopicWriter {
         message: TopicWriterMessage,
         ack_sender: Option<oneshot::Sender<YdbResult<MessageWriteStatus>>>,
     ) -> YdbResult<()> {
-        if let Some(err) = self.fatal_error.read().await.as_ref() {
+        Self::write_to(&self.reconnector, &self.fatal_error, message, ack_sender).await
+    }
+
+    async fn write_to(
+        reconnector: &Reconnector,
+        fatal_error: &RwLock<Option<YdbError>>,
+        message: TopicWriterMessage,
+        ack_sender: Option<oneshot::Sender<YdbResult<MessageWriteStatus>>>,
+    ) -> YdbResult<()> {
+        if let Some(err) = fatal_error.read().await.as_ref() {
             return Err(err.clone());
         }
 
-        self.reconnector.add_message(message, ack_sender).await?;
+        reconnector.add_message(message, ack_sender).await?;
 
         Ok(())
     }
 
+    /// Buffer and acknowledgement counters of the writer.
+    pub fn stats(&self) -> TopicWriterStats {
+        self.reconnector.stats()
+    }
+
     #[instrument(name = "ydb.TopicWriter.Flush", skip_all, fields(db.system.name = "ydb"), err)]
     pub async fn flush(&self) -> YdbResult<()> {
         self.flush_inner().await
@@ -201,10 +229,18 @@ impl TopicWriter {
     }
 
     #[instrument(name = "ydb.TopicWriter.Stop", skip_all, fields(db.system.name = "ydb"), err)]
-    pub async fn stop(self) -> YdbResult<()> {
+    pub async fn stop(mut self) -> YdbResult<()> {
         trace!("stopping...");
 
-        let reconnector_result = self.reconnector.stop().await.map_err(|err| {
+        // Finish a write started through Sink, so the reconnector is not shared anymore.
+        if let Some(write) = self.sink.write.take() {
+            write.await?;
+        }
+        self.sink.flush = None;
+        let reconnector = Arc::into_inner(self.reconnector)
+            .ok_or_else(|| YdbError::custom("stop: topic writer is still in use"))?;
+
+        let reconnector_result = reconnector.stop().await.map_err(|err| {
             YdbError::custom(format!(
                 "stop: error while waiting for reconnector to finish: {err}"
             ))
@@ -225,3 +261,53 @@ impl TopicWriter {
         Ok(())
     }
 }
+
+/// Writes messages without acks; `poll_flush` waits for acks of everything written before.
+///
+/// `poll_close` only flushes: call [`TopicWriter::stop`] to close the writer.
+impl Sink<TopicWriterMessage> for TopicWriter {
+    type Error = YdbError;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<YdbResult<()>> {
+        let this = self.get_mut();
+        if let Some(write) = this.sink.write.as_mut() {
+            let result = ready!(write.as_mut().poll(cx));
+            this.sink.write = None;
+            result?;
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, message: TopicWriterMessage) -> YdbResult<()> {
+        let this = self.get_mut();
+        if this.sink.write.is_some() {
+            return Err(YdbError::custom(
+                "start_send called without poll_ready on topic writer sink",
+            ));
+        }
+
+        let reconnector = this.reconnector.clone();
+        let fatal_error = this.fatal_error.clone();
+        this.sink.write = Some(Box::pin(async move {
+            Self::write_to(&reconnector, &fatal_error, message, None).await
+        }));
+        Ok(())
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<YdbResult<()>> {
+        ready!(self.as_mut().poll_ready(cx))?;
+
+        let this = self.get_mut();
+        let flush = this.sink.flush.get_or_insert_with(|| {
+            let reconnector = this.reconnector.clone();
+            Box::pin(async move { reconnector.flush().await })
+        });
+        let result = ready!(flush.as_mut().poll(cx));
+        this.sink.flush = None;
+        Poll::Ready(result)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<YdbResult<()>> {
+        self.poll_flush(cx)
+    }
+}
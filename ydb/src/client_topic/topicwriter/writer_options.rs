@@ -28,6 +28,17 @@ pub struct TopicWriterOptions {
     #[builder(default)]
     pub(crate) codec_selector: CodecSelection,
 
+    /// Split payloads larger than this many bytes into sequential chunks, to be reassembled
+    /// by readers with `reassemble_chunks`. Requires `auto_seq_no` and
+    /// [`BufferOverflowPolicy::Block`].
+    pub(crate) chunk_size: Option<usize>,
+
+    // buffering: limits on written but not yet acknowledged messages
+    pub(crate) max_buffered_bytes: Option<usize>,
+    pub(crate) max_buffered_messages: Option<usize>,
+    #[builder(default)]
+    pub(crate) buffer_overflow_policy: BufferOverflowPolicy,
+
     // internal write-loop tuning
     #[builder(default = 1000)]
     pub(crate) write_request_messages_chunk_size: usize,
@@ -46,3 +57,23 @@ impl<S: topic_writer_options_builder::State> TopicWriterOptionsBuilder<S> {
         self
     }
 }
+
+/// What a write does when `max_buffered_bytes` or `max_buffered_messages` is reached.
+///
+/// A single message is always accepted into an empty buffer, even if it is larger than
+/// `max_buffered_bytes`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BufferOverflowPolicy {
+    /// Wait until acknowledgements free enough space.
+    #[default]
+    Block,
+    /// Fail the write with [`crate::YdbError::TopicWriterBufferOverflow`].
+    FailFast,
+    /// Drop the oldest messages which are not sent yet; their acks fail with
+    /// [`crate::YdbError::TopicWriterBufferOverflow`]. Waits like `Block` when only
+    /// in-flight messages are left.
+    ///
+    /// Messages sent before a reconnect are resent and never dropped, since the server may
+    /// have written them already.
+    DropOldest,
+}
@@ -0,0 +1,64 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Topic writer buffer counters, see [`crate::TopicWriter::stats`].
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(not(feature = "force-exhaustive-all"), non_exhaustive)]
+pub struct TopicWriterStats {
+    /// Payload bytes of written messages which are not acknowledged yet.
+    pub buffered_bytes: usize,
+    /// Written messages which are not acknowledged yet, including in-flight ones.
+    pub buffered_messages: usize,
+    /// Messages sent to the server and waiting for acknowledgement.
+    pub in_flight_messages: usize,
+    /// Messages acknowledged since the writer was created.
+    pub acked_messages: u64,
+    /// Messages dropped by [`crate::BufferOverflowPolicy::DropOldest`].
+    pub dropped_messages: u64,
+    /// Time from write to acknowledgement of the last acknowledged message.
+    pub last_ack_latency: Option<Duration>,
+    /// Mean time from write to acknowledgement over all acknowledged messages.
+    pub mean_ack_latency: Option<Duration>,
+}
+
+#[derive(Default)]
+pub(crate) struct WriterStatsCollector {
+    state: Mutex<StatsState>,
+}
+
+#[derive(Default)]
+struct StatsState {
+    stats: TopicWriterStats,
+    ack_latency_total: Duration,
+}
+
+impl WriterStatsCollector {
+    pub(crate) fn snapshot(&self) -> TopicWriterStats {
+        self.state.lock().expect("writer stats lock").stats.clone()
+    }
+
+    pub(crate) fn set_buffer(&self, bytes: usize, messages: usize, in_flight: usize) {
+        let stats = &mut self.state.lock().expect("writer stats lock").stats;
+        stats.buffered_bytes = bytes;
+        stats.buffered_messages = messages;
+        stats.in_flight_messages = in_flight;
+    }
+
+    pub(crate) fn record_ack(&self, latency: Duration) {
+        let mut state = self.state.lock().expect("writer stats lock");
+        state.ack_latency_total += latency;
+        state.stats.acked_messages += 1;
+        state.stats.last_ack_latency = Some(latency);
+        state.stats.mean_ack_latency = Some(Duration::from_nanos(
+            (state.ack_latency_total.as_nanos() / state.stats.acked_messages as u128) as u64,
+        ));
+    }
+
+    pub(crate) fn record_drop(&self) {
+        self.state
+            .lock()
+            .expect("writer stats lock")
+            .stats
+            .dropped_messages += 1;
+    }
+}
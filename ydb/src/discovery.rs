@@ -73,6 +73,14 @@ impl DiscoveryState {
         self.nodes.len() == 0
     }
 
+    // pessimized nodes are included: the caller needs exactly this node
+    pub(crate) fn node_uri(&self, node_id: u32) -> Option<&Uri> {
+        self.original_nodes
+            .iter()
+            .find(|node| node.node_id == Some(node_id))
+            .map(|node| &node.uri)
+    }
+
     fn is_pessimized(&self, uri: &Uri) -> bool {
         self.pessimized_nodes.contains(uri)
     }
@@ -108,11 +116,21 @@ impl Default for DiscoveryState {
 pub(crate) struct NodeInfo {
     pub(crate) uri: Uri,
     pub(crate) location: String,
+    pub(crate) node_id: Option<u32>,
 }
 
 impl NodeInfo {
     pub(crate) fn new(uri: Uri, location: String) -> Self {
-        Self { uri, location }
+        Self {
+            uri,
+            location,
+            node_id: None,
+        }
+    }
+
+    pub(crate) fn with_node_id(mut self, node_id: u32) -> Self {
+        self.node_id = Some(node_id);
+        self
     }
 }
 
@@ -405,7 +423,9 @@ impl DiscoverySharedState {
     fn list_endpoints_to_node_infos(list: Vec<EndpointInfo>) -> YdbResult<Vec<NodeInfo>> {
         list.into_iter()
             .map(|item| match Self::endpoint_info_to_uri(&item) {
-                Ok(uri) => YdbResult::<NodeInfo>::Ok(NodeInfo::new(uri, item.location.clone())),
+                Ok(uri) => YdbResult::<NodeInfo>::Ok(
+                    NodeInfo::new(uri, item.location.clone()).with_node_id(item.node_id),
+                ),
                 Err(err) => YdbResult::<NodeInfo>::Err(err),
             })
             .try_collect()
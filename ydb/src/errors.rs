@@ -111,6 +111,16 @@ pub enum YdbError {
 
     /// Error from operation status
     YdbStatusError(YdbStatusError),
+
+    /// Topic writer buffer limit is reached: the message is rejected or dropped
+    /// according to the writer's `BufferOverflowPolicy`.
+    TopicWriterBufferOverflow(String),
+
+    /// Migration script failed, `source` is the error of the script execution.
+    MigrationFailed {
+        message: String,
+        source: Box<YdbError>,
+    },
 }
 
 impl YdbError {
@@ -286,7 +296,9 @@ impl YdbError {
             | Self::Custom(_)
             | Self::InternalError(_)
             | Self::NoRows
-            | Self::EndpointHasNoHost(_) => NeedRetry::False,
+            | Self::EndpointHasNoHost(_)
+            | Self::TopicWriterBufferOverflow(_) => NeedRetry::False,
+            Self::MigrationFailed { source, .. } => source.need_retry(),
             Self::TransportDial(_) => NeedRetry::True,
             Self::Transport(_) => IdempotentOnly, // TODO: check when transport error created
             Self::TransportGRPCStatus(status) => {
@@ -336,7 +348,14 @@ macro_rules! to_custom_ydb_err {
     };
 }
 
-impl std::error::Error for YdbError {}
+impl std::error::Error for YdbError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::MigrationFailed { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
 
 #[cfg(test)]
 mod invalidate_tx_tests {
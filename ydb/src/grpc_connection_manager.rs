@@ -84,6 +84,13 @@ impl<BalancerT, ConnectionT: Connection> GrpcConnectionManagerGeneric<BalancerT,
         self.balancer.endpoint(service)
     }
 
+    pub(crate) fn node_endpoint(&self, node_id: u32) -> Option<Uri>
+    where
+        BalancerT: LoadBalancer,
+    {
+        self.balancer.node_endpoint(node_id)
+    }
+
     pub(crate) fn database(&self) -> &String {
         &self.database
     }
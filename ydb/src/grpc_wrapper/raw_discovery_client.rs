@@ -48,6 +48,7 @@ impl GrpcDiscoveryClient {
                 port: item.port,
                 ssl: item.ssl,
                 location: item.location,
+                node_id: item.node_id,
             })
             .collect_vec();
         Ok(res)
@@ -59,6 +60,7 @@ pub(crate) struct EndpointInfo {
     pub(crate) port: u32,
     pub(crate) ssl: bool,
     pub(crate) location: String,
+    pub(crate) node_id: u32,
 }
 
 impl GrpcServiceForDiscovery for GrpcDiscoveryClient {
@@ -6,6 +6,7 @@ use crate::grpc_wrapper::raw_scheme_client::describe_path_types::{
 use crate::grpc_wrapper::raw_scheme_client::list_directory_types::{
     RawListDirectoryRequest, RawListDirectoryResult,
 };
+use crate::grpc_wrapper::raw_scheme_client::modify_permissions_types::RawModifyPermissionsRequest;
 use crate::grpc_wrapper::raw_services::{GrpcServiceForDiscovery, Service};
 use crate::grpc_wrapper::raw_ydb_operation::RawOperationParams;
 use crate::grpc_wrapper::runtime_interceptors::InterceptedChannel;
@@ -74,6 +75,14 @@ impl RawSchemeClient {
             ydb_grpc::ydb_proto::scheme::DescribePathResult => RawDescribePathResult
         );
     }
+
+    #[instrument(name = "ydb.grpc.ModifyPermissions", skip(self), fields(db.system.name = "ydb", ydb.path = %req.path), err)]
+    pub async fn modify_permissions(&mut self, req: RawModifyPermissionsRequest) -> RawResult<()> {
+        request_without_result!(
+            self.service.modify_permissions,
+            req => ydb_grpc::ydb_proto::scheme::ModifyPermissionsRequest
+        );
+    }
 }
 
 impl GrpcServiceForDiscovery for RawSchemeClient {
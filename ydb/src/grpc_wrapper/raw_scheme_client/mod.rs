@@ -1,3 +1,4 @@
 pub(crate) mod client;
 pub(crate) mod describe_path_types;
 pub(crate) mod list_directory_types;
+pub(crate) mod modify_permissions_types;
@@ -0,0 +1,120 @@
+use crate::grpc_wrapper::raw_ydb_operation::RawOperationParams;
+use crate::{SchemePermissions, SchemePermissionsAction};
+use ydb_grpc::ydb_proto::scheme::modify_permissions_request::Inheritance;
+use ydb_grpc::ydb_proto::scheme::permissions_action::Action;
+
+#[derive(Debug)]
+pub(crate) struct RawModifyPermissionsRequest {
+    pub(crate) operation_params: RawOperationParams,
+    pub(crate) path: String,
+    pub(crate) actions: Vec<SchemePermissionsAction>,
+    pub(crate) clear_permissions: bool,
+    pub(crate) interrupt_inheritance: Option<bool>,
+}
+
+impl From<RawModifyPermissionsRequest> for ydb_grpc::ydb_proto::scheme::ModifyPermissionsRequest {
+    fn from(v: RawModifyPermissionsRequest) -> Self {
+        Self {
+            operation_params: Some(v.operation_params.into()),
+            path: v.path,
+            actions: v.actions.into_iter().map(Into::into).collect(),
+            clear_permissions: v.clear_permissions,
+            inheritance: v
+                .interrupt_inheritance
+                .map(Inheritance::InterruptInheritance),
+        }
+    }
+}
+
+impl From<SchemePermissionsAction> for ydb_grpc::ydb_proto::scheme::PermissionsAction {
+    fn from(v: SchemePermissionsAction) -> Self {
+        let action = match v {
+            SchemePermissionsAction::Grant(permissions) => Action::Grant(permissions.into()),
+            SchemePermissionsAction::Revoke(permissions) => Action::Revoke(permissions.into()),
+            SchemePermissionsAction::Set(permissions) => Action::Set(permissions.into()),
+            SchemePermissionsAction::ChangeOwner(owner) => Action::ChangeOwner(owner),
+        };
+        Self {
+            action: Some(action),
+        }
+    }
+}
+
+impl From<SchemePermissions> for ydb_grpc::ydb_proto::scheme::Permissions {
+    fn from(v: SchemePermissions) -> Self {
+        Self {
+            subject: v.subject,
+            permission_names: v.permission_names,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use ydb_grpc::ydb_proto::scheme::ModifyPermissionsRequest;
+
+    #[test]
+    fn modify_permissions_request_conversion() {
+        let raw = RawModifyPermissionsRequest {
+            operation_params: RawOperationParams::new_with_timeouts(
+                Duration::from_secs(1),
+                Duration::from_secs(2),
+            ),
+            path: "/local/dir".to_string(),
+            actions: vec![
+                SchemePermissionsAction::Grant(SchemePermissions::new(
+                    "user1",
+                    vec!["ydb.generic.read".to_string()],
+                )),
+                SchemePermissionsAction::Revoke(SchemePermissions::new(
+                    "user2",
+                    vec!["ydb.generic.write".to_string()],
+                )),
+                SchemePermissionsAction::ChangeOwner("root".to_string()),
+            ],
+            clear_permissions: true,
+            interrupt_inheritance: Some(true),
+        };
+
+        let proto: ModifyPermissionsRequest = raw.into();
+        assert_eq!(proto.path, "/local/dir");
+        assert!(proto.clear_permissions);
+        assert_eq!(
+            proto.inheritance,
+            Some(Inheritance::InterruptInheritance(true))
+        );
+        assert_eq!(proto.actions.len(), 3);
+        assert!(matches!(
+            &proto.actions[0].action,
+            Some(Action::Grant(p)) if p.subject == "user1" && p.permission_names == ["ydb.generic.read"]
+        ));
+        assert!(matches!(
+            &proto.actions[1].action,
+            Some(Action::Revoke(p)) if p.subject == "user2"
+        ));
+        assert!(matches!(
+            &proto.actions[2].action,
+            Some(Action::ChangeOwner(owner)) if owner == "root"
+        ));
+    }
+
+    #[test]
+    fn modify_permissions_request_keeps_inheritance_unset() {
+        let raw = RawModifyPermissionsRequest {
+            operation_params: RawOperationParams::new_with_timeouts(
+                Duration::from_secs(1),
+                Duration::from_secs(2),
+            ),
+            path: "/local/dir".to_string(),
+            actions: vec![],
+            clear_permissions: false,
+            interrupt_inheritance: None,
+        };
+
+        let proto: ModifyPermissionsRequest = raw.into();
+        assert!(proto.inheritance.is_none());
+        assert!(!proto.clear_permissions);
+    }
+}
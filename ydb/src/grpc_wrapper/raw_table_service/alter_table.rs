@@ -1,4 +1,4 @@
-use crate::grpc_wrapper::raw_table_service::create_table::RawCreateTableColumn;
+use crate::grpc_wrapper::raw_table_service::create_table::{RawCreateTableColumn, RawTableIndex};
 use crate::grpc_wrapper::raw_ydb_operation::RawOperationParams;
 use std::collections::HashMap;
 use ydb_grpc::ydb_proto::table::{AlterTableRequest, ColumnMeta};
@@ -10,6 +10,8 @@ pub(crate) struct RawAlterTableRequest {
     pub drop_columns: Vec<String>,
     pub alter_columns: Vec<RawCreateTableColumn>,
     pub alter_attributes: HashMap<String, String>,
+    pub add_indexes: Vec<RawTableIndex>,
+    pub drop_indexes: Vec<String>,
     pub operation_params: RawOperationParams,
 }
 
@@ -36,6 +38,8 @@ impl From<RawAlterTableRequest> for AlterTableRequest {
                 .map(to_column_meta)
                 .collect(),
             alter_attributes: value.alter_attributes,
+            add_indexes: value.add_indexes.into_iter().map(Into::into).collect(),
+            drop_indexes: value.drop_indexes,
             operation_params: Some(value.operation_params.into()),
             ..Default::default()
         }
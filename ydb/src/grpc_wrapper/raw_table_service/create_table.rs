@@ -1,7 +1,10 @@
 use crate::grpc_wrapper::raw_table_service::value::r#type::RawType;
 use crate::grpc_wrapper::raw_ydb_operation::RawOperationParams;
 use std::collections::HashMap;
-use ydb_grpc::ydb_proto::table::{ColumnMeta, CreateTableRequest};
+use ydb_grpc::ydb_proto::table::table_index::Type;
+use ydb_grpc::ydb_proto::table::{
+    ColumnMeta, CreateTableRequest, GlobalAsyncIndex, GlobalIndex, GlobalUniqueIndex, TableIndex,
+};
 
 pub(crate) struct RawCreateTableColumn {
     pub name: String,
@@ -10,11 +13,43 @@ pub(crate) struct RawCreateTableColumn {
     pub family: String,
 }
 
+pub(crate) struct RawTableIndex {
+    pub name: String,
+    pub index_columns: Vec<String>,
+    pub data_columns: Vec<String>,
+    pub index_type: RawTableIndexType,
+}
+
+pub(crate) enum RawTableIndexType {
+    Global,
+    GlobalAsync,
+    GlobalUnique,
+}
+
+impl From<RawTableIndex> for TableIndex {
+    fn from(value: RawTableIndex) -> Self {
+        let index_type = match value.index_type {
+            RawTableIndexType::Global => Type::GlobalIndex(GlobalIndex::default()),
+            RawTableIndexType::GlobalAsync => Type::GlobalAsyncIndex(GlobalAsyncIndex::default()),
+            RawTableIndexType::GlobalUnique => {
+                Type::GlobalUniqueIndex(GlobalUniqueIndex::default())
+            }
+        };
+        Self {
+            name: value.name,
+            index_columns: value.index_columns,
+            data_columns: value.data_columns,
+            r#type: Some(index_type),
+        }
+    }
+}
+
 pub(crate) struct RawCreateTableRequest {
     pub session_id: String,
     pub path: String,
     pub columns: Vec<RawCreateTableColumn>,
     pub primary_key: Vec<String>,
+    pub indexes: Vec<RawTableIndex>,
     pub attributes: HashMap<String, String>,
     pub operation_params: RawOperationParams,
 }
@@ -36,6 +71,7 @@ impl From<RawCreateTableRequest> for CreateTableRequest {
                 })
                 .collect(),
             primary_key: value.primary_key,
+            indexes: value.indexes.into_iter().map(Into::into).collect(),
             attributes: value.attributes,
             operation_params: Some(value.operation_params.into()),
             ..Default::default()
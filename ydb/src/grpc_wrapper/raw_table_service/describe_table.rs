@@ -2,6 +2,8 @@ use crate::grpc_wrapper::raw_errors::RawError;
 use crate::grpc_wrapper::raw_table_service::value::r#type::RawType;
 use crate::grpc_wrapper::raw_ydb_operation::RawOperationParams;
 use crate::table_service_types::{ColumnDescription, TableDescription, UnknownTypeDescription};
+use std::time::Duration;
+use ydb_grpc::ydb_proto::feature_flag::Status as FeatureFlagStatus;
 
 pub(crate) struct RawDescribeTableRequest {
     pub session_id: String,
@@ -31,6 +33,9 @@ pub(crate) struct RawDescribeTableResult {
     pub indexes: Vec<RawIndexDescription>,
     pub store_type: RawStoreType,
     pub attributes: std::collections::HashMap<String, String>,
+    pub column_families: Vec<RawColumnFamily>,
+    pub ttl_settings: Option<RawTtlSettings>,
+    pub partitioning_settings: Option<RawTablePartitioningSettings>,
 }
 
 impl TryFrom<ydb_grpc::ydb_proto::table::DescribeTableResult> for RawDescribeTableResult {
@@ -57,6 +62,15 @@ impl TryFrom<ydb_grpc::ydb_proto::table::DescribeTableResult> for RawDescribeTab
             indexes,
             store_type: value.store_type.try_into()?,
             attributes: value.attributes,
+            column_families: value
+                .column_families
+                .into_iter()
+                .map(RawColumnFamily::from)
+                .collect(),
+            ttl_settings: value.ttl_settings.and_then(RawTtlSettings::from_proto),
+            partitioning_settings: value
+                .partitioning_settings
+                .map(RawTablePartitioningSettings::from),
         })
     }
 }
@@ -176,6 +190,113 @@ impl TryFrom<i32> for RawStoreType {
     }
 }
 
+#[derive(Debug)]
+pub(crate) struct RawColumnFamily {
+    pub name: String,
+    pub data_media: String,
+    pub compression: RawColumnFamilyCompression,
+}
+
+impl From<ydb_grpc::ydb_proto::table::ColumnFamily> for RawColumnFamily {
+    fn from(value: ydb_grpc::ydb_proto::table::ColumnFamily) -> Self {
+        use ydb_grpc::ydb_proto::table::column_family::Compression;
+        let compression = match Compression::try_from(value.compression) {
+            Ok(Compression::None) => RawColumnFamilyCompression::None,
+            Ok(Compression::Lz4) => RawColumnFamilyCompression::Lz4,
+            Ok(Compression::Unspecified) | Err(_) => RawColumnFamilyCompression::Unspecified,
+        };
+        Self {
+            name: value.name,
+            data_media: value.data.map(|pool| pool.media).unwrap_or_default(),
+            compression,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum RawColumnFamilyCompression {
+    Unspecified,
+    None,
+    Lz4,
+}
+
+#[derive(Debug)]
+pub(crate) struct RawTtlSettings {
+    pub column_name: String,
+    pub expire_after: Duration,
+    // None for date type column mode
+    pub column_unit: Option<RawTtlUnit>,
+}
+
+impl RawTtlSettings {
+    fn from_proto(value: ydb_grpc::ydb_proto::table::TtlSettings) -> Option<Self> {
+        use ydb_grpc::ydb_proto::table::ttl_settings::Mode;
+        use ydb_grpc::ydb_proto::table::value_since_unix_epoch_mode_settings::Unit;
+
+        match value.mode? {
+            Mode::DateTypeColumn(settings) => Some(Self {
+                column_name: settings.column_name,
+                expire_after: Duration::from_secs(settings.expire_after_seconds.into()),
+                column_unit: None,
+            }),
+            Mode::ValueSinceUnixEpoch(settings) => {
+                let unit = match Unit::try_from(settings.column_unit) {
+                    Ok(Unit::Seconds) => RawTtlUnit::Seconds,
+                    Ok(Unit::Milliseconds) => RawTtlUnit::Milliseconds,
+                    Ok(Unit::Microseconds) => RawTtlUnit::Microseconds,
+                    Ok(Unit::Nanoseconds) => RawTtlUnit::Nanoseconds,
+                    Ok(Unit::Unspecified) | Err(_) => RawTtlUnit::Unspecified,
+                };
+                Some(Self {
+                    column_name: settings.column_name,
+                    expire_after: Duration::from_secs(settings.expire_after_seconds.into()),
+                    column_unit: Some(unit),
+                })
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum RawTtlUnit {
+    Unspecified,
+    Seconds,
+    Milliseconds,
+    Microseconds,
+    Nanoseconds,
+}
+
+#[derive(Debug)]
+pub(crate) struct RawTablePartitioningSettings {
+    pub partition_by: Vec<String>,
+    pub partitioning_by_size: Option<bool>,
+    pub partition_size_mb: u64,
+    pub partitioning_by_load: Option<bool>,
+    pub min_partitions_count: u64,
+    pub max_partitions_count: u64,
+}
+
+impl From<ydb_grpc::ydb_proto::table::PartitioningSettings> for RawTablePartitioningSettings {
+    fn from(value: ydb_grpc::ydb_proto::table::PartitioningSettings) -> Self {
+        Self {
+            partition_by: value.partition_by,
+            partitioning_by_size: feature_flag_to_option(value.partitioning_by_size),
+            partition_size_mb: value.partition_size_mb,
+            partitioning_by_load: feature_flag_to_option(value.partitioning_by_load),
+            min_partitions_count: value.min_partitions_count,
+            max_partitions_count: value.max_partitions_count,
+        }
+    }
+}
+
+fn feature_flag_to_option(value: i32) -> Option<bool> {
+    match FeatureFlagStatus::try_from(value) {
+        Ok(FeatureFlagStatus::Enabled) => Some(true),
+        Ok(FeatureFlagStatus::Disabled) => Some(false),
+        Ok(FeatureFlagStatus::Unspecified) | Err(_) => None,
+    }
+}
+
 pub(crate) fn table_description_from_raw(
     raw_result: RawDescribeTableResult,
 ) -> Result<TableDescription, UnknownTypeDescription> {
@@ -184,11 +305,13 @@ pub(crate) fn table_description_from_raw(
         .into_iter()
         .map(|raw_col| ColumnDescription {
             name: raw_col.name,
+            type_name: raw_col.column_type.yql_type_name(),
             type_value: raw_col.column_type.into_value_example().map_err(|e| {
                 UnknownTypeDescription {
                     error: e.to_string(),
                 }
             }),
+            not_null: raw_col.not_null,
             family: raw_col.family,
         })
         .collect();
@@ -205,5 +328,12 @@ pub(crate) fn table_description_from_raw(
         indexes,
         store_type: raw_result.store_type.into(),
         attributes: raw_result.attributes,
+        column_families: raw_result
+            .column_families
+            .into_iter()
+            .map(Into::into)
+            .collect(),
+        ttl_settings: raw_result.ttl_settings.map(Into::into),
+        partitioning_settings: raw_result.partitioning_settings.map(Into::into),
     })
 }
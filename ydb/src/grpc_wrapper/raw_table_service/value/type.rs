@@ -212,6 +212,76 @@ impl RawType {
         };
         Ok(res)
     }
+
+    /// Type name in YQL syntax, for example `Optional<Decimal(22,9)>`.
+    pub fn yql_type_name(&self) -> String {
+        fn join(types: &[RawType]) -> String {
+            types
+                .iter()
+                .map(RawType::yql_type_name)
+                .collect::<Vec<_>>()
+                .join(",")
+        }
+
+        fn members(members: &[StructMember]) -> String {
+            members
+                .iter()
+                .map(|m| format!("'{}':{}", m.name, m.member_type.yql_type_name()))
+                .collect::<Vec<_>>()
+                .join(",")
+        }
+
+        match self {
+            RawType::Bool => "Bool".to_string(),
+            RawType::Int8 => "Int8".to_string(),
+            RawType::Uint8 => "Uint8".to_string(),
+            RawType::Int16 => "Int16".to_string(),
+            RawType::Uint16 => "Uint16".to_string(),
+            RawType::Int32 => "Int32".to_string(),
+            RawType::Uint32 => "Uint32".to_string(),
+            RawType::Int64 => "Int64".to_string(),
+            RawType::Uint64 => "Uint64".to_string(),
+            RawType::Float => "Float".to_string(),
+            RawType::Double => "Double".to_string(),
+            RawType::Date => "Date".to_string(),
+            RawType::DateTime => "Datetime".to_string(),
+            RawType::Timestamp => "Timestamp".to_string(),
+            RawType::Interval => "Interval".to_string(),
+            RawType::Date32 => "Date32".to_string(),
+            RawType::Datetime64 => "Datetime64".to_string(),
+            RawType::Timestamp64 => "Timestamp64".to_string(),
+            RawType::Interval64 => "Interval64".to_string(),
+            RawType::TzDate => "TzDate".to_string(),
+            RawType::TzDatetime => "TzDatetime".to_string(),
+            RawType::TzTimestamp => "TzTimestamp".to_string(),
+            RawType::Bytes => "String".to_string(),
+            RawType::UTF8 => "Utf8".to_string(),
+            RawType::Yson => "Yson".to_string(),
+            RawType::Json => "Json".to_string(),
+            RawType::Uuid => "Uuid".to_string(),
+            RawType::JSONDocument => "JsonDocument".to_string(),
+            RawType::DyNumber => "DyNumber".to_string(),
+            RawType::Decimal(t) => format!("Decimal({},{})", t.precision, t.scale),
+            RawType::Optional(t) => format!("Optional<{}>", t.yql_type_name()),
+            RawType::List(t) => format!("List<{}>", t.yql_type_name()),
+            RawType::Tuple(t) => format!("Tuple<{}>", join(&t.elements)),
+            RawType::Struct(t) => format!("Struct<{}>", members(&t.members)),
+            RawType::Dict(t) => format!(
+                "Dict<{},{}>",
+                t.key.yql_type_name(),
+                t.payload.yql_type_name()
+            ),
+            RawType::Variant(VariantType::Tuple(t)) => format!("Variant<{}>", join(&t.elements)),
+            RawType::Variant(VariantType::Struct(t)) => {
+                format!("Variant<{}>", members(&t.members))
+            }
+            RawType::Tagged(t) => format!("Tagged<{},'{}'>", t.item_type.yql_type_name(), t.tag),
+            RawType::Void => "Void".to_string(),
+            RawType::Null => "Null".to_string(),
+            RawType::EmptyList => "EmptyList".to_string(),
+            RawType::EmptyDict => "EmptyDict".to_string(),
+        }
+    }
 }
 
 //
@@ -96,3 +96,54 @@ fn consistent_conversion() -> RawResult<()> {
 
     Ok(())
 }
+
+#[test]
+fn yql_type_name() {
+    use RawType::*;
+
+    let cases = vec![
+        (Int64, "Int64"),
+        (Bytes, "String"),
+        (UTF8, "Utf8"),
+        (DateTime, "Datetime"),
+        (JSONDocument, "JsonDocument"),
+        (
+            Decimal(DecimalType {
+                precision: 22,
+                scale: 9,
+            }),
+            "Decimal(22,9)",
+        ),
+        (Optional(Box::new(Uint64)), "Optional<Uint64>"),
+        (
+            List(Box::new(Optional(Box::new(Bool)))),
+            "List<Optional<Bool>>",
+        ),
+        (
+            Tuple(TupleType {
+                elements: vec![Int32, Double],
+            }),
+            "Tuple<Int32,Double>",
+        ),
+        (
+            Struct(StructType {
+                members: vec![StructMember {
+                    name: "a".to_string(),
+                    member_type: Uuid,
+                }],
+            }),
+            "Struct<'a':Uuid>",
+        ),
+        (
+            Dict(Box::new(DictType {
+                key: UTF8,
+                payload: Bytes,
+            })),
+            "Dict<Utf8,String>",
+        ),
+    ];
+
+    for (t, expected) in cases {
+        assert_eq!(t.yql_type_name(), expected);
+    }
+}
@@ -3,7 +3,9 @@ use std::collections::HashMap;
 use super::common::codecs::RawSupportedCodecs;
 use super::common::consumer::RawConsumer;
 use super::common::metering_mode::RawMeteringMode;
-use super::common::partitioning_settings::RawAlterPartitioningSettings;
+use super::common::partitioning_settings::{
+    RawAlterAutoPartitioningSettings, RawAlterPartitioningSettings,
+};
 use crate::client_topic::client::AlterTopicOptions;
 use crate::grpc_wrapper::raw_common_types::Duration;
 use crate::grpc_wrapper::raw_topic_service::common::consumer::RawAlterConsumer;
@@ -33,12 +35,27 @@ impl RawAlterTopicRequest {
         operation_params: RawOperationParams,
         options: AlterTopicOptions,
     ) -> Self {
+        let alter_auto_partitioning_settings = RawAlterAutoPartitioningSettings {
+            set_strategy: options
+                .set_auto_partitioning_strategy
+                .map(|x| Some(x).into()),
+            set_stabilization_window: options
+                .set_auto_partitioning_stabilization_window
+                .map(Into::into),
+            set_up_utilization_percent: options.set_auto_partitioning_up_utilization_percent,
+            set_down_utilization_percent: options.set_auto_partitioning_down_utilization_percent,
+        };
+        let alter_auto_partitioning_settings = (!alter_auto_partitioning_settings.is_empty())
+            .then_some(alter_auto_partitioning_settings);
+
         let alter_partitioning_settings = if options.set_min_active_partitions.is_some()
             || options.set_partition_count_limit.is_some()
+            || alter_auto_partitioning_settings.is_some()
         {
             Some(RawAlterPartitioningSettings {
                 set_min_active_partitions: options.set_min_active_partitions,
                 set_partition_count_limit: options.set_partition_count_limit,
+                alter_auto_partitioning_settings,
             })
         } else {
             None
@@ -1,13 +1,16 @@
 use tracing::{instrument, trace};
 
 use ydb_grpc::ydb_proto::topic::v1::topic_service_client::TopicServiceClient;
-use ydb_grpc::ydb_proto::topic::{stream_read_message, stream_write_message};
+use ydb_grpc::ydb_proto::topic::{
+    stream_direct_read_message, stream_read_message, stream_write_message,
+};
 
 use crate::grpc_wrapper::grpc_limits::WithGrpcMaxMessageSize;
 use crate::grpc_wrapper::grpc_stream_wrapper::AsyncGrpcStreamWrapper;
 use crate::grpc_wrapper::raw_errors::RawResult;
 use crate::grpc_wrapper::raw_services::{GrpcServiceForDiscovery, Service};
 use crate::grpc_wrapper::raw_topic_service::alter_topic::RawAlterTopicRequest;
+use crate::grpc_wrapper::raw_topic_service::commit_offset::RawCommitOffsetRequest;
 use crate::grpc_wrapper::raw_topic_service::create_topic::RawCreateTopicRequest;
 use crate::grpc_wrapper::raw_topic_service::describe_consumer::{
     RawDescribeConsumerRequest, RawDescribeConsumerResult,
@@ -16,12 +19,16 @@ use crate::grpc_wrapper::raw_topic_service::describe_topic::{
     RawDescribeTopicRequest, RawDescribeTopicResult,
 };
 use crate::grpc_wrapper::raw_topic_service::drop_topic::RawDropTopicRequest;
+use crate::grpc_wrapper::raw_topic_service::stream_direct_read;
 use crate::grpc_wrapper::raw_topic_service::stream_read;
 use crate::grpc_wrapper::raw_topic_service::update_offsets_in_transaction::RawUpdateOffsetsInTransactionRequest;
 use crate::grpc_wrapper::runtime_interceptors::InterceptedChannel;
 
 pub(crate) struct RawTopicClient {
     service: TopicServiceClient<InterceptedChannel>,
+    // generated client has no StreamDirectRead method, it is called through the raw channel
+    channel: InterceptedChannel,
+    max_message_size: Option<usize>,
 }
 
 impl WithGrpcMaxMessageSize for RawTopicClient {
@@ -30,6 +37,7 @@ impl WithGrpcMaxMessageSize for RawTopicClient {
             .service
             .max_decoding_message_size(bytes)
             .max_encoding_message_size(bytes);
+        self.max_message_size = Some(bytes);
         self
     }
 }
@@ -37,7 +45,9 @@ impl WithGrpcMaxMessageSize for RawTopicClient {
 impl RawTopicClient {
     pub fn new(service: InterceptedChannel) -> Self {
         Self {
-            service: TopicServiceClient::new(service),
+            service: TopicServiceClient::new(service.clone()),
+            channel: service,
+            max_message_size: None,
         }
     }
 
@@ -89,6 +99,14 @@ impl RawTopicClient {
         );
     }
 
+    #[instrument(name = "ydb.grpc.CommitOffset", skip_all, fields(ydb.topic.path = %req.path, ydb.consumer.name = %req.consumer), err)]
+    pub async fn commit_offset(&mut self, req: RawCommitOffsetRequest) -> RawResult<()> {
+        request_without_result!(
+            self.service.commit_offset,
+            req => ydb_grpc::ydb_proto::topic::CommitOffsetRequest
+        );
+    }
+
     #[instrument(name = "ydb.grpc.UpdateOffsetsInTransaction", skip_all, fields(ydb.consumer.name = %req.consumer), err)]
     pub async fn update_offsets_in_transaction(
         &mut self,
@@ -129,6 +147,52 @@ impl RawTopicClient {
         >::new(tx, response_stream))
     }
 
+    #[instrument(name = "ydb.grpc.StreamDirectRead", skip_all, fields(ydb.consumer.name = %init_req_body.consumer), err)]
+    pub async fn stream_direct_read(
+        &mut self,
+        init_req_body: stream_direct_read::messages::RawDirectInitRequest,
+    ) -> RawResult<
+        AsyncGrpcStreamWrapper<
+            stream_direct_read_message::FromClient,
+            stream_direct_read_message::FromServer,
+        >,
+    > {
+        let (tx, rx): (
+            tokio::sync::mpsc::UnboundedSender<stream_direct_read_message::FromClient>,
+            tokio::sync::mpsc::UnboundedReceiver<stream_direct_read_message::FromClient>,
+        ) = tokio::sync::mpsc::unbounded_channel();
+
+        tx.send(
+            stream_direct_read::messages::RawDirectFromClientOneOf::InitRequest(init_req_body)
+                .into(),
+        )?;
+
+        let mut grpc = tonic::client::Grpc::new(self.channel.clone());
+        if let Some(bytes) = self.max_message_size {
+            grpc = grpc
+                .max_decoding_message_size(bytes)
+                .max_encoding_message_size(bytes);
+        }
+        grpc.ready()
+            .await
+            .map_err(|err| tonic::Status::unknown(format!("Service was not ready: {err}")))?;
+
+        let path =
+            http::uri::PathAndQuery::from_static("/Ydb.Topic.V1.TopicService/StreamDirectRead");
+        let mut request =
+            tonic::Request::new(tokio_stream::wrappers::UnboundedReceiverStream::new(rx));
+        request.extensions_mut().insert(tonic::GrpcMethod::new(
+            "Ydb.Topic.V1.TopicService",
+            "StreamDirectRead",
+        ));
+        let response_stream = grpc
+            .streaming(request, path, tonic_prost::ProstCodec::default())
+            .await?
+            .into_inner();
+
+        Ok(AsyncGrpcStreamWrapper::new(tx, response_stream))
+    }
+
     #[instrument(name = "ydb.grpc.StreamWrite", skip_all, fields(ydb.topic.path = %init_req_body.path), err)]
     pub async fn stream_write(
         &mut self,
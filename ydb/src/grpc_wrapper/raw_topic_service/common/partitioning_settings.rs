@@ -1,9 +1,15 @@
-use ydb_grpc::ydb_proto::topic::{AlterPartitioningSettings, PartitioningSettings};
+use crate::grpc_wrapper::raw_common_types::Duration;
+use ydb_grpc::ydb_proto::topic::{
+    AlterAutoPartitioningSettings, AlterAutoPartitioningWriteSpeedStrategy,
+    AlterPartitioningSettings, AutoPartitioningSettings, AutoPartitioningStrategy,
+    AutoPartitioningWriteSpeedStrategy, PartitioningSettings,
+};
 
 #[derive(Debug, serde::Serialize)]
 pub(crate) struct RawPartitioningSettings {
     pub min_active_partitions: i64,
     pub partition_count_limit: i64,
+    pub auto_partitioning_settings: Option<RawAutoPartitioningSettings>,
 }
 
 impl From<PartitioningSettings> for RawPartitioningSettings {
@@ -11,6 +17,7 @@ impl From<PartitioningSettings> for RawPartitioningSettings {
         Self {
             min_active_partitions: value.min_active_partitions,
             partition_count_limit: value.max_active_partitions,
+            auto_partitioning_settings: value.auto_partitioning_settings.map(Into::into),
         }
     }
 }
@@ -22,7 +29,74 @@ impl From<RawPartitioningSettings> for PartitioningSettings {
             min_active_partitions: value.min_active_partitions,
             partition_count_limit: value.partition_count_limit,
             max_active_partitions: value.partition_count_limit,
-            auto_partitioning_settings: None,
+            auto_partitioning_settings: value.auto_partitioning_settings.map(Into::into),
+        }
+    }
+}
+
+#[derive(Debug, serde::Serialize, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum RawAutoPartitioningStrategy {
+    #[default]
+    Unspecified,
+    Disabled,
+    ScaleUp,
+    ScaleUpAndDown,
+    Paused,
+}
+
+impl From<i32> for RawAutoPartitioningStrategy {
+    fn from(value: i32) -> Self {
+        match AutoPartitioningStrategy::try_from(value) {
+            Ok(AutoPartitioningStrategy::Disabled) => Self::Disabled,
+            Ok(AutoPartitioningStrategy::ScaleUp) => Self::ScaleUp,
+            Ok(AutoPartitioningStrategy::ScaleUpAndDown) => Self::ScaleUpAndDown,
+            Ok(AutoPartitioningStrategy::Paused) => Self::Paused,
+            Ok(AutoPartitioningStrategy::Unspecified) | Err(_) => Self::Unspecified,
+        }
+    }
+}
+
+impl From<RawAutoPartitioningStrategy> for AutoPartitioningStrategy {
+    fn from(value: RawAutoPartitioningStrategy) -> Self {
+        match value {
+            RawAutoPartitioningStrategy::Unspecified => AutoPartitioningStrategy::Unspecified,
+            RawAutoPartitioningStrategy::Disabled => AutoPartitioningStrategy::Disabled,
+            RawAutoPartitioningStrategy::ScaleUp => AutoPartitioningStrategy::ScaleUp,
+            RawAutoPartitioningStrategy::ScaleUpAndDown => AutoPartitioningStrategy::ScaleUpAndDown,
+            RawAutoPartitioningStrategy::Paused => AutoPartitioningStrategy::Paused,
+        }
+    }
+}
+
+#[derive(Debug, serde::Serialize)]
+pub(crate) struct RawAutoPartitioningSettings {
+    pub strategy: RawAutoPartitioningStrategy,
+    pub stabilization_window: Option<Duration>,
+    pub up_utilization_percent: i32,
+    pub down_utilization_percent: i32,
+}
+
+impl From<AutoPartitioningSettings> for RawAutoPartitioningSettings {
+    fn from(value: AutoPartitioningSettings) -> Self {
+        let write_speed = value.partition_write_speed.unwrap_or_default();
+        Self {
+            strategy: value.strategy.into(),
+            stabilization_window: write_speed.stabilization_window.map(Into::into),
+            up_utilization_percent: write_speed.up_utilization_percent,
+            down_utilization_percent: write_speed.down_utilization_percent,
+        }
+    }
+}
+
+impl From<RawAutoPartitioningSettings> for AutoPartitioningSettings {
+    fn from(value: RawAutoPartitioningSettings) -> Self {
+        Self {
+            strategy: AutoPartitioningStrategy::from(value.strategy) as i32,
+            partition_write_speed: Some(AutoPartitioningWriteSpeedStrategy {
+                stabilization_window: value.stabilization_window.map(Into::into),
+                up_utilization_percent: value.up_utilization_percent,
+                down_utilization_percent: value.down_utilization_percent,
+            }),
         }
     }
 }
@@ -31,6 +105,7 @@ impl From<RawPartitioningSettings> for PartitioningSettings {
 pub(crate) struct RawAlterPartitioningSettings {
     pub set_min_active_partitions: Option<i64>,
     pub set_partition_count_limit: Option<i64>,
+    pub alter_auto_partitioning_settings: Option<RawAlterAutoPartitioningSettings>,
 }
 
 impl From<AlterPartitioningSettings> for RawAlterPartitioningSettings {
@@ -38,6 +113,9 @@ impl From<AlterPartitioningSettings> for RawAlterPartitioningSettings {
         Self {
             set_min_active_partitions: value.set_min_active_partitions,
             set_partition_count_limit: value.set_max_active_partitions,
+            alter_auto_partitioning_settings: value
+                .alter_auto_partitioning_settings
+                .map(Into::into),
         }
     }
 }
@@ -49,7 +127,62 @@ impl From<RawAlterPartitioningSettings> for AlterPartitioningSettings {
             set_min_active_partitions: value.set_min_active_partitions,
             set_partition_count_limit: value.set_partition_count_limit,
             set_max_active_partitions: value.set_partition_count_limit,
-            alter_auto_partitioning_settings: None,
+            alter_auto_partitioning_settings: value
+                .alter_auto_partitioning_settings
+                .map(Into::into),
+        }
+    }
+}
+
+#[derive(Debug, Default, serde::Serialize)]
+pub(crate) struct RawAlterAutoPartitioningSettings {
+    pub set_strategy: Option<RawAutoPartitioningStrategy>,
+    pub set_stabilization_window: Option<Duration>,
+    pub set_up_utilization_percent: Option<i32>,
+    pub set_down_utilization_percent: Option<i32>,
+}
+
+impl RawAlterAutoPartitioningSettings {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.set_strategy.is_none()
+            && self.set_stabilization_window.is_none()
+            && self.set_up_utilization_percent.is_none()
+            && self.set_down_utilization_percent.is_none()
+    }
+}
+
+impl From<AlterAutoPartitioningSettings> for RawAlterAutoPartitioningSettings {
+    fn from(value: AlterAutoPartitioningSettings) -> Self {
+        let write_speed = value.set_partition_write_speed.unwrap_or_default();
+        Self {
+            set_strategy: value.set_strategy.map(Into::into),
+            set_stabilization_window: write_speed.set_stabilization_window.map(Into::into),
+            set_up_utilization_percent: write_speed.set_up_utilization_percent,
+            set_down_utilization_percent: write_speed.set_down_utilization_percent,
+        }
+    }
+}
+
+impl From<RawAlterAutoPartitioningSettings> for AlterAutoPartitioningSettings {
+    fn from(value: RawAlterAutoPartitioningSettings) -> Self {
+        let set_partition_write_speed = if value.set_stabilization_window.is_some()
+            || value.set_up_utilization_percent.is_some()
+            || value.set_down_utilization_percent.is_some()
+        {
+            Some(AlterAutoPartitioningWriteSpeedStrategy {
+                set_stabilization_window: value.set_stabilization_window.map(Into::into),
+                set_up_utilization_percent: value.set_up_utilization_percent,
+                set_down_utilization_percent: value.set_down_utilization_percent,
+            })
+        } else {
+            None
+        };
+
+        Self {
+            set_strategy: value
+                .set_strategy
+                .map(|x| AutoPartitioningStrategy::from(x) as i32),
+            set_partition_write_speed,
         }
     }
 }
@@ -39,6 +39,7 @@ impl RawCreateTopicRequest {
             partitioning_settings: RawPartitioningSettings {
                 min_active_partitions: options.min_active_partitions,
                 partition_count_limit: options.partition_count_limit,
+                auto_partitioning_settings: options.auto_partitioning_settings.map(Into::into),
             },
             retention_period: options.retention_period.map(|x| x.into()),
             retention_storage_mb: options.retention_storage_mb,
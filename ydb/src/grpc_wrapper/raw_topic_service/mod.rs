@@ -1,10 +1,12 @@
 pub(crate) mod alter_topic;
 pub(crate) mod client;
+pub(crate) mod commit_offset;
 pub(crate) mod common;
 pub(crate) mod create_topic;
 pub(crate) mod describe_consumer;
 pub(crate) mod describe_topic;
 pub(crate) mod drop_topic;
+pub(crate) mod stream_direct_read;
 pub(crate) mod stream_read;
 pub(crate) mod stream_write;
 pub(crate) mod update_offsets_in_transaction;
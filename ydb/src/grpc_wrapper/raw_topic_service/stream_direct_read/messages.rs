@@ -0,0 +1,176 @@
+use crate::YdbStatusError;
+use crate::grpc_wrapper::grpc::proto_issues_to_ydb_issues;
+use crate::grpc_wrapper::raw_errors::RawError;
+use crate::grpc_wrapper::raw_topic_service::common::update_token::RawUpdateTokenRequest;
+use crate::grpc_wrapper::raw_topic_service::stream_read::messages::RawReadResponse;
+use ydb_grpc::ydb_proto::status_ids::StatusCode;
+use ydb_grpc::ydb_proto::topic::{stream_direct_read_message, stream_read_message};
+
+pub(crate) enum RawDirectFromClientOneOf {
+    InitRequest(RawDirectInitRequest),
+    StartDirectReadPartitionSession(RawStartDirectReadPartitionSessionRequest),
+    UpdateTokenRequest(RawUpdateTokenRequest),
+}
+
+impl From<RawDirectFromClientOneOf> for stream_direct_read_message::FromClient {
+    fn from(value: RawDirectFromClientOneOf) -> Self {
+        use stream_direct_read_message::from_client::ClientMessage;
+
+        let message = match value {
+            RawDirectFromClientOneOf::InitRequest(init_request) => {
+                ClientMessage::InitRequest(init_request.into())
+            }
+            RawDirectFromClientOneOf::StartDirectReadPartitionSession(request) => {
+                ClientMessage::StartDirectReadPartitionSessionRequest(request.into())
+            }
+            RawDirectFromClientOneOf::UpdateTokenRequest(update_token_request) => {
+                ClientMessage::UpdateTokenRequest(update_token_request.into())
+            }
+        };
+
+        Self {
+            client_message: Some(message),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum RawDirectFromServer {
+    InitResponse,
+    StartDirectReadPartitionSessionResponse(RawStartDirectReadPartitionSessionResponse),
+    StopDirectReadPartitionSession(RawStopDirectReadPartitionSession),
+    DirectReadResponse(RawDirectReadResponse),
+    UpdateTokenResponse,
+}
+
+impl TryFrom<stream_direct_read_message::FromServer> for RawDirectFromServer {
+    type Error = RawError;
+
+    fn try_from(value: stream_direct_read_message::FromServer) -> Result<Self, Self::Error> {
+        use stream_direct_read_message::from_server::ServerMessage;
+
+        if value.status != StatusCode::Success as i32 {
+            return Err(RawError::YdbStatus(YdbStatusError {
+                message: "".to_string(),
+                operation_status: value.status,
+                issues: proto_issues_to_ydb_issues(value.issues),
+            }));
+        }
+
+        let message = value.server_message.ok_or(RawError::Custom(
+            "Server message is absent in streaming response body for topic direct read stream"
+                .to_string(),
+        ))?;
+
+        Ok(match message {
+            ServerMessage::InitResponse(_) => RawDirectFromServer::InitResponse,
+            ServerMessage::StartDirectReadPartitionSessionResponse(response) => {
+                RawDirectFromServer::StartDirectReadPartitionSessionResponse(response.into())
+            }
+            ServerMessage::StopDirectReadPartitionSession(request) => {
+                RawDirectFromServer::StopDirectReadPartitionSession(request.into())
+            }
+            ServerMessage::DirectReadResponse(response) => {
+                RawDirectFromServer::DirectReadResponse(response.into())
+            }
+            ServerMessage::UpdateTokenResponse(_) => RawDirectFromServer::UpdateTokenResponse,
+        })
+    }
+}
+
+pub(crate) struct RawDirectInitRequest {
+    pub session_id: String,
+    pub topics: Vec<String>,
+    pub consumer: String,
+}
+
+impl From<RawDirectInitRequest> for stream_direct_read_message::InitRequest {
+    fn from(value: RawDirectInitRequest) -> Self {
+        Self {
+            session_id: value.session_id,
+            topics_read_settings: value
+                .topics
+                .into_iter()
+                .map(|path| stream_direct_read_message::init_request::TopicReadSettings { path })
+                .collect(),
+            consumer: value.consumer,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct RawStartDirectReadPartitionSessionRequest {
+    pub partition_session_id: i64,
+    pub last_direct_read_id: i64,
+    pub generation: i64,
+}
+
+impl From<RawStartDirectReadPartitionSessionRequest>
+    for stream_direct_read_message::StartDirectReadPartitionSessionRequest
+{
+    fn from(value: RawStartDirectReadPartitionSessionRequest) -> Self {
+        Self {
+            partition_session_id: value.partition_session_id,
+            last_direct_read_id: value.last_direct_read_id,
+            generation: value.generation,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct RawStartDirectReadPartitionSessionResponse {
+    pub partition_session_id: i64,
+    pub generation: i64,
+}
+
+impl From<stream_direct_read_message::StartDirectReadPartitionSessionResponse>
+    for RawStartDirectReadPartitionSessionResponse
+{
+    fn from(value: stream_direct_read_message::StartDirectReadPartitionSessionResponse) -> Self {
+        Self {
+            partition_session_id: value.partition_session_id,
+            generation: value.generation,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct RawStopDirectReadPartitionSession {
+    pub status: i32,
+    pub partition_session_id: i64,
+    pub generation: i64,
+}
+
+impl From<stream_direct_read_message::StopDirectReadPartitionSession>
+    for RawStopDirectReadPartitionSession
+{
+    fn from(value: stream_direct_read_message::StopDirectReadPartitionSession) -> Self {
+        Self {
+            status: value.status,
+            partition_session_id: value.partition_session_id,
+            generation: value.generation,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct RawDirectReadResponse {
+    pub partition_session_id: i64,
+    pub direct_read_id: i64,
+    pub read_response: RawReadResponse,
+}
+
+impl From<stream_direct_read_message::DirectReadResponse> for RawDirectReadResponse {
+    fn from(value: stream_direct_read_message::DirectReadResponse) -> Self {
+        let read_response = stream_read_message::ReadResponse {
+            partition_data: value.partition_data.into_iter().collect(),
+            bytes_size: value.bytes_size,
+        };
+
+        Self {
+            partition_session_id: value.partition_session_id,
+            direct_read_id: value.direct_read_id,
+            read_response: read_response.into(),
+        }
+    }
+}
@@ -7,13 +7,14 @@ use crate::grpc_wrapper::raw_topic_service::common::partition::RawOffsetsRange;
 use crate::grpc_wrapper::raw_topic_service::common::update_token::{
     RawUpdateTokenRequest, RawUpdateTokenResponse,
 };
+use crate::grpc_wrapper::raw_topic_service::stream_direct_read::messages::RawDirectReadResponse;
 use std::collections::{HashMap, VecDeque};
 use std::time::UNIX_EPOCH;
 use tracing::warn;
 use ydb_grpc::ydb_proto::status_ids::StatusCode;
-use ydb_grpc::ydb_proto::topic::stream_read_message;
 use ydb_grpc::ydb_proto::topic::stream_read_message::FromServer;
 use ydb_grpc::ydb_proto::topic::stream_read_message::from_client::ClientMessage;
+use ydb_grpc::ydb_proto::topic::{PartitionLocation, stream_read_message};
 
 pub(crate) enum RawFromClientOneOf {
     InitRequest(RawInitRequest),
@@ -21,6 +22,7 @@ pub(crate) enum RawFromClientOneOf {
     CommitOffsetRequest(RawCommitOffsetRequest),
     StartPartitionSessionResponse(RawStartPartitionSessionResponse),
     StopPartitionSessionResponse(RawStopPartitionSessionResponse),
+    DirectReadAck(RawDirectReadAck),
     UpdateTokenRequest(RawUpdateTokenRequest),
 }
 
@@ -52,6 +54,9 @@ impl From<RawFromClientOneOf> for stream_read_message::from_client::ClientMessag
             RawFromClientOneOf::StopPartitionSessionResponse(stop_partition_session_response) => {
                 ClientMessage::StopPartitionSessionResponse(stop_partition_session_response.into())
             }
+            RawFromClientOneOf::DirectReadAck(direct_read_ack) => {
+                ClientMessage::DirectReadAck(direct_read_ack.into())
+            }
             RawFromClientOneOf::UpdateTokenRequest(update_token_request) => {
                 ClientMessage::UpdateTokenRequest(update_token_request.into())
             }
@@ -66,7 +71,11 @@ pub(crate) enum RawFromServer {
     CommitOffsetResponse(RawCommitOffsetResponse),
     StartPartitionSessionRequest(RawStartPartitionSessionRequest),
     StopPartitionSessionRequest(RawStopPartitionSessionRequest),
+    UpdatePartitionSession(RawUpdatePartitionSession),
+    EndPartitionSession(RawEndPartitionSession),
     UpdateTokenResponse(RawUpdateTokenResponse),
+    // Not a part of the read stream, forwarded from the direct read streams.
+    DirectReadResponse(RawDirectReadResponse),
     UnsupportedMessage(String),
 }
 
@@ -105,6 +114,12 @@ impl TryFrom<stream_read_message::FromServer> for RawFromServer {
             stream_read_message::from_server::ServerMessage::StopPartitionSessionRequest(
                 stop_partition_session_request,
             ) => RawFromServer::StopPartitionSessionRequest(stop_partition_session_request.into()),
+            stream_read_message::from_server::ServerMessage::UpdatePartitionSession(
+                update_partition_session,
+            ) => RawFromServer::UpdatePartitionSession(update_partition_session.into()),
+            stream_read_message::from_server::ServerMessage::EndPartitionSession(
+                end_partition_session,
+            ) => RawFromServer::EndPartitionSession(end_partition_session.into()),
             stream_read_message::from_server::ServerMessage::UpdateTokenResponse(
                 update_token_response,
             ) => RawFromServer::UpdateTokenResponse(update_token_response.into()),
@@ -125,6 +140,7 @@ pub(crate) struct RawInitRequest {
     pub topics_read_settings: Vec<RawTopicReadSettings>,
     pub consumer: String,
     pub reader_name: String,
+    pub direct_read: bool,
 }
 
 impl From<RawInitRequest> for stream_read_message::InitRequest {
@@ -137,8 +153,8 @@ impl From<RawInitRequest> for stream_read_message::InitRequest {
                 .collect(),
             consumer: value.consumer,
             reader_name: value.reader_name,
-            direct_read: false,
-            auto_partitioning_support: false,
+            direct_read: value.direct_read,
+            auto_partitioning_support: true,
             ..Default::default()
         }
     }
@@ -164,7 +180,7 @@ impl From<RawTopicReadSettings> for stream_read_message::init_request::TopicRead
 
 #[derive(Debug)]
 pub(crate) struct RawInitResponse {
-    session_id: String,
+    pub session_id: String,
 }
 
 impl From<stream_read_message::InitResponse> for RawInitResponse {
@@ -323,6 +339,7 @@ pub(crate) struct RawMessageData {
     pub created_at: Option<Timestamp>,
     pub uncompressed_size: i64,
     pub data: Vec<u8>,
+    pub metadata_items: Vec<(String, Vec<u8>)>,
 
     pub read_session_size_bytes: i64,
 }
@@ -335,6 +352,11 @@ impl From<stream_read_message::read_response::MessageData> for RawMessageData {
             created_at: value.created_at.map(|x| x.into()),
             uncompressed_size: value.uncompressed_size,
             data: value.data.into_iter().collect(),
+            metadata_items: value
+                .metadata_items
+                .into_iter()
+                .map(|item| (item.key, item.value))
+                .collect(),
             read_session_size_bytes: 0,
         }
     }
@@ -372,6 +394,8 @@ impl From<PartitionCommitOffset>
 pub(crate) struct RawStartPartitionSessionRequest {
     pub partition_session: RawPartitionSession,
     pub committed_offset: i64,
+    pub partition_offsets: RawOffsetsRange,
+    pub partition_location: Option<RawPartitionLocation>,
 }
 
 impl From<stream_read_message::StartPartitionSessionRequest> for RawStartPartitionSessionRequest {
@@ -386,6 +410,10 @@ impl From<stream_read_message::StartPartitionSessionRequest> for RawStartPartiti
                 |x| x.into(),
             ),
             committed_offset: value.committed_offset,
+            partition_offsets: value
+                .partition_offsets
+                .map_or(RawOffsetsRange { start: 0, end: 0 }, Into::into),
+            partition_location: value.partition_location.map(Into::into),
         }
     }
 }
@@ -409,23 +437,26 @@ impl From<stream_read_message::PartitionSession> for RawPartitionSession {
 
 pub(crate) struct RawStartPartitionSessionResponse {
     pub partition_session_id: i64,
+    pub read_offset: Option<i64>,
+    pub commit_offset: Option<i64>,
 }
 
 impl From<RawStartPartitionSessionResponse> for stream_read_message::StartPartitionSessionResponse {
     fn from(value: RawStartPartitionSessionResponse) -> Self {
         stream_read_message::StartPartitionSessionResponse {
             partition_session_id: value.partition_session_id,
-            read_offset: None,
-            commit_offset: None,
+            read_offset: value.read_offset,
+            commit_offset: value.commit_offset,
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct RawStopPartitionSessionRequest {
     pub partition_session_id: i64,
     pub graceful: bool,
     pub committed_offset: i64,
+    pub last_direct_read_id: i64,
 }
 
 impl From<stream_read_message::StopPartitionSessionRequest> for RawStopPartitionSessionRequest {
@@ -434,19 +465,82 @@ impl From<stream_read_message::StopPartitionSessionRequest> for RawStopPartition
             partition_session_id: value.partition_session_id,
             graceful: value.graceful,
             committed_offset: value.committed_offset,
+            last_direct_read_id: value.last_direct_read_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RawPartitionLocation {
+    pub node_id: i32,
+    pub generation: i64,
+}
+
+impl From<PartitionLocation> for RawPartitionLocation {
+    fn from(value: PartitionLocation) -> Self {
+        RawPartitionLocation {
+            node_id: value.node_id,
+            generation: value.generation,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct RawUpdatePartitionSession {
+    pub partition_session_id: i64,
+    pub partition_location: Option<RawPartitionLocation>,
+}
+
+impl From<stream_read_message::UpdatePartitionSession> for RawUpdatePartitionSession {
+    fn from(value: stream_read_message::UpdatePartitionSession) -> Self {
+        RawUpdatePartitionSession {
+            partition_session_id: value.partition_session_id,
+            partition_location: value.partition_location.map(Into::into),
+        }
+    }
+}
+
+pub(crate) struct RawDirectReadAck {
+    pub partition_session_id: i64,
+    pub direct_read_id: i64,
+}
+
+impl From<RawDirectReadAck> for stream_read_message::DirectReadAck {
+    fn from(value: RawDirectReadAck) -> Self {
+        stream_read_message::DirectReadAck {
+            partition_session_id: value.partition_session_id,
+            direct_read_id: value.direct_read_id,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct RawEndPartitionSession {
+    pub partition_session_id: i64,
+    pub adjacent_partition_ids: Vec<i64>,
+    pub child_partition_ids: Vec<i64>,
+}
+
+impl From<stream_read_message::EndPartitionSession> for RawEndPartitionSession {
+    fn from(value: stream_read_message::EndPartitionSession) -> Self {
+        RawEndPartitionSession {
+            partition_session_id: value.partition_session_id,
+            adjacent_partition_ids: value.adjacent_partition_ids,
+            child_partition_ids: value.child_partition_ids,
         }
     }
 }
 
 pub(crate) struct RawStopPartitionSessionResponse {
     pub partition_session_id: i64,
+    pub graceful: bool,
 }
 
 impl From<RawStopPartitionSessionResponse> for stream_read_message::StopPartitionSessionResponse {
     fn from(value: RawStopPartitionSessionResponse) -> Self {
         stream_read_message::StopPartitionSessionResponse {
             partition_session_id: value.partition_session_id,
-            graceful: false,
+            graceful: value.graceful,
         }
     }
 }
@@ -43,6 +43,7 @@
 
 extern crate core;
 
+pub mod cdc;
 pub(crate) mod client;
 mod client_builder;
 pub(crate) mod client_common;
@@ -87,6 +88,7 @@ mod async_closure;
 #[cfg(test)]
 pub(crate) mod coordination_test;
 pub(crate) mod discovery_pessimization_interceptor;
+pub mod migrate;
 mod table_requests;
 mod table_service_types;
 #[cfg(test)]
@@ -135,15 +137,25 @@ pub use client_topic::client::{
     DescribeTopicOptionsBuilder, TopicClient,
 };
 pub use client_topic::list_types::{
-    AlterConsumer, AlterConsumerBuilder, Codec, Consumer, ConsumerBuilder, ConsumerDescription,
-    MeteringMode, PartitionInfo, PartitionLocation, PartitionStats, PartitioningSettings,
-    TopicDescription, TopicStats,
+    AlterConsumer, AlterConsumerBuilder, AutoPartitioningSettings, AutoPartitioningSettingsBuilder,
+    AutoPartitioningStrategy, Codec, Consumer, ConsumerBuilder, ConsumerDescription,
+    ConsumerResetTarget, MeteringMode, PartitionInfo, PartitionLocation, PartitionStats,
+    PartitioningSettings, TopicDescription, TopicStats,
 };
 // full enum pub types
+pub use client_topic::topicreader::consumer::{
+    TopicConsumer, TopicConsumerOptions, TopicConsumerOptionsBuilder,
+};
+pub use client_topic::topicreader::dead_letter::{DeadLetterPolicy, DeadLetterPolicyBuilder};
 pub use client_topic::topicreader::messages::{
     PartitionSessionKey, TopicReaderBatch, TopicReaderMessage,
 };
 // full enum pub types
+pub use client_topic::topicreader::partition_events::{
+    PartitionSessionEnded, PartitionSessionEvent, PartitionSessionEvents, PartitionSessionFinished,
+    PartitionSessionStarted, PartitionSessionStopping,
+};
+pub use client_topic::topicreader::partition_range::PartitionReadStart;
 pub use client_topic::topicreader::reader::{
     TopicReader, TopicReaderCommitMarker, TopicSelector, TopicSelectorBuilder, TopicSelectors,
 };
@@ -151,16 +163,40 @@ pub use client_topic::topicreader::reader_options::{
     TopicReaderOptions, TopicReaderOptionsBuilder,
 };
 pub use client_topic::topicreader::reader_tx::TopicReaderTx;
+pub use client_topic::topicreader::tx_processor::{TopicTxHandlerArgs, TopicTxProcessor};
+pub use client_topic::topicreader::typed_reader::{
+    RejectedTopicMessage, TypedTopicBatch, TypedTopicMessage, TypedTopicReader,
+};
 // full enum pub types
+pub use client_topic::topicwriter::keyed_writer::{
+    KeyedTopicWriter, KeyedTopicWriterOptions, KeyedTopicWriterOptionsBuilder,
+};
 pub use client_topic::topicwriter::message::{TopicWriterMessage, TopicWriterMessageBuilder};
 // full enum pub types
 pub use client_topic::topicwriter::partitioning::PartitioningStrategy;
+pub use client_topic::topicwriter::typed_writer::TypedTopicWriter;
+
+#[cfg(feature = "msgpack")]
+pub use client_topic::payload_format::MessagePackPayload;
+pub use client_topic::payload_format::{
+    CONTENT_TYPE_METADATA_KEY, JsonPayload, PayloadDecoder, PayloadEncoder, PayloadFormat,
+    ProtobufPayload, SCHEMA_VERSION_METADATA_KEY,
+};
 // full enum pub types
-pub use client_topic::compression::{CompressionDecoder, CompressionEncoder, Executor};
+#[cfg(feature = "zstd")]
+pub use client_topic::compression::ZstdEncoder;
+pub use client_topic::compression::{
+    CodecSelection, CompressionDecoder, CompressionEncoder, Executor,
+};
+#[cfg(feature = "encryption")]
+pub use client_topic::compression::{
+    EncryptionDecoder, EncryptionEncoder, EncryptionKey, KeyProvider, StaticKeyProvider,
+};
 pub use client_topic::topicwriter::writer::TopicWriter;
 pub use client_topic::topicwriter::writer_options::{
-    TopicWriterOptions, TopicWriterOptionsBuilder,
+    BufferOverflowPolicy, TopicWriterOptions, TopicWriterOptionsBuilder,
 };
+pub use client_topic::topicwriter::writer_stats::TopicWriterStats;
 pub use client_topic::topicwriter::writer_tx::TopicWriterTx;
 pub use client_topic::topicwriter::writer_tx_options::{
     TopicWriterTxOptions, TopicWriterTxOptionsBuilder,
@@ -191,13 +227,18 @@ pub use client_table::TableClient;
 
 // full enum pub types
 pub use table_service_types::{
-    ColumnDescription, CopyTableItem, IndexDescription, IndexStatus, IndexType, RenameTableItem,
-    StoreType, TableDescription, UnknownTypeDescription,
+    ColumnDescription, ColumnFamilyCompression, ColumnFamilyDescription, CopyTableItem,
+    IndexDescription, IndexStatus, IndexType, RenameTableItem, StoreType, TableDescription,
+    TablePartitioningSettings, TtlMode, TtlSettings, TtlUnit, UnknownTypeDescription,
 };
 
 // full enum pub types
 pub use client_scheme::client::SchemeClient;
-pub use client_scheme::list_types::{SchemeEntry, SchemeEntryType, SchemePermissions};
+pub use client_scheme::dump_schema::{SchemaDump, SchemaObject};
+pub use client_scheme::list_types::{
+    SchemeEntry, SchemeEntryType, SchemePermissions, SchemePermissionsAction, SchemeWalkEntry,
+};
+pub use client_scheme::modify_permissions::ModifyPermissionsBuilder;
 
 pub use client_operation::{
     ListOperationsRequest, ListOperationsResult, OperationClient, OperationInfo, OperationKind,
@@ -211,7 +252,7 @@ pub use query::Query;
 pub use result::{ResultSet, ResultSetRowsIter, Row};
 pub use table_requests::{
     AlterTableRequest, CreateTableRequest, DropTableRequest, NamedPolicyDescription,
-    ReadRowsRequest, TableColumn, TableOptionsDescription,
+    ReadRowsRequest, TableColumn, TableIndex, TableOptionsDescription,
 };
 // full enum pub types
 pub use waiter::Waiter;
@@ -20,6 +20,11 @@ pub(crate) trait LoadBalancer: Send + Sync + Waiter {
     fn endpoint(&self, service: Service) -> YdbResult<Uri>;
     fn set_discovery_state(&mut self, discovery_state: &Arc<DiscoveryState>) -> YdbResult<()>;
     fn waiter(&self) -> Box<dyn Waiter>; // need for wait ready in without read lock
+
+    // endpoint of the exact node, None if discovery doesn't know the node
+    fn node_endpoint(&self, _node_id: u32) -> Option<Uri> {
+        None
+    }
 }
 
 #[async_trait::async_trait]
@@ -135,6 +135,10 @@ impl LoadBalancer for NearestDCBalancer {
             FallbackStrategy::Error => self_waiter,
         }
     }
+
+    fn node_endpoint(&self, node_id: u32) -> Option<Uri> {
+        self.state_sender.borrow().node_uri(node_id).cloned()
+    }
 }
 
 pub(super) const NODES_PER_DC: usize = 5;
@@ -56,6 +56,10 @@ impl LoadBalancer for RandomLoadBalancer {
     fn waiter(&self) -> Box<dyn Waiter> {
         Box::new(self.waiter.clone())
     }
+
+    fn node_endpoint(&self, node_id: u32) -> Option<Uri> {
+        self.discovery_state.node_uri(node_id).cloned()
+    }
 }
 
 #[async_trait::async_trait]
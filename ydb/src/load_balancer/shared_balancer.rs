@@ -51,6 +51,10 @@ impl LoadBalancer for SharedLoadBalancer {
     fn waiter(&self) -> Box<dyn Waiter> {
         return self.inner.read().unwrap().waiter();
     }
+
+    fn node_endpoint(&self, node_id: u32) -> Option<Uri> {
+        self.inner.read().ok()?.node_endpoint(node_id)
+    }
 }
 
 #[async_trait::async_trait]
@@ -0,0 +1,116 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tracing_test::traced_test;
+
+use super::{Migration, MigrationState, Migrator, SchemaReconciler, TableChange};
+use crate::test_integration_helper::create_client;
+use crate::{CreateTableRequest, DropTableRequest, TableColumn, TableIndex, Value, YdbResult};
+
+#[tokio::test]
+#[traced_test]
+#[ignore] // need YDB access
+async fn migrate_up_and_down() -> YdbResult<()> {
+    let client = create_client().await?;
+    let suffix = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system time before UNIX epoch")
+        .as_nanos();
+    let bookkeeping = format!("migrate_test_{suffix}");
+    let users = format!("migrate_test_users_{suffix}");
+
+    let migrations = vec![
+        Migration::new(
+            1,
+            "create_users",
+            format!("CREATE TABLE `{users}` (id Uint64, PRIMARY KEY (id))"),
+        )
+        .with_down(format!("DROP TABLE `{users}`")),
+        Migration::new(
+            2,
+            "add_name",
+            format!("ALTER TABLE `{users}` ADD COLUMN name Utf8"),
+        )
+        .with_down(format!("ALTER TABLE `{users}` DROP COLUMN name")),
+    ];
+    let migrator =
+        || Migrator::new(&client, migrations.clone()).map(|m| m.with_table(bookkeeping.clone()));
+
+    let planned = migrator()?.with_dry_run(true).up().await?;
+    assert_eq!(planned.len(), 2);
+    assert!(planned.iter().all(|step| !step.executed));
+
+    let applied = migrator()?.up().await?;
+    assert_eq!(applied.len(), 2);
+    assert!(migrator()?.up().await?.is_empty());
+
+    let status = migrator()?.status().await?;
+    assert!(
+        status
+            .iter()
+            .all(|item| item.state == MigrationState::Applied)
+    );
+
+    let changed = vec![
+        migrations[0].clone(),
+        Migration::new(2, "add_name", "SELECT 1"),
+    ];
+    assert!(
+        Migrator::new(&client, changed)?
+            .with_table(bookkeeping.clone())
+            .up()
+            .await
+            .is_err()
+    );
+
+    let reverted = migrator()?.down(2).await?;
+    assert_eq!(reverted.len(), 2);
+
+    client
+        .query_client()
+        .exec(format!("DROP TABLE `{bookkeeping}`"))
+        .await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+#[ignore] // need YDB access
+async fn reconcile_table() -> YdbResult<()> {
+    let client = create_client().await?;
+    let suffix = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system time before UNIX epoch")
+        .as_nanos();
+    let table_path = format!("{}/reconcile_test_{suffix}", client.database());
+    let base = CreateTableRequest::new(table_path.clone())
+        .with_column(TableColumn::new("id", Value::Int64(0)))
+        .with_column(TableColumn::new("name", Value::Text(String::new())).with_not_null(false))
+        .with_primary_key(["id"]);
+
+    let reconciler = SchemaReconciler::new(&client);
+    let plan = reconciler.reconcile(base.clone()).await?;
+    assert_eq!(plan.changes(), [TableChange::CreateTable]);
+    assert!(reconciler.plan(base.clone()).await?.is_empty());
+
+    let extended = base
+        .clone()
+        .with_column(TableColumn::new("email", Value::Text(String::new())).with_not_null(false))
+        .with_index(TableIndex::new("by_email", ["email"]))
+        .with_attribute("owner", "tests");
+    reconciler.reconcile(extended.clone()).await?;
+    assert!(reconciler.plan(extended).await?.is_empty());
+
+    let plan = reconciler.plan(base.clone()).await?;
+    assert!(plan.is_destructive());
+    assert!(reconciler.apply(&plan).await.is_err());
+    SchemaReconciler::new(&client)
+        .with_allow_destructive(true)
+        .apply(&plan)
+        .await?;
+    assert!(reconciler.plan(base).await?.is_empty());
+
+    client
+        .table_client()
+        .drop_table(DropTableRequest::new(table_path))
+        .await
+}
@@ -0,0 +1,202 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::{YdbError, YdbResult};
+
+/// Single versioned schema migration.
+#[derive(Clone, Debug)]
+pub struct Migration {
+    version: u64,
+    name: String,
+    up: String,
+    down: Option<String>,
+}
+
+impl Migration {
+    /// Create migration with YQL text, which applies it.
+    ///
+    /// The text is executed by [`crate::QueryClient::exec`] with [`crate::TxMode::Implicit`], so
+    /// it may contain DDL statements.
+    pub fn new(version: u64, name: impl Into<String>, up: impl Into<String>) -> Self {
+        Self {
+            version,
+            name: name.into(),
+            up: up.into(),
+            down: None,
+        }
+    }
+
+    /// Set YQL text, which reverts the migration.
+    pub fn with_down(mut self, down: impl Into<String>) -> Self {
+        self.down = Some(down.into());
+        self
+    }
+
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn up(&self) -> &str {
+        &self.up
+    }
+
+    pub fn down(&self) -> Option<&str> {
+        self.down.as_deref()
+    }
+
+    /// Checksum of the up text: CRC32 in hex.
+    ///
+    /// Line endings are normalized before hashing, so checkouts with `\r\n` give the same result.
+    pub fn checksum(&self) -> String {
+        let mut crc = flate2::Crc::new();
+        crc.update(self.up.replace("\r\n", "\n").as_bytes());
+        format!("{:08x}", crc.sum())
+    }
+
+    /// Build migrations from files content, for example embedded with `include_str!`.
+    ///
+    /// File names follow `<version>_<name>.up.sql` and `<version>_<name>.down.sql`,
+    /// `<version>_<name>.sql` is the same as `.up.sql`. Files without `.sql` extension are
+    /// ignored. Result is ordered by version.
+    pub fn from_files<I, N, C>(files: I) -> YdbResult<Vec<Migration>>
+    where
+        I: IntoIterator<Item = (N, C)>,
+        N: AsRef<str>,
+        C: Into<String>,
+    {
+        let mut ups: BTreeMap<u64, Migration> = BTreeMap::new();
+        let mut downs: BTreeMap<u64, (String, String)> = BTreeMap::new();
+
+        for (file_name, content) in files {
+            let file_name = file_name.as_ref();
+            let Some(stem) = file_name.strip_suffix(".sql") else {
+                continue;
+            };
+
+            let (stem, is_down) = if let Some(stem) = stem.strip_suffix(".down") {
+                (stem, true)
+            } else {
+                (stem.strip_suffix(".up").unwrap_or(stem), false)
+            };
+            let (version, name) = parse_stem(stem).ok_or_else(|| {
+                YdbError::custom(format!(
+                    "bad migration file name: '{file_name}', expected <version>_<name>.up.sql"
+                ))
+            })?;
+
+            let duplicate = if is_down {
+                downs
+                    .insert(version, (name.to_string(), content.into()))
+                    .is_some()
+            } else {
+                ups.insert(version, Migration::new(version, name, content))
+                    .is_some()
+            };
+            if duplicate {
+                return Err(YdbError::custom(format!(
+                    "duplicate migration version {version}: '{file_name}'"
+                )));
+            }
+        }
+
+        for (version, (name, down)) in downs {
+            let migration = ups.get_mut(&version).ok_or_else(|| {
+                YdbError::custom(format!(
+                    "down migration {version}_{name} has no up migration"
+                ))
+            })?;
+            if migration.name != name {
+                return Err(YdbError::custom(format!(
+                    "names of up and down migration {version} differ: '{}' and '{name}'",
+                    migration.name
+                )));
+            }
+            migration.down = Some(down);
+        }
+
+        Ok(ups.into_values().collect())
+    }
+
+    /// Load migrations from `.sql` files of the directory, see [`Self::from_files`] for names.
+    pub fn load_dir(path: impl AsRef<Path>) -> YdbResult<Vec<Migration>> {
+        let path = path.as_ref();
+        let read_err = |err: std::io::Error| {
+            YdbError::custom(format!(
+                "failed to read migrations from '{}': {err}",
+                path.display()
+            ))
+        };
+
+        let mut files = Vec::new();
+        for entry in std::fs::read_dir(path).map_err(read_err)? {
+            let entry = entry.map_err(read_err)?;
+            if !entry.file_type().map_err(read_err)?.is_file() {
+                continue;
+            }
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+            if !file_name.ends_with(".sql") {
+                continue;
+            }
+            let content = std::fs::read_to_string(entry.path()).map_err(read_err)?;
+            files.push((file_name, content));
+        }
+
+        Self::from_files(files)
+    }
+}
+
+fn parse_stem(stem: &str) -> Option<(u64, &str)> {
+    let (version, name) = stem.split_once('_')?;
+    if name.is_empty() {
+        return None;
+    }
+    Some((version.parse().ok()?, name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_files_pairs_up_and_down() -> YdbResult<()> {
+        let migrations = Migration::from_files([
+            ("2_add_column.up.sql", "ALTER TABLE t ADD COLUMN v Utf8"),
+            ("1_init.sql", "CREATE TABLE t (id Uint64, PRIMARY KEY (id))"),
+            ("2_add_column.down.sql", "ALTER TABLE t DROP COLUMN v"),
+            ("README.md", "docs"),
+        ])?;
+
+        assert_eq!(migrations.len(), 2);
+        assert_eq!(migrations[0].version(), 1);
+        assert_eq!(migrations[0].name(), "init");
+        assert_eq!(migrations[0].down(), None);
+        assert_eq!(migrations[1].version(), 2);
+        assert_eq!(migrations[1].name(), "add_column");
+        assert_eq!(migrations[1].down(), Some("ALTER TABLE t DROP COLUMN v"));
+        Ok(())
+    }
+
+    #[test]
+    fn from_files_rejects_bad_sets() {
+        assert!(Migration::from_files([("init.sql", "")]).is_err());
+        assert!(Migration::from_files([("1_.sql", "")]).is_err());
+        assert!(Migration::from_files([("1_a.sql", ""), ("1_b.up.sql", "")]).is_err());
+        assert!(Migration::from_files([("1_a.down.sql", "")]).is_err());
+        assert!(Migration::from_files([("1_a.sql", ""), ("1_b.down.sql", "")]).is_err());
+    }
+
+    #[test]
+    fn checksum_ignores_line_endings() {
+        let unix = Migration::new(1, "a", "SELECT 1;\nSELECT 2;");
+        let windows = Migration::new(1, "a", "SELECT 1;\r\nSELECT 2;");
+        let other = Migration::new(1, "a", "SELECT 3;");
+
+        assert_eq!(unix.checksum(), windows.checksum());
+        assert_ne!(unix.checksum(), other.checksum());
+        assert_eq!(unix.checksum().len(), 8);
+    }
+}
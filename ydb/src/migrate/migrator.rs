@@ -0,0 +1,654 @@
+use std::collections::BTreeMap;
+use std::time::{Duration, SystemTime};
+
+use tracing::{info, instrument};
+use ydb_grpc::ydb_proto::status_ids::StatusCode;
+
+use super::has_status;
+use super::migration::Migration;
+use crate::{
+    AcquireOptionsBuilder, Client, CoordinationClient, CoordinationSession, Lease,
+    NodeConfigBuilder, QueryClient, SessionOptionsBuilder, TxMode, YdbError, YdbResult,
+};
+
+const DEFAULT_TABLE: &str = "schema_migrations";
+const DEFAULT_LOCK_NODE: &str = "schema_migrations_lock";
+const LOCK_SEMAPHORE: &str = "migrate";
+
+/// Direction of [`MigrationStep`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "force-exhaustive-all"), non_exhaustive)]
+pub enum MigrationDirection {
+    Up,
+    Down,
+}
+
+/// Migration, which was applied or reverted by [`Migrator`] (or would be, in dry-run mode).
+#[derive(Clone, Debug)]
+#[cfg_attr(not(feature = "force-exhaustive-all"), non_exhaustive)]
+pub struct MigrationStep {
+    pub version: u64,
+    pub name: String,
+    pub direction: MigrationDirection,
+    /// YQL text of the step.
+    pub script: String,
+    /// False in dry-run mode.
+    pub executed: bool,
+}
+
+/// State of a migration version, see [`Migrator::status`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "force-exhaustive-all"), non_exhaustive)]
+pub enum MigrationState {
+    /// Known locally, not applied yet.
+    Pending,
+
+    /// Applied with the same checksum as the local migration.
+    Applied,
+
+    /// Applied, but local migration text was changed after that.
+    ChecksumDrift { applied_checksum: String },
+
+    /// Applied, but absent in local migrations.
+    Missing,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(not(feature = "force-exhaustive-all"), non_exhaustive)]
+pub struct MigrationStatus {
+    pub version: u64,
+    pub name: String,
+    pub state: MigrationState,
+    pub applied_at: Option<SystemTime>,
+}
+
+#[derive(Clone, Debug)]
+struct AppliedMigration {
+    version: u64,
+    name: String,
+    checksum: String,
+    applied_at: SystemTime,
+}
+
+/// Applies versioned [`Migration`]s to the database.
+///
+/// Applied versions are stored in a bookkeeping table (`schema_migrations` by default).
+/// Runs, which change the schema, hold an exclusive semaphore on a coordination node, so
+/// concurrent runners wait for each other and then see the migrations applied by the first one.
+pub struct Migrator {
+    query_client: QueryClient,
+    coordination_client: CoordinationClient,
+    migrations: Vec<Migration>,
+    table: String,
+    lock_node: String,
+    lock_timeout: Duration,
+    dry_run: bool,
+    allow_checksum_drift: bool,
+}
+
+impl Migrator {
+    /// Create migrator for the client database.
+    ///
+    /// Returns error if migrations contain duplicate versions.
+    pub fn new(
+        client: &Client,
+        migrations: impl IntoIterator<Item = Migration>,
+    ) -> YdbResult<Self> {
+        let mut migrations: Vec<Migration> = migrations.into_iter().collect();
+        migrations.sort_by_key(Migration::version);
+        if let Some(pair) = migrations
+            .windows(2)
+            .find(|pair| pair[0].version() == pair[1].version())
+        {
+            return Err(YdbError::custom(format!(
+                "duplicate migration version {}: '{}' and '{}'",
+                pair[0].version(),
+                pair[0].name(),
+                pair[1].name()
+            )));
+        }
+
+        Ok(Self {
+            query_client: client.query_client(),
+            coordination_client: client.coordination_client(),
+            migrations,
+            table: DEFAULT_TABLE.to_string(),
+            lock_node: format!("{}/{}", client.database(), DEFAULT_LOCK_NODE),
+            lock_timeout: Duration::from_secs(60),
+            dry_run: false,
+            allow_checksum_drift: false,
+        })
+    }
+
+    /// Bookkeeping table name, relative to the database. Default: `schema_migrations`.
+    pub fn with_table(mut self, table: impl Into<String>) -> Self {
+        self.table = table.into();
+        self
+    }
+
+    /// Full path of coordination node for the runners lock.
+    /// Default: `<database>/schema_migrations_lock`. Created if not exists.
+    pub fn with_lock_node(mut self, path: impl Into<String>) -> Self {
+        self.lock_node = path.into();
+        self
+    }
+
+    /// Max time for waiting the lock, held by other runner. Default: 60 seconds.
+    pub fn with_lock_timeout(mut self, timeout: Duration) -> Self {
+        self.lock_timeout = timeout;
+        self
+    }
+
+    /// Plan migrations without executing them and without taking the lock.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Apply pending migrations even if checksums of applied ones differ from local texts.
+    /// By default such runs fail before any change.
+    pub fn with_allow_checksum_drift(mut self, allow: bool) -> Self {
+        self.allow_checksum_drift = allow;
+        self
+    }
+
+    /// State of every local and applied migration, ordered by version.
+    pub async fn status(&mut self) -> YdbResult<Vec<MigrationStatus>> {
+        let applied = self.applied_or_empty().await?;
+        Ok(migration_statuses(&self.migrations, &applied))
+    }
+
+    /// Apply all pending migrations.
+    pub async fn up(&mut self) -> YdbResult<Vec<MigrationStep>> {
+        self.up_to(u64::MAX).await
+    }
+
+    /// Apply pending migrations with versions up to `version` inclusive.
+    #[instrument(name = "ydb.Migrator.Up", skip(self), fields(db.system.name = "ydb"))]
+    pub async fn up_to(&mut self, version: u64) -> YdbResult<Vec<MigrationStep>> {
+        if self.dry_run {
+            let applied = self.applied_or_empty().await?;
+            let plan = plan_up(
+                &self.migrations,
+                &applied,
+                version,
+                self.allow_checksum_drift,
+            )?;
+            return Ok(plan.into_iter().map(|m| up_step(m, false)).collect());
+        }
+
+        let (_session, lease) = self.lock().await?;
+        self.ensure_table().await?;
+        let applied = self.applied().await?;
+        let plan: Vec<Migration> = plan_up(
+            &self.migrations,
+            &applied,
+            version,
+            self.allow_checksum_drift,
+        )?
+        .into_iter()
+        .cloned()
+        .collect();
+
+        let mut steps = Vec::with_capacity(plan.len());
+        for migration in plan {
+            check_lock(&lease)?;
+            info!(
+                version = migration.version(),
+                name = migration.name(),
+                "apply migration"
+            );
+            self.query_client
+                .exec(migration.up())
+                .implicit_tx()
+                .await
+                .map_err(|err| step_error(&migration, MigrationDirection::Up, err))?;
+            self.record_applied(&migration).await?;
+            steps.push(up_step(&migration, true));
+        }
+        Ok(steps)
+    }
+
+    /// Revert the last `steps` applied migrations.
+    pub async fn down(&mut self, steps: usize) -> YdbResult<Vec<MigrationStep>> {
+        self.revert(|applied| applied.keys().rev().nth(steps).copied().unwrap_or_default())
+            .await
+    }
+
+    /// Revert applied migrations with versions greater than `version`.
+    ///
+    /// Every reverted migration must have a down script.
+    #[instrument(name = "ydb.Migrator.Down", skip(self), fields(db.system.name = "ydb"))]
+    pub async fn down_to(&mut self, version: u64) -> YdbResult<Vec<MigrationStep>> {
+        self.revert(|_| version).await
+    }
+
+    /// Target version is computed from the applied migrations read under the lock.
+    async fn revert(
+        &mut self,
+        target: impl FnOnce(&BTreeMap<u64, AppliedMigration>) -> u64,
+    ) -> YdbResult<Vec<MigrationStep>> {
+        if self.dry_run {
+            let applied = self.applied_or_empty().await?;
+            let plan = plan_down(&self.migrations, &applied, target(&applied))?;
+            return Ok(plan.into_iter().map(|m| down_step(m, false)).collect());
+        }
+
+        let (_session, lease) = self.lock().await?;
+        self.ensure_table().await?;
+        let applied = self.applied().await?;
+        let plan: Vec<Migration> = plan_down(&self.migrations, &applied, target(&applied))?
+            .into_iter()
+            .cloned()
+            .collect();
+
+        let mut steps = Vec::with_capacity(plan.len());
+        for migration in plan {
+            check_lock(&lease)?;
+            info!(
+                version = migration.version(),
+                name = migration.name(),
+                "revert migration"
+            );
+            let down = migration.down().unwrap_or_default();
+            self.query_client
+                .exec(down)
+                .implicit_tx()
+                .await
+                .map_err(|err| step_error(&migration, MigrationDirection::Down, err))?;
+            self.delete_applied(migration.version()).await?;
+            steps.push(down_step(&migration, true));
+        }
+        Ok(steps)
+    }
+
+    async fn lock(&mut self) -> YdbResult<(CoordinationSession, Lease)> {
+        if self
+            .coordination_client
+            .describe_node(self.lock_node.clone())
+            .await
+            .is_err()
+        {
+            let created = self
+                .coordination_client
+                .create_node(
+                    self.lock_node.clone(),
+                    NodeConfigBuilder::default().build()?,
+                )
+                .await;
+            match created {
+                Err(err) if !has_status(&err, StatusCode::AlreadyExists) => return Err(err),
+                _ => {}
+            }
+        }
+
+        let session = self
+            .coordination_client
+            .create_session(
+                self.lock_node.clone(),
+                SessionOptionsBuilder::default()
+                    .description("ydb schema migrations".to_string())
+                    .build()?,
+            )
+            .await?;
+        let lease = session
+            .acquire_semaphore_with_params(
+                LOCK_SEMAPHORE,
+                u64::MAX,
+                AcquireOptionsBuilder::default()
+                    .ephemeral(true)
+                    .timeout(self.lock_timeout)
+                    .build()?,
+            )
+            .await
+            .map_err(|err| {
+                YdbError::custom(format!(
+                    "failed to lock migrations on '{}': {err}",
+                    self.lock_node
+                ))
+            })?;
+        Ok((session, lease))
+    }
+
+    async fn ensure_table(&mut self) -> YdbResult<()> {
+        self.query_client
+            .exec(format!(
+                "CREATE TABLE IF NOT EXISTS `{}` (
+                    version Uint64 NOT NULL,
+                    name Utf8 NOT NULL,
+                    checksum Utf8 NOT NULL,
+                    applied_at Timestamp NOT NULL,
+                    PRIMARY KEY (version)
+                )",
+                self.table
+            ))
+            .implicit_tx()
+            .await
+    }
+
+    async fn applied_or_empty(&mut self) -> YdbResult<BTreeMap<u64, AppliedMigration>> {
+        match self.applied().await {
+            Err(err) if has_status(&err, StatusCode::SchemeError) => Ok(BTreeMap::new()),
+            res => res,
+        }
+    }
+
+    async fn applied(&mut self) -> YdbResult<BTreeMap<u64, AppliedMigration>> {
+        let result_set = self
+            .query_client
+            .query_result_set(format!(
+                "SELECT version, name, checksum, applied_at FROM `{}` ORDER BY version",
+                self.table
+            ))
+            .with_tx_mode(TxMode::SerializableReadWrite)
+            .idempotent(true)
+            .await?;
+
+        let mut applied = BTreeMap::new();
+        for mut row in result_set.into_iter() {
+            let version: u64 = row.remove_field_by_name("version")?.try_into()?;
+            applied.insert(
+                version,
+                AppliedMigration {
+                    version,
+                    name: row.remove_field_by_name("name")?.try_into()?,
+                    checksum: row.remove_field_by_name("checksum")?.try_into()?,
+                    applied_at: row.remove_field_by_name("applied_at")?.try_into()?,
+                },
+            );
+        }
+        Ok(applied)
+    }
+
+    async fn record_applied(&mut self, migration: &Migration) -> YdbResult<()> {
+        self.query_client
+            .exec(format!(
+                "UPSERT INTO `{}` (version, name, checksum, applied_at)
+                VALUES ($version, $name, $checksum, $applied_at)",
+                self.table
+            ))
+            .param("$version", migration.version())
+            .param("$name", migration.name().to_string())
+            .param("$checksum", migration.checksum())
+            .param("$applied_at", SystemTime::now())
+            .with_tx_mode(TxMode::SerializableReadWrite)
+            .idempotent(true)
+            .await
+    }
+
+    async fn delete_applied(&mut self, version: u64) -> YdbResult<()> {
+        self.query_client
+            .exec(format!(
+                "DELETE FROM `{}` WHERE version = $version",
+                self.table
+            ))
+            .param("$version", version)
+            .with_tx_mode(TxMode::SerializableReadWrite)
+            .idempotent(true)
+            .await
+    }
+}
+
+fn check_lock(lease: &Lease) -> YdbResult<()> {
+    if lease.alive().is_cancelled() {
+        return Err(YdbError::custom(
+            "migrations lock was lost, stop applying migrations",
+        ));
+    }
+    Ok(())
+}
+
+fn step_error(migration: &Migration, direction: MigrationDirection, err: YdbError) -> YdbError {
+    let action = match direction {
+        MigrationDirection::Up => "apply",
+        MigrationDirection::Down => "revert",
+    };
+    YdbError::MigrationFailed {
+        message: format!(
+            "failed to {action} migration {}_{}: {err}",
+            migration.version(),
+            migration.name()
+        ),
+        source: Box::new(err),
+    }
+}
+
+fn up_step(migration: &Migration, executed: bool) -> MigrationStep {
+    MigrationStep {
+        version: migration.version(),
+        name: migration.name().to_string(),
+        direction: MigrationDirection::Up,
+        script: migration.up().to_string(),
+        executed,
+    }
+}
+
+fn down_step(migration: &Migration, executed: bool) -> MigrationStep {
+    MigrationStep {
+        version: migration.version(),
+        name: migration.name().to_string(),
+        direction: MigrationDirection::Down,
+        script: migration.down().unwrap_or_default().to_string(),
+        executed,
+    }
+}
+
+fn migration_statuses(
+    migrations: &[Migration],
+    applied: &BTreeMap<u64, AppliedMigration>,
+) -> Vec<MigrationStatus> {
+    let mut statuses: BTreeMap<u64, MigrationStatus> = applied
+        .values()
+        .map(|item| {
+            (
+                item.version,
+                MigrationStatus {
+                    version: item.version,
+                    name: item.name.clone(),
+                    state: MigrationState::Missing,
+                    applied_at: Some(item.applied_at),
+                },
+            )
+        })
+        .collect();
+
+    for migration in migrations {
+        let state = match applied.get(&migration.version()) {
+            None => MigrationState::Pending,
+            Some(item) if item.checksum == migration.checksum() => MigrationState::Applied,
+            Some(item) => MigrationState::ChecksumDrift {
+                applied_checksum: item.checksum.clone(),
+            },
+        };
+        statuses.insert(
+            migration.version(),
+            MigrationStatus {
+                version: migration.version(),
+                name: migration.name().to_string(),
+                state,
+                applied_at: applied
+                    .get(&migration.version())
+                    .map(|item| item.applied_at),
+            },
+        );
+    }
+
+    statuses.into_values().collect()
+}
+
+fn plan_up<'a>(
+    migrations: &'a [Migration],
+    applied: &BTreeMap<u64, AppliedMigration>,
+    target: u64,
+    allow_checksum_drift: bool,
+) -> YdbResult<Vec<&'a Migration>> {
+    if !allow_checksum_drift {
+        let drifted: Vec<String> = migrations
+            .iter()
+            .filter(|m| {
+                applied
+                    .get(&m.version())
+                    .is_some_and(|item| item.checksum != m.checksum())
+            })
+            .map(|m| format!("{}_{}", m.version(), m.name()))
+            .collect();
+        if !drifted.is_empty() {
+            return Err(YdbError::custom(format!(
+                "applied migrations were changed: {}",
+                drifted.join(", ")
+            )));
+        }
+    }
+
+    Ok(migrations
+        .iter()
+        .filter(|m| m.version() <= target && !applied.contains_key(&m.version()))
+        .collect())
+}
+
+fn plan_down<'a>(
+    migrations: &'a [Migration],
+    applied: &BTreeMap<u64, AppliedMigration>,
+    target: u64,
+) -> YdbResult<Vec<&'a Migration>> {
+    applied
+        .values()
+        .rev()
+        .filter(|item| item.version > target)
+        .map(|item| {
+            let migration = migrations
+                .iter()
+                .find(|m| m.version() == item.version)
+                .ok_or_else(|| {
+                    YdbError::custom(format!(
+                        "applied migration {}_{} is absent in local migrations",
+                        item.version, item.name
+                    ))
+                })?;
+            if migration.down().is_none() {
+                return Err(YdbError::custom(format!(
+                    "migration {}_{} has no down script",
+                    migration.version(),
+                    migration.name()
+                )));
+            }
+            Ok(migration)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn applied(migrations: &[&Migration]) -> BTreeMap<u64, AppliedMigration> {
+        migrations
+            .iter()
+            .map(|m| {
+                (
+                    m.version(),
+                    AppliedMigration {
+                        version: m.version(),
+                        name: m.name().to_string(),
+                        checksum: m.checksum(),
+                        applied_at: SystemTime::UNIX_EPOCH,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    fn migrations() -> Vec<Migration> {
+        vec![
+            Migration::new(1, "a", "CREATE TABLE a (id Uint64, PRIMARY KEY (id))")
+                .with_down("DROP TABLE a"),
+            Migration::new(2, "b", "CREATE TABLE b (id Uint64, PRIMARY KEY (id))")
+                .with_down("DROP TABLE b"),
+            Migration::new(3, "c", "CREATE TABLE c (id Uint64, PRIMARY KEY (id))"),
+        ]
+    }
+
+    fn versions(plan: &[&Migration]) -> Vec<u64> {
+        plan.iter().map(|m| m.version()).collect()
+    }
+
+    #[test]
+    fn plan_up_skips_applied() -> YdbResult<()> {
+        let migrations = migrations();
+        let applied = applied(&[&migrations[0]]);
+
+        assert_eq!(
+            versions(&plan_up(&migrations, &applied, u64::MAX, false)?),
+            [2, 3]
+        );
+        assert_eq!(versions(&plan_up(&migrations, &applied, 2, false)?), [2]);
+        Ok(())
+    }
+
+    #[test]
+    fn plan_up_detects_checksum_drift() -> YdbResult<()> {
+        let mut migrations = migrations();
+        let applied = applied(&[&migrations[0]]);
+        migrations[0] = Migration::new(1, "a", "CREATE TABLE a2 (id Uint64, PRIMARY KEY (id))");
+
+        assert!(plan_up(&migrations, &applied, u64::MAX, false).is_err());
+        assert_eq!(
+            versions(&plan_up(&migrations, &applied, u64::MAX, true)?),
+            [2, 3]
+        );
+
+        let statuses = migration_statuses(&migrations, &applied);
+        assert!(matches!(
+            statuses[0].state,
+            MigrationState::ChecksumDrift { .. }
+        ));
+        assert_eq!(statuses[1].state, MigrationState::Pending);
+        Ok(())
+    }
+
+    #[test]
+    fn plan_down_reverts_in_reverse_order() -> YdbResult<()> {
+        let migrations = migrations();
+        let applied = applied(&[&migrations[0], &migrations[1]]);
+
+        assert_eq!(versions(&plan_down(&migrations, &applied, 0)?), [2, 1]);
+        assert_eq!(versions(&plan_down(&migrations, &applied, 1)?), [2]);
+
+        let applied_all = self::applied(&migrations.iter().collect::<Vec<_>>());
+        assert!(plan_down(&migrations, &applied_all, 0).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn status_reports_missing_migrations() {
+        let migrations = migrations();
+        let applied = applied(&[&migrations[0], &migrations[2]]);
+
+        let statuses = migration_statuses(&migrations[..2], &applied);
+        let states: Vec<_> = statuses
+            .iter()
+            .map(|s| (s.version, s.state.clone()))
+            .collect();
+        assert_eq!(
+            states,
+            [
+                (1, MigrationState::Applied),
+                (2, MigrationState::Pending),
+                (3, MigrationState::Missing),
+            ]
+        );
+    }
+
+    #[test]
+    fn step_error_keeps_source() {
+        let source =
+            YdbError::TransportGRPCStatus(std::sync::Arc::new(tonic::Status::aborted("aborted")));
+        let err = step_error(&migrations()[0], MigrationDirection::Up, source);
+
+        assert!(matches!(
+            std::error::Error::source(&err).and_then(|source| source.downcast_ref::<YdbError>()),
+            Some(YdbError::TransportGRPCStatus(_))
+        ));
+        assert_eq!(err.need_retry(), crate::errors::NeedRetry::True);
+    }
+}
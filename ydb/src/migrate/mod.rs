@@ -0,0 +1,54 @@
+//! Versioned schema migrations.
+//!
+//! [`Migrator`] applies ordered YQL [`Migration`]s and records applied versions with checksums
+//! in a bookkeeping table. Concurrent runners are serialized with a coordination node
+//! semaphore, so several instances of a service may run migrations on start.
+//!
+//! Migrations are embedded into the binary with [`Migration::new`] and `include_str!`, or loaded
+//! from a directory with [`Migration::load_dir`].
+//!
+//! Tables may be also declared by their desired shape: [`SchemaReconciler`] compares
+//! [`crate::CreateTableRequest`] with the existing table and plans the minimal
+//! [`crate::AlterTableRequest`].
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use ydb::{ClientBuilder, YdbResult};
+//! # use ydb::migrate::{Migration, Migrator};
+//! # #[tokio::main]
+//! # async fn main() -> YdbResult<()> {
+//! # let client = ClientBuilder::new_from_connection_string("grpc://localhost:2136/local")?.client()?;
+//! let migrations = vec![
+//!     Migration::new(1, "create_users", "CREATE TABLE users (id Uint64, PRIMARY KEY (id))")
+//!         .with_down("DROP TABLE users"),
+//!     Migration::new(2, "add_name", "ALTER TABLE users ADD COLUMN name Utf8")
+//!         .with_down("ALTER TABLE users DROP COLUMN name"),
+//! ];
+//!
+//! let applied = Migrator::new(&client, migrations)?.up().await?;
+//! println!("applied {} migrations", applied.len());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::YdbError;
+use ydb_grpc::ydb_proto::status_ids::StatusCode;
+
+mod migration;
+mod migrator;
+mod reconcile;
+
+#[cfg(test)]
+mod integration_test;
+
+pub use migration::Migration;
+pub use migrator::{MigrationDirection, MigrationState, MigrationStatus, MigrationStep, Migrator};
+pub use reconcile::{SchemaReconciler, TableChange, TablePlan};
+
+fn has_status(err: &YdbError, code: StatusCode) -> bool {
+    match err {
+        YdbError::YdbStatusError(status) => status.operation_status().ok() == Some(code),
+        _ => false,
+    }
+}
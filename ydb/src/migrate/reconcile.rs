@@ -0,0 +1,503 @@
+use std::collections::{BTreeMap, HashMap};
+
+use ydb_grpc::ydb_proto::status_ids::StatusCode;
+
+use super::has_status;
+use crate::{
+    AlterTableRequest, Client, CreateTableRequest, IndexDescription, IndexType, TableClient,
+    TableColumn, TableDescription, TableIndex, YdbError, YdbResult,
+};
+
+/// Single difference between desired and existing table, see [`TablePlan::changes`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "force-exhaustive-all"), non_exhaustive)]
+pub enum TableChange {
+    /// Table does not exist yet.
+    CreateTable,
+    AddColumn {
+        name: String,
+    },
+    DropColumn {
+        name: String,
+    },
+    SetColumnFamily {
+        name: String,
+        family: String,
+    },
+    AddIndex {
+        name: String,
+    },
+    DropIndex {
+        name: String,
+    },
+    /// Index with the same name has other columns or type, it is dropped and built again.
+    RecreateIndex {
+        name: String,
+    },
+    SetAttribute {
+        key: String,
+        value: String,
+    },
+    DropAttribute {
+        key: String,
+    },
+}
+
+impl TableChange {
+    /// Change loses data: dropped columns, or indexes, which are unavailable until rebuilt.
+    pub fn is_destructive(&self) -> bool {
+        matches!(
+            self,
+            TableChange::DropColumn { .. }
+                | TableChange::DropIndex { .. }
+                | TableChange::RecreateIndex { .. }
+        )
+    }
+}
+
+/// Changes, which bring an existing table to the desired shape.
+#[derive(Clone, Debug)]
+pub struct TablePlan {
+    path: String,
+    changes: Vec<TableChange>,
+    create: Option<CreateTableRequest>,
+    alter: AlterTableRequest,
+    recreate_indexes: Vec<TableIndex>,
+}
+
+impl TablePlan {
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn changes(&self) -> &[TableChange] {
+        &self.changes
+    }
+
+    /// Table already has the desired shape.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn is_destructive(&self) -> bool {
+        self.changes.iter().any(TableChange::is_destructive)
+    }
+
+    /// Request, which creates the table, if it does not exist.
+    pub fn create_request(&self) -> Option<&CreateTableRequest> {
+        self.create.as_ref()
+    }
+
+    /// Minimal request, which alters the existing table.
+    ///
+    /// Recreated indexes are dropped by this request and added by
+    /// [`Self::recreate_indexes_request`], because single AlterTable can't drop and add
+    /// an index with the same name.
+    pub fn alter_request(&self) -> Option<&AlterTableRequest> {
+        if self.create.is_some() || self.is_empty() {
+            return None;
+        }
+        Some(&self.alter)
+    }
+
+    /// Indexes dropped by [`Self::alter_request`] to be built again with other columns or type.
+    pub fn recreate_indexes(&self) -> &[TableIndex] {
+        &self.recreate_indexes
+    }
+
+    /// Request, which adds recreated indexes back. Apply it after [`Self::alter_request`].
+    pub fn recreate_indexes_request(&self) -> Option<AlterTableRequest> {
+        if self.recreate_indexes.is_empty() {
+            return None;
+        }
+        let mut request = AlterTableRequest::new(self.path.clone());
+        request.add_indexes = self.recreate_indexes.clone();
+        Some(request)
+    }
+}
+
+/// Compares desired table shape, declared as [`CreateTableRequest`], with the existing table
+/// and applies the difference.
+///
+/// Column type, nullability and primary key can't be changed by AlterTable: planning fails
+/// when they differ. Destructive changes (see [`TableChange::is_destructive`]) are refused
+/// by [`Self::apply`] unless allowed with [`Self::with_allow_destructive`].
+pub struct SchemaReconciler {
+    table_client: TableClient,
+    allow_destructive: bool,
+}
+
+impl SchemaReconciler {
+    pub fn new(client: &Client) -> Self {
+        Self {
+            table_client: client.table_client(),
+            allow_destructive: false,
+        }
+    }
+
+    pub fn with_allow_destructive(mut self, allow: bool) -> Self {
+        self.allow_destructive = allow;
+        self
+    }
+
+    /// Plan changes without applying them.
+    pub async fn plan(&self, desired: CreateTableRequest) -> YdbResult<TablePlan> {
+        match self.table_client.describe_table(desired.path.clone()).await {
+            Ok(current) => plan_table(desired, &current),
+            Err(err) if has_status(&err, StatusCode::SchemeError) => Ok(TablePlan {
+                path: desired.path.clone(),
+                changes: vec![TableChange::CreateTable],
+                alter: AlterTableRequest::new(desired.path.clone()),
+                create: Some(desired),
+                recreate_indexes: Vec::new(),
+            }),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Apply a plan, made by [`Self::plan`].
+    pub async fn apply(&self, plan: &TablePlan) -> YdbResult<()> {
+        if plan.is_destructive() && !self.allow_destructive {
+            let destructive: Vec<String> = plan
+                .changes
+                .iter()
+                .filter(|change| change.is_destructive())
+                .map(|change| format!("{change:?}"))
+                .collect();
+            return Err(YdbError::custom(format!(
+                "plan for '{}' contains destructive changes: {}",
+                plan.path,
+                destructive.join(", ")
+            )));
+        }
+
+        if let Some(create) = &plan.create {
+            return self.table_client.create_table(create.clone()).await;
+        }
+        if plan.is_empty() {
+            return Ok(());
+        }
+
+        self.table_client.alter_table(plan.alter.clone()).await?;
+        if let Some(request) = plan.recreate_indexes_request() {
+            self.table_client.alter_table(request).await?;
+        }
+        Ok(())
+    }
+
+    /// Plan and apply changes, returns applied plan.
+    pub async fn reconcile(&self, desired: CreateTableRequest) -> YdbResult<TablePlan> {
+        let plan = self.plan(desired).await?;
+        self.apply(&plan).await?;
+        Ok(plan)
+    }
+}
+
+pub(crate) fn plan_table(
+    desired: CreateTableRequest,
+    current: &TableDescription,
+) -> YdbResult<TablePlan> {
+    let mut changes = Vec::new();
+    let mut alter = AlterTableRequest::new(desired.path.clone());
+    let mut incompatible = Vec::new();
+
+    if desired.primary_key != current.primary_key {
+        incompatible.push(format!(
+            "primary key ({}) differs from ({})",
+            desired.primary_key.join(", "),
+            current.primary_key.join(", ")
+        ));
+    }
+
+    let current_columns: HashMap<&str, _> = current
+        .columns
+        .iter()
+        .map(|column| (column.name.as_str(), column))
+        .collect();
+    for column in &desired.columns {
+        let Some(existing) = current_columns.get(column.name.as_str()) else {
+            changes.push(TableChange::AddColumn {
+                name: column.name.clone(),
+            });
+            alter.add_columns.push(column.clone());
+            continue;
+        };
+
+        let (desired_type, desired_nullable) = desired_column_type(column)?;
+        let (current_type, current_nullable) = split_optional(&existing.type_name);
+        if desired_type != current_type || desired_nullable != current_nullable {
+            incompatible.push(format!(
+                "column '{}' has type {}, desired {}",
+                column.name,
+                existing.type_name,
+                column_type_name(&desired_type, desired_nullable)
+            ));
+            continue;
+        }
+
+        if family_name(&column.family) != family_name(&existing.family) {
+            changes.push(TableChange::SetColumnFamily {
+                name: column.name.clone(),
+                family: column.family.clone(),
+            });
+            alter.alter_columns.push(column.clone());
+        }
+    }
+    for column in &current.columns {
+        if !desired.columns.iter().any(|c| c.name == column.name) {
+            changes.push(TableChange::DropColumn {
+                name: column.name.clone(),
+            });
+            alter.drop_columns.push(column.name.clone());
+        }
+    }
+
+    if !incompatible.is_empty() {
+        return Err(YdbError::custom(format!(
+            "table '{}' can't be altered to the desired shape: {}",
+            desired.path,
+            incompatible.join("; ")
+        )));
+    }
+
+    let mut recreate_indexes = Vec::new();
+    for index in &desired.indexes {
+        match current.indexes.iter().find(|i| i.name == index.name) {
+            None => {
+                changes.push(TableChange::AddIndex {
+                    name: index.name.clone(),
+                });
+                alter.add_indexes.push(index.clone());
+            }
+            Some(existing) if !same_index(index, existing) => {
+                changes.push(TableChange::RecreateIndex {
+                    name: index.name.clone(),
+                });
+                alter.drop_indexes.push(index.name.clone());
+                recreate_indexes.push(index.clone());
+            }
+            Some(_) => {}
+        }
+    }
+    for index in &current.indexes {
+        if !desired.indexes.iter().any(|i| i.name == index.name) {
+            changes.push(TableChange::DropIndex {
+                name: index.name.clone(),
+            });
+            alter.drop_indexes.push(index.name.clone());
+        }
+    }
+
+    let desired_attributes: BTreeMap<_, _> = desired.attributes.iter().collect();
+    for (key, value) in &desired_attributes {
+        if current.attributes.get(*key) != Some(*value) {
+            changes.push(TableChange::SetAttribute {
+                key: key.to_string(),
+                value: value.to_string(),
+            });
+            alter = alter.alter_attribute(*key, *value);
+        }
+    }
+    let current_attributes: BTreeMap<_, _> = current.attributes.iter().collect();
+    for key in current_attributes.keys() {
+        if !desired.attributes.contains_key(*key) {
+            changes.push(TableChange::DropAttribute {
+                key: key.to_string(),
+            });
+            alter = alter.drop_attribute(*key);
+        }
+    }
+
+    Ok(TablePlan {
+        path: desired.path,
+        changes,
+        create: None,
+        alter,
+        recreate_indexes,
+    })
+}
+
+fn desired_column_type(column: &TableColumn) -> YdbResult<(String, bool)> {
+    let type_name = column.yql_type_name()?;
+    let (base, optional) = split_optional(&type_name);
+    Ok((base, optional || !column.not_null))
+}
+
+fn split_optional(type_name: &str) -> (String, bool) {
+    match type_name
+        .strip_prefix("Optional<")
+        .and_then(|inner| inner.strip_suffix('>'))
+    {
+        Some(inner) => (inner.to_string(), true),
+        None => (type_name.to_string(), false),
+    }
+}
+
+fn column_type_name(base: &str, nullable: bool) -> String {
+    if nullable {
+        format!("Optional<{base}>")
+    } else {
+        base.to_string()
+    }
+}
+
+fn family_name(family: &str) -> &str {
+    if family.is_empty() { "default" } else { family }
+}
+
+fn same_index(desired: &TableIndex, current: &IndexDescription) -> bool {
+    let index_type = |t: IndexType| match t {
+        IndexType::Unspecified => IndexType::Global,
+        t => t,
+    };
+    desired.index_columns == current.index_columns
+        && desired.data_columns == current.data_columns
+        && index_type(desired.index_type) == index_type(current.index_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ColumnDescription, IndexStatus, StoreType, Value};
+
+    fn column(name: &str, type_name: &str) -> ColumnDescription {
+        ColumnDescription {
+            name: name.to_string(),
+            type_name: type_name.to_string(),
+            type_value: Ok(Value::Int64(0)),
+            not_null: !type_name.starts_with("Optional<"),
+            family: String::new(),
+        }
+    }
+
+    fn current() -> TableDescription {
+        TableDescription {
+            columns: vec![
+                column("id", "Int64"),
+                column("name", "Optional<Utf8>"),
+                column("legacy", "Optional<Int64>"),
+            ],
+            primary_key: vec!["id".to_string()],
+            indexes: vec![IndexDescription {
+                name: "by_name".to_string(),
+                index_columns: vec!["name".to_string()],
+                data_columns: vec![],
+                status: IndexStatus::Ready,
+                index_type: IndexType::Global,
+            }],
+            store_type: StoreType::Row,
+            attributes: HashMap::from([("owner".to_string(), "team".to_string())]),
+            column_families: vec![],
+            ttl_settings: None,
+            partitioning_settings: None,
+        }
+    }
+
+    fn desired() -> CreateTableRequest {
+        CreateTableRequest::new("/local/users")
+            .with_column(TableColumn::new("id", Value::Int64(0)))
+            .with_column(TableColumn::new("name", Value::Text(String::new())).with_not_null(false))
+            .with_column(TableColumn::new("legacy", Value::Int64(0)).with_not_null(false))
+            .with_primary_key(["id"])
+            .with_index(TableIndex::new("by_name", ["name"]))
+            .with_attribute("owner", "team")
+    }
+
+    #[test]
+    fn same_shape_gives_empty_plan() -> YdbResult<()> {
+        let plan = plan_table(desired(), &current())?;
+        assert!(plan.is_empty(), "{:?}", plan.changes());
+        assert!(plan.alter_request().is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn additive_changes_are_not_destructive() -> YdbResult<()> {
+        let desired = desired()
+            .with_column(TableColumn::new("email", Value::Text(String::new())).with_not_null(false))
+            .with_index(
+                TableIndex::new("by_email", ["email"]).with_index_type(IndexType::GlobalAsync),
+            )
+            .with_attribute("owner", "other")
+            .with_attribute("purpose", "tests");
+
+        let plan = plan_table(desired, &current())?;
+        assert!(!plan.is_destructive());
+        assert_eq!(
+            plan.changes(),
+            [
+                TableChange::AddColumn {
+                    name: "email".to_string()
+                },
+                TableChange::AddIndex {
+                    name: "by_email".to_string()
+                },
+                TableChange::SetAttribute {
+                    key: "owner".to_string(),
+                    value: "other".to_string()
+                },
+                TableChange::SetAttribute {
+                    key: "purpose".to_string(),
+                    value: "tests".to_string()
+                },
+            ]
+        );
+
+        let alter = plan.alter_request().expect("alter request");
+        assert_eq!(alter.add_columns.len(), 1);
+        assert_eq!(alter.add_indexes.len(), 1);
+        assert_eq!(alter.alter_attributes.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn drops_are_destructive() -> YdbResult<()> {
+        let mut desired = desired();
+        desired.columns.retain(|c| c.name != "legacy");
+        desired.indexes = vec![TableIndex::new("by_name", ["name"]).with_data_columns(["id"])];
+        desired.attributes.clear();
+
+        let plan = plan_table(desired, &current())?;
+        assert!(plan.is_destructive());
+        assert_eq!(
+            plan.changes(),
+            [
+                TableChange::DropColumn {
+                    name: "legacy".to_string()
+                },
+                TableChange::RecreateIndex {
+                    name: "by_name".to_string()
+                },
+                TableChange::DropAttribute {
+                    key: "owner".to_string()
+                },
+            ]
+        );
+
+        let alter = plan.alter_request().expect("alter request");
+        assert_eq!(alter.drop_columns, ["legacy"]);
+        assert_eq!(alter.drop_indexes, ["by_name"]);
+        assert!(alter.add_indexes.is_empty());
+        assert_eq!(plan.recreate_indexes().len(), 1);
+        assert_eq!(
+            plan.recreate_indexes_request().unwrap().add_indexes.len(),
+            1
+        );
+        assert_eq!(alter.alter_attributes.get("owner"), Some(&String::new()));
+        Ok(())
+    }
+
+    #[test]
+    fn type_and_key_changes_are_rejected() {
+        let mut changed_type = desired();
+        changed_type.columns[2] = TableColumn::new("legacy", Value::Text(String::new()));
+        assert!(plan_table(changed_type, &current()).is_err());
+
+        let mut not_null = desired();
+        not_null.columns[1] = TableColumn::new("name", Value::Text(String::new()));
+        assert!(plan_table(not_null, &current()).is_err());
+
+        let changed_key = desired().with_primary_key(["id", "name"]);
+        assert!(plan_table(changed_key, &current()).is_err());
+    }
+}
@@ -5,7 +5,10 @@
 use std::collections::HashMap;
 
 use crate::errors::{YdbError, YdbResult};
-use crate::grpc_wrapper::raw_table_service::create_table::RawCreateTableColumn;
+use crate::grpc_wrapper::raw_table_service::create_table::{
+    RawCreateTableColumn, RawTableIndex, RawTableIndexType,
+};
+use crate::table_service_types::IndexType;
 use crate::types::Value;
 
 /// Column specification for [`CreateTableRequest`] and [`AlterTableRequest`].
@@ -37,6 +40,16 @@ impl TableColumn {
         self
     }
 
+    /// Column type in YQL syntax, as reported by [`crate::ColumnDescription::type_name`].
+    pub(crate) fn yql_type_name(&self) -> YdbResult<String> {
+        let typed: crate::grpc_wrapper::raw_table_service::value::RawTypedValue = self
+            .type_example
+            .clone()
+            .try_into()
+            .map_err(YdbError::from)?;
+        Ok(typed.r#type.yql_type_name())
+    }
+
     pub(crate) fn into_raw(self) -> YdbResult<RawCreateTableColumn> {
         let typed: crate::grpc_wrapper::raw_table_service::value::RawTypedValue =
             self.type_example.try_into().map_err(YdbError::from)?;
@@ -49,12 +62,66 @@ impl TableColumn {
     }
 }
 
+/// Secondary index specification for [`CreateTableRequest`] and [`AlterTableRequest`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TableIndex {
+    pub name: String,
+    pub index_columns: Vec<String>,
+    pub data_columns: Vec<String>,
+    /// [`IndexType::Unspecified`] is sent as [`IndexType::Global`].
+    pub index_type: IndexType,
+}
+
+impl TableIndex {
+    /// Global synchronous index on `index_columns`.
+    pub fn new(
+        name: impl Into<String>,
+        index_columns: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            index_columns: index_columns.into_iter().map(Into::into).collect(),
+            data_columns: Vec::new(),
+            index_type: IndexType::Global,
+        }
+    }
+
+    /// Columns, copied into the index table (covering index).
+    pub fn with_data_columns(
+        mut self,
+        columns: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.data_columns = columns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn with_index_type(mut self, index_type: IndexType) -> Self {
+        self.index_type = index_type;
+        self
+    }
+
+    pub(crate) fn into_raw(self) -> RawTableIndex {
+        let index_type = match self.index_type {
+            IndexType::Unspecified | IndexType::Global => RawTableIndexType::Global,
+            IndexType::GlobalAsync => RawTableIndexType::GlobalAsync,
+            IndexType::GlobalUnique => RawTableIndexType::GlobalUnique,
+        };
+        RawTableIndex {
+            name: self.name,
+            index_columns: self.index_columns,
+            data_columns: self.data_columns,
+            index_type,
+        }
+    }
+}
+
 /// CreateTable RPC request (go-sdk: `Session.CreateTable`).
 #[derive(Clone, Debug, Default)]
 pub struct CreateTableRequest {
     pub path: String,
     pub columns: Vec<TableColumn>,
     pub primary_key: Vec<String>,
+    pub indexes: Vec<TableIndex>,
     pub attributes: HashMap<String, String>,
 }
 
@@ -83,6 +150,7 @@ impl CreateTableRequest {
                 path: self.path,
                 columns,
                 primary_key: self.primary_key,
+                indexes: self.indexes.into_iter().map(TableIndex::into_raw).collect(),
                 attributes: self.attributes,
                 operation_params,
             },
@@ -102,6 +170,11 @@ impl CreateTableRequest {
         self
     }
 
+    pub fn with_index(mut self, index: TableIndex) -> Self {
+        self.indexes.push(index);
+        self
+    }
+
     pub fn with_attribute(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
         self.attributes.insert(key.into(), value.into());
         self
@@ -171,6 +244,8 @@ pub struct AlterTableRequest {
     pub drop_columns: Vec<String>,
     pub alter_columns: Vec<TableColumn>,
     pub alter_attributes: HashMap<String, String>,
+    pub add_indexes: Vec<TableIndex>,
+    pub drop_indexes: Vec<String>,
 }
 
 impl AlterTableRequest {
@@ -204,6 +279,12 @@ impl AlterTableRequest {
                 drop_columns: self.drop_columns,
                 alter_columns,
                 alter_attributes: self.alter_attributes,
+                add_indexes: self
+                    .add_indexes
+                    .into_iter()
+                    .map(TableIndex::into_raw)
+                    .collect(),
+                drop_indexes: self.drop_indexes,
                 operation_params,
             },
         )
@@ -224,6 +305,16 @@ impl AlterTableRequest {
         self
     }
 
+    pub fn add_index(mut self, index: TableIndex) -> Self {
+        self.add_indexes.push(index);
+        self
+    }
+
+    pub fn drop_index(mut self, name: impl Into<String>) -> Self {
+        self.drop_indexes.push(name.into());
+        self
+    }
+
     /// Set or update a table attribute (go-sdk: `options.WithAlterAttribute`).
     ///
     /// To remove an attribute, use [`Self::drop_attribute`] or pass an empty `value`
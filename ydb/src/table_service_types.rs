@@ -64,6 +64,12 @@ pub struct TableDescription {
     pub store_type: StoreType,
     /// User-defined table attributes (key/value, up to 10 KB total).
     pub attributes: std::collections::HashMap<String, String>,
+    /// List of column families
+    pub column_families: Vec<ColumnFamilyDescription>,
+    /// Time to live settings, None if TTL is disabled
+    pub ttl_settings: Option<TtlSettings>,
+    /// Auto partitioning settings
+    pub partitioning_settings: Option<TablePartitioningSettings>,
 }
 
 /// Error description of an unknown/unsupported column type
@@ -77,13 +83,145 @@ pub struct UnknownTypeDescription {
 pub struct ColumnDescription {
     /// Column name
     pub name: String,
+    /// Column type in YQL syntax, for example `Optional<Int64>`
+    pub type_name: String,
     /// Column type, represented as an example Value
     /// Err if the type has not been converted to Value
     pub type_value: Result<crate::Value, UnknownTypeDescription>,
+    /// Column declared as NOT NULL
+    pub not_null: bool,
     /// Column family name
     pub family: String,
 }
 
+/// Description of a table column family
+#[derive(Debug, Clone)]
+pub struct ColumnFamilyDescription {
+    pub name: String,
+    /// Storage pool media kind (for example `ssd`), empty if not set
+    pub data: String,
+    pub compression: ColumnFamilyCompression,
+}
+
+impl From<crate::grpc_wrapper::raw_table_service::describe_table::RawColumnFamily>
+    for ColumnFamilyDescription
+{
+    fn from(raw: crate::grpc_wrapper::raw_table_service::describe_table::RawColumnFamily) -> Self {
+        Self {
+            name: raw.name,
+            data: raw.data_media,
+            compression: raw.compression.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(feature = "force-exhaustive-all"), non_exhaustive)]
+pub enum ColumnFamilyCompression {
+    Unspecified,
+    None,
+    Lz4,
+}
+
+impl From<crate::grpc_wrapper::raw_table_service::describe_table::RawColumnFamilyCompression>
+    for ColumnFamilyCompression
+{
+    fn from(
+        raw: crate::grpc_wrapper::raw_table_service::describe_table::RawColumnFamilyCompression,
+    ) -> Self {
+        use crate::grpc_wrapper::raw_table_service::describe_table::RawColumnFamilyCompression;
+        match raw {
+            RawColumnFamilyCompression::Unspecified => ColumnFamilyCompression::Unspecified,
+            RawColumnFamilyCompression::None => ColumnFamilyCompression::None,
+            RawColumnFamilyCompression::Lz4 => ColumnFamilyCompression::Lz4,
+        }
+    }
+}
+
+/// Table time to live settings: rows expire `expire_after` after the value of `column_name`
+#[derive(Debug, Clone)]
+pub struct TtlSettings {
+    pub column_name: String,
+    pub expire_after: std::time::Duration,
+    pub mode: TtlMode,
+}
+
+impl From<crate::grpc_wrapper::raw_table_service::describe_table::RawTtlSettings> for TtlSettings {
+    fn from(raw: crate::grpc_wrapper::raw_table_service::describe_table::RawTtlSettings) -> Self {
+        Self {
+            column_name: raw.column_name,
+            expire_after: raw.expire_after,
+            mode: match raw.column_unit {
+                None => TtlMode::DateTypeColumn,
+                Some(unit) => TtlMode::ValueSinceUnixEpoch(unit.into()),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(feature = "force-exhaustive-all"), non_exhaustive)]
+pub enum TtlMode {
+    /// Column has a date type (Date, Datetime, Timestamp)
+    DateTypeColumn,
+    /// Column has a numeric type, holding time since unix epoch in given units
+    ValueSinceUnixEpoch(TtlUnit),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(feature = "force-exhaustive-all"), non_exhaustive)]
+pub enum TtlUnit {
+    Unspecified,
+    Seconds,
+    Milliseconds,
+    Microseconds,
+    Nanoseconds,
+}
+
+impl From<crate::grpc_wrapper::raw_table_service::describe_table::RawTtlUnit> for TtlUnit {
+    fn from(raw: crate::grpc_wrapper::raw_table_service::describe_table::RawTtlUnit) -> Self {
+        use crate::grpc_wrapper::raw_table_service::describe_table::RawTtlUnit;
+        match raw {
+            RawTtlUnit::Unspecified => TtlUnit::Unspecified,
+            RawTtlUnit::Seconds => TtlUnit::Seconds,
+            RawTtlUnit::Milliseconds => TtlUnit::Milliseconds,
+            RawTtlUnit::Microseconds => TtlUnit::Microseconds,
+            RawTtlUnit::Nanoseconds => TtlUnit::Nanoseconds,
+        }
+    }
+}
+
+/// Table auto partitioning settings.
+///
+/// `None` in a flag field means the server didn't report the value.
+#[derive(Debug, Clone, Default)]
+pub struct TablePartitioningSettings {
+    /// Columns to partition by (column tables)
+    pub partition_by: Vec<String>,
+    pub partitioning_by_size: Option<bool>,
+    pub partition_size_mb: u64,
+    pub partitioning_by_load: Option<bool>,
+    pub min_partitions_count: u64,
+    pub max_partitions_count: u64,
+}
+
+impl From<crate::grpc_wrapper::raw_table_service::describe_table::RawTablePartitioningSettings>
+    for TablePartitioningSettings
+{
+    fn from(
+        raw: crate::grpc_wrapper::raw_table_service::describe_table::RawTablePartitioningSettings,
+    ) -> Self {
+        Self {
+            partition_by: raw.partition_by,
+            partitioning_by_size: raw.partitioning_by_size,
+            partition_size_mb: raw.partition_size_mb,
+            partitioning_by_load: raw.partitioning_by_load,
+            min_partitions_count: raw.min_partitions_count,
+            max_partitions_count: raw.max_partitions_count,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct IndexDescription {
     pub name: String,
@@ -10,8 +10,8 @@ use crate::client_topic::client::{
 use crate::client_topic::list_types::ConsumerBuilder;
 use crate::test_integration_helper::create_client;
 use crate::{
-    Client, TopicReaderBatch, TopicReaderOptions, TopicWriterMessage, TopicWriterOptions,
-    Transaction, YdbError, YdbResult, YdbResultWithCustomerErr, closure,
+    Client, TopicReaderBatch, TopicReaderOptions, TopicTxProcessor, TopicWriterMessage,
+    TopicWriterOptions, Transaction, YdbError, YdbResult, YdbResultWithCustomerErr, closure,
 };
 
 async fn wait_topic_absent(
@@ -400,3 +400,60 @@ async fn query_topic_reader_tx_rewrap_same_reader_same_tx_commits_all_offsets()
 
     Ok(())
 }
+
+#[tokio::test]
+#[traced_test]
+#[ignore] // need YDB access
+async fn topic_tx_processor_retries_batch_and_commits_offsets() -> YdbResult<()> {
+    let client = create_client().await?;
+    let topic_name = "topic_tx_processor_retries_batch_and_commits_offsets";
+    let consumer_name = "topic-tx-processor-consumer";
+    let producer_id = "topic-tx-processor-producer";
+    let payloads = ["tx-processor-1", "tx-processor-2"];
+
+    let topic_path = create_topic(&client, topic_name, &[consumer_name]).await?;
+    let mut topic_client = client.topic_client();
+    write_messages(&mut topic_client, &topic_path, producer_id, &payloads).await?;
+
+    let reader = topic_client
+        .create_reader(consumer_name.to_string(), topic_path.clone())
+        .await?;
+    let mut processor = TopicTxProcessor::new(reader, client.query_client());
+
+    let mut attempts = 0;
+    let mut observed = Vec::new();
+    while observed.len() < payloads.len() {
+        timeout(
+            Duration::from_secs(30),
+            processor.process_batch(&mut closure!(
+                [&mut attempts, &mut observed],
+                async |(batch, _tx): (TopicReaderBatch, &mut Transaction)| {
+                    *attempts += 1;
+                    if *attempts == 1 {
+                        return Err(YdbError::TransportGRPCStatus(std::sync::Arc::new(
+                            tonic::Status::aborted("planned retryable failure"),
+                        ))
+                        .into());
+                    }
+                    observed.extend(read_payloads_from_batch(batch).await?);
+                    Ok(())
+                }
+            )),
+        )
+        .await
+        .expect("timeout processing topic batch")
+        .map_err(|err| YdbError::Custom(err.to_string()))?;
+    }
+    assert_eq!(observed, expected_payloads(&payloads));
+    assert!(attempts > 1, "failed attempt must be retried");
+
+    wait_committed_offset(
+        &mut topic_client,
+        &topic_path,
+        consumer_name,
+        payloads.len() as i64,
+    )
+    .await?;
+
+    Ok(())
+}
@@ -360,6 +360,101 @@ async fn codec_gzip_fixed() -> YdbResult<()> {
     .await
 }
 
+#[cfg(feature = "zstd")]
+#[tokio::test]
+#[traced_test]
+#[ignore] // need YDB access
+async fn codec_zstd_fixed() -> YdbResult<()> {
+    let messages = (0..20)
+        .map(|i| format!("test-message-{i}").into_bytes())
+        .collect();
+    roundtrip(
+        "codec_zstd_fixed",
+        &[],
+        messages,
+        |topic_path| {
+            TopicWriterOptions::builder()
+                .topic_path(topic_path)
+                .codec_selector(CodecSelection::Fixed(Codec::ZSTD))
+                .build()
+        },
+        |topic, consumer| {
+            TopicReaderOptions::builder()
+                .topic(topic)
+                .consumer(consumer)
+                .build()
+        },
+    )
+    .await
+}
+
+#[cfg(all(feature = "encryption", feature = "zstd"))]
+#[tokio::test]
+#[traced_test]
+#[ignore] // need YDB access
+async fn codec_encrypted_zstd_fixed() -> YdbResult<()> {
+    use crate::{EncryptionDecoder, EncryptionEncoder, EncryptionKey, StaticKeyProvider};
+
+    let keys = Arc::new(StaticKeyProvider::new(EncryptionKey::new("k1", [7; 32])?));
+    let messages = (0..20)
+        .map(|i| format!("test-message-{i}").into_bytes())
+        .collect();
+    roundtrip(
+        "codec_encrypted_zstd_fixed",
+        &[],
+        messages,
+        |topic_path| {
+            TopicWriterOptions::builder()
+                .topic_path(topic_path)
+                .codec_selector(CodecSelection::Fixed(Codec::PAR))
+                .add_encoder(
+                    EncryptionEncoder::new(Codec::PAR, Codec::ZSTD, keys.clone())
+                        .expect("encryption encoder"),
+                )
+                .build()
+        },
+        |topic, consumer| {
+            TopicReaderOptions::builder()
+                .topic(topic)
+                .consumer(consumer)
+                .add_decoder(
+                    EncryptionDecoder::new(Codec::PAR, Codec::ZSTD, keys.clone())
+                        .expect("encryption decoder"),
+                )
+                .build()
+        },
+    )
+    .await
+}
+
+#[cfg(feature = "lzop")]
+#[tokio::test]
+#[traced_test]
+#[ignore] // need YDB access
+async fn codec_lzop_fixed() -> YdbResult<()> {
+    let messages = (0..20)
+        .map(|i| format!("test-message-{i}").into_bytes())
+        .collect();
+    roundtrip(
+        "codec_lzop_fixed",
+        &[],
+        messages,
+        |topic_path| {
+            TopicWriterOptions::builder()
+                .topic_path(topic_path)
+                .codec_selector(CodecSelection::Fixed(Codec::LZOP))
+                .build()
+        },
+        |topic, consumer| {
+            TopicReaderOptions::builder()
+                .topic(topic)
+                .consumer(consumer)
+                .build()
+        },
+    )
+    .await
+}
+
 #[tokio::test]
 #[traced_test]
 #[ignore] // need YDB access
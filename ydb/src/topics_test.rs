@@ -3,17 +3,22 @@ use std::time::{Duration, Instant, SystemTime};
 use tokio::time::timeout;
 use tracing_test::traced_test;
 
+use crate::cdc::{ChangeKind, ChangeRecord, ChangeRecordDecoder, ChangefeedMode};
 use crate::client_topic::client::DescribeConsumerOptionsBuilder;
-use crate::client_topic::list_types::ConsumerBuilder;
+use crate::client_topic::list_types::{
+    AutoPartitioningSettingsBuilder, AutoPartitioningStrategy, ConsumerBuilder, ConsumerResetTarget,
+};
 use crate::grpc_wrapper::runtime_interceptors::InterceptedChannel;
 use crate::test_helpers::CONNECTION_STRING;
 use crate::test_integration_helper::{TcpForwardProxy, create_client};
 use crate::{
-    ClientBuilder, Codec, DescribeTopicOptionsBuilder, PartitioningStrategy, StaticDiscovery,
-    TopicWriterMessage, TopicWriterOptions, YdbError, YdbResult,
+    ClientBuilder, Codec, DeadLetterPolicy, DescribeTopicOptionsBuilder, JsonPayload,
+    KeyedTopicWriterOptions, PartitioningStrategy, StaticDiscovery, TopicConsumerOptions,
+    TopicReaderBatch, TopicReaderOptions, TopicWriterMessage, TopicWriterOptions, TypedTopicReader,
+    TypedTopicWriter, YdbError, YdbResult,
     client_topic::client::{AlterTopicOptionsBuilder, CreateTopicOptionsBuilder},
 };
-use crate::{Transaction, closure};
+use crate::{Transaction, Value, closure};
 use tracing::{debug, info, trace, warn};
 use ydb_grpc::ydb_proto::topic::stream_read_message;
 use ydb_grpc::ydb_proto::topic::stream_read_message::init_request::TopicReadSettings;
@@ -245,6 +250,78 @@ async fn alter_topic_test() -> YdbResult<()> {
     Ok(())
 }
 
+#[tokio::test]
+#[traced_test]
+#[ignore] // need YDB access
+async fn auto_partitioning_settings_test() -> YdbResult<()> {
+    let client = create_client().await?;
+    let topic_path = format!("{}/auto_partitioning_test_topic", client.database());
+    let mut topic_client = client.topic_client();
+
+    let _ = topic_client.drop_topic(topic_path.clone()).await; // ignoring error
+
+    topic_client
+        .create_topic(
+            topic_path.clone(),
+            CreateTopicOptionsBuilder::default()
+                .min_active_partitions(1)
+                .partition_count_limit(10)
+                .auto_partitioning_settings(
+                    AutoPartitioningSettingsBuilder::default()
+                        .strategy(AutoPartitioningStrategy::ScaleUp)
+                        .stabilization_window(std::time::Duration::from_secs(60))
+                        .up_utilization_percent(80)
+                        .build()?,
+                )
+                .build()?,
+        )
+        .await?;
+
+    let description = topic_client
+        .describe_topic(
+            topic_path.clone(),
+            DescribeTopicOptionsBuilder::default().build()?,
+        )
+        .await?;
+    let auto = description
+        .partitioning_settings
+        .auto_partitioning_settings
+        .expect("auto partitioning settings should be described");
+    assert_eq!(auto.strategy, Some(AutoPartitioningStrategy::ScaleUp));
+    assert_eq!(
+        auto.stabilization_window,
+        Some(std::time::Duration::from_secs(60))
+    );
+    assert_eq!(auto.up_utilization_percent, 80);
+
+    topic_client
+        .alter_topic(
+            topic_path.clone(),
+            AlterTopicOptionsBuilder::default()
+                .set_auto_partitioning_strategy(AutoPartitioningStrategy::Paused)
+                .set_auto_partitioning_down_utilization_percent(20)
+                .build()?,
+        )
+        .await?;
+
+    let description = topic_client
+        .describe_topic(
+            topic_path.clone(),
+            DescribeTopicOptionsBuilder::default().build()?,
+        )
+        .await?;
+    let auto = description
+        .partitioning_settings
+        .auto_partitioning_settings
+        .expect("auto partitioning settings should be described");
+    assert_eq!(auto.strategy, Some(AutoPartitioningStrategy::Paused));
+    assert_eq!(auto.up_utilization_percent, 80);
+    assert_eq!(auto.down_utilization_percent, 20);
+
+    topic_client.drop_topic(topic_path).await?;
+    Ok(())
+}
+
 #[tokio::test]
 #[traced_test]
 #[ignore] // need YDB access
@@ -563,6 +640,7 @@ async fn read_topic_message() -> YdbResult<()> {
             TopicWriterMessage::builder()
                 .seq_no(200)
                 .data("test-1".as_bytes().into())
+                .metadata("content-type", "text/plain")
                 .build(),
         )
         .await?;
@@ -598,6 +676,10 @@ async fn read_topic_message() -> YdbResult<()> {
     let mut msg = batch.messages.into_iter().next().unwrap();
     assert_eq!(msg.get_producer_id(), producer_id);
     assert_eq!(msg.seq_no, 200);
+    assert_eq!(
+        msg.metadata_value("content-type"),
+        Some("text/plain".as_bytes())
+    );
     assert_eq!(msg.read_and_take().await?.unwrap(), "test-1".as_bytes());
     // assert_eq!(msg.get_topic_path(), topic_path);
 
@@ -1185,3 +1267,828 @@ async fn topic_writer_reconnects() -> YdbResult<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+#[traced_test]
+#[ignore] // need YDB access
+async fn reset_consumer_test() -> YdbResult<()> {
+    let client = create_client().await?;
+    let topic_path = format!("{}/reset_consumer_test_topic", client.database());
+    let consumer_name = "reset_consumer".to_string();
+    let mut topic_client = client.topic_client();
+
+    let _ = topic_client.drop_topic(topic_path.clone()).await; // ignoring error
+
+    topic_client
+        .create_topic(
+            topic_path.clone(),
+            CreateTopicOptionsBuilder::default()
+                .consumers(vec![
+                    ConsumerBuilder::default()
+                        .name(consumer_name.clone())
+                        .build()?,
+                ])
+                .build()?,
+        )
+        .await?;
+
+    let writer = topic_client.create_writer(topic_path.clone()).await?;
+    for content in ["first", "second"] {
+        writer
+            .write_with_ack(
+                TopicWriterMessage::builder()
+                    .data(content.as_bytes().into())
+                    .build(),
+            )
+            .await?;
+    }
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let timestamp = SystemTime::now();
+    writer
+        .write_with_ack(TopicWriterMessage::builder().data(b"third".into()).build())
+        .await?;
+
+    let offsets = topic_client
+        .reset_consumer(
+            topic_path.clone(),
+            consumer_name.clone(),
+            ConsumerResetTarget::Latest,
+        )
+        .await?;
+    assert_eq!(offsets.get(&0), Some(&3));
+
+    let offsets = topic_client
+        .reset_consumer(
+            topic_path.clone(),
+            consumer_name.clone(),
+            ConsumerResetTarget::Timestamp(timestamp),
+        )
+        .await?;
+    assert_eq!(offsets.get(&0), Some(&2));
+
+    topic_client
+        .commit_offset(topic_path.clone(), 0, consumer_name.clone(), 1)
+        .await?;
+    let description = topic_client
+        .describe_consumer(
+            topic_path.clone(),
+            consumer_name.clone(),
+            DescribeConsumerOptionsBuilder::default()
+                .include_stats(true)
+                .build()?,
+        )
+        .await?;
+    assert_eq!(description.partitions[0].consumer_stats.committed_offset, 1);
+
+    topic_client.drop_topic(topic_path).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+#[ignore] // need YDB access
+async fn keyed_writer_routes_keys_to_partitions() -> YdbResult<()> {
+    use std::collections::HashMap;
+
+    let client = create_client().await?;
+    let topic_path = format!("{}/keyed_writer_test_topic", client.database());
+    let consumer_name = "test-consumer".to_string();
+    let mut topic_client = client.topic_client();
+
+    let _ = topic_client.drop_topic(topic_path.clone()).await; // ignoring error
+
+    topic_client
+        .create_topic(
+            topic_path.clone(),
+            CreateTopicOptionsBuilder::default()
+                .min_active_partitions(3)
+                .consumers(vec![
+                    ConsumerBuilder::default()
+                        .name(consumer_name.clone())
+                        .build()?,
+                ])
+                .build()?,
+        )
+        .await?;
+
+    let writer = topic_client
+        .create_keyed_writer(
+            KeyedTopicWriterOptions::builder()
+                .writer_options(
+                    TopicWriterOptions::builder()
+                        .topic_path(topic_path.clone())
+                        .build(),
+                )
+                .build(),
+        )
+        .await?;
+
+    let keys = ["user-1", "user-2", "user-3", "user-4", "user-5"];
+    let mut expected: HashMap<String, i64> = HashMap::new();
+    for key in keys {
+        expected.insert(key.to_string(), writer.partition_for_key(key).await?);
+    }
+    for i in 0..3 {
+        for key in keys {
+            writer
+                .write(
+                    key,
+                    TopicWriterMessage::builder()
+                        .data(format!("{key}:{i}").into_bytes())
+                        .build(),
+                )
+                .await?;
+        }
+    }
+    writer.stop().await?;
+
+    let mut reader = topic_client
+        .create_reader(consumer_name.clone(), topic_path.clone())
+        .await?;
+
+    let mut received: HashMap<String, Vec<(i64, String)>> = HashMap::new();
+    let mut count = 0;
+    while count < keys.len() * 3 {
+        let batch = timeout(Duration::from_secs(30), reader.read_batch())
+            .await
+            .map_err(|_| YdbError::custom("timeout waiting for keyed messages"))??;
+        for mut msg in batch.messages {
+            let partition_id = msg.get_partition_id();
+            let text = String::from_utf8(msg.read_and_take().await?.unwrap()).unwrap();
+            let (key, index) = text.split_once(':').unwrap();
+            received
+                .entry(key.to_string())
+                .or_default()
+                .push((partition_id, index.to_string()));
+            count += 1;
+        }
+    }
+
+    for key in keys {
+        let messages = &received[key];
+        assert!(
+            messages
+                .iter()
+                .all(|(partition_id, _)| *partition_id == expected[key]),
+            "all messages of {key} must be in partition {}: {messages:?}",
+            expected[key]
+        );
+        let order: Vec<&str> = messages.iter().map(|(_, index)| index.as_str()).collect();
+        assert_eq!(order, vec!["0", "1", "2"]);
+    }
+
+    topic_client.drop_topic(topic_path).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+#[ignore] // need YDB access
+async fn writer_sink_and_reader_stream() -> YdbResult<()> {
+    use futures_util::{SinkExt, TryStreamExt};
+
+    let client = create_client().await?;
+    let topic_path = format!("{}/writer_sink_reader_stream_topic", client.database());
+    let consumer_name = "test-consumer".to_string();
+    let mut topic_client = client.topic_client();
+
+    let _ = topic_client.drop_topic(topic_path.clone()).await; // ignoring error
+
+    topic_client
+        .create_topic(
+            topic_path.clone(),
+            CreateTopicOptionsBuilder::default()
+                .consumers(vec![
+                    ConsumerBuilder::default()
+                        .name(consumer_name.clone())
+                        .build()?,
+                ])
+                .build()?,
+        )
+        .await?;
+
+    let mut writer = topic_client.create_writer(topic_path.clone()).await?;
+    futures_util::stream::iter(0..10)
+        .map(|i| {
+            Ok(TopicWriterMessage::builder()
+                .data(format!("message-{i}").into_bytes())
+                .build())
+        })
+        .forward(&mut writer)
+        .await?;
+    writer
+        .send(TopicWriterMessage::builder().data(b"last".into()).build())
+        .await?;
+    writer.stop().await?;
+
+    let mut reader = topic_client
+        .create_reader(consumer_name.clone(), topic_path.clone())
+        .await?;
+
+    let mut received = Vec::new();
+    while received.len() < 11 {
+        let mut message = timeout(Duration::from_secs(30), reader.try_next())
+            .await
+            .map_err(|_| YdbError::custom("timeout waiting for messages"))??
+            .expect("reader stream must not end");
+        received.push(String::from_utf8(message.read_and_take().await?.unwrap()).unwrap());
+    }
+
+    let mut expected: Vec<String> = (0..10).map(|i| format!("message-{i}")).collect();
+    expected.push("last".to_string());
+    assert_eq!(received, expected);
+
+    topic_client.drop_topic(topic_path).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+#[ignore] // need YDB access
+async fn read_batch_with_limits_and_close_flushes_commits() -> YdbResult<()> {
+    let client = create_client().await?;
+    let topic_path = format!("{}/read_batch_with_close_topic", client.database());
+    let consumer_name = "test-consumer".to_string();
+    let mut topic_client = client.topic_client();
+
+    let _ = topic_client.drop_topic(topic_path.clone()).await; // ignoring error
+
+    topic_client
+        .create_topic(
+            topic_path.clone(),
+            CreateTopicOptionsBuilder::default()
+                .consumers(vec![
+                    ConsumerBuilder::default()
+                        .name(consumer_name.clone())
+                        .build()?,
+                ])
+                .build()?,
+        )
+        .await?;
+
+    let writer = topic_client.create_writer(topic_path.clone()).await?;
+    for i in 0..10 {
+        writer
+            .write(
+                TopicWriterMessage::builder()
+                    .data(format!("message-{i}").into_bytes())
+                    .build(),
+            )
+            .await?;
+    }
+    writer.stop().await?;
+
+    let mut reader = topic_client
+        .create_reader(consumer_name.clone(), topic_path.clone())
+        .await?;
+
+    let mut read = 0;
+    while read < 10 {
+        let batch = reader
+            .read_batch_with(3, 1024, Duration::from_secs(30))
+            .await?
+            .ok_or_else(|| YdbError::custom("timeout waiting for messages"))?;
+        assert!(batch.messages.len() <= 3);
+        read += batch.messages.len();
+        reader.commit(batch.get_commit_marker())?;
+    }
+    reader.close(true).await?;
+
+    let mut reader = topic_client
+        .create_reader(consumer_name.clone(), topic_path.clone())
+        .await?;
+    assert!(
+        reader
+            .read_batch_with(10, usize::MAX, Duration::from_secs(3))
+            .await?
+            .is_none()
+    );
+
+    topic_client.drop_topic(topic_path).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+#[ignore] // need YDB access
+async fn read_partition_range_without_consumer() -> YdbResult<()> {
+    use futures_util::TryStreamExt;
+
+    let client = create_client().await?;
+    let topic_path = format!("{}/read_partition_range_topic", client.database());
+    let mut topic_client = client.topic_client();
+
+    let _ = topic_client.drop_topic(topic_path.clone()).await; // ignoring error
+
+    topic_client
+        .create_topic(
+            topic_path.clone(),
+            CreateTopicOptionsBuilder::default().build()?,
+        )
+        .await?;
+
+    let writer = topic_client.create_writer(topic_path.clone()).await?;
+    for i in 0..10 {
+        writer
+            .write(
+                TopicWriterMessage::builder()
+                    .data(format!("message-{i}").into_bytes())
+                    .build(),
+            )
+            .await?;
+    }
+    writer.stop().await?;
+
+    let messages: Vec<_> = timeout(
+        Duration::from_secs(30),
+        topic_client
+            .read_partition_range(topic_path.clone(), 0, 4)
+            .await?
+            .try_collect(),
+    )
+    .await
+    .map_err(|_| YdbError::custom("timeout waiting for partition range"))??;
+    let offsets: Vec<i64> = messages.iter().map(|message| message.offset).collect();
+    assert_eq!(offsets, (4..10).collect::<Vec<_>>());
+
+    let future = SystemTime::now() + Duration::from_secs(3600);
+    let messages: Vec<_> = topic_client
+        .read_partition_range(topic_path.clone(), 0, future)
+        .await?
+        .try_collect()
+        .await?;
+    assert!(messages.is_empty());
+
+    topic_client.drop_topic(topic_path).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+#[ignore] // need YDB access
+async fn topic_consumer_handles_and_commits_batches() -> YdbResult<()> {
+    use std::sync::{Arc, Mutex};
+    use tokio_util::sync::CancellationToken;
+
+    let client = create_client().await?;
+    let topic_path = format!("{}/topic_consumer_topic", client.database());
+    let consumer_name = "test-consumer".to_string();
+    let mut topic_client = client.topic_client();
+
+    let _ = topic_client.drop_topic(topic_path.clone()).await; // ignoring error
+
+    topic_client
+        .create_topic(
+            topic_path.clone(),
+            CreateTopicOptionsBuilder::default()
+                .min_active_partitions(2)
+                .consumers(vec![
+                    ConsumerBuilder::default()
+                        .name(consumer_name.clone())
+                        .build()?,
+                ])
+                .build()?,
+        )
+        .await?;
+
+    let writer = topic_client.create_writer(topic_path.clone()).await?;
+    for i in 0..20 {
+        writer
+            .write(
+                TopicWriterMessage::builder()
+                    .data(format!("message-{i}").into_bytes())
+                    .build(),
+            )
+            .await?;
+    }
+    writer.stop().await?;
+
+    let consumer = topic_client
+        .create_consumer(
+            TopicConsumerOptions::builder()
+                .reader_options(
+                    TopicReaderOptions::builder()
+                        .consumer(consumer_name.clone())
+                        .topic(topic_path.clone())
+                        .build(),
+                )
+                .commit_interval(Duration::from_millis(100))
+                .build(),
+        )
+        .await?;
+
+    let handled = Arc::new(Mutex::new(0usize));
+    let all_handled = CancellationToken::new();
+    let run = tokio::spawn(consumer.run(
+        {
+            let handled = handled.clone();
+            let all_handled = all_handled.clone();
+            move |batch, _revoked| {
+                let handled = handled.clone();
+                let all_handled = all_handled.clone();
+                async move {
+                    let mut handled = handled.lock().unwrap();
+                    *handled += batch.messages.len();
+                    if *handled == 20 {
+                        all_handled.cancel();
+                    }
+                    Ok(())
+                }
+            }
+        },
+        all_handled.clone(),
+    ));
+
+    timeout(Duration::from_secs(30), run)
+        .await
+        .map_err(|_| YdbError::custom("timeout waiting for consumer"))?
+        .expect("consumer task must not panic")?;
+    assert_eq!(*handled.lock().unwrap(), 20);
+
+    let mut reader = topic_client
+        .create_reader(consumer_name.clone(), topic_path.clone())
+        .await?;
+    assert!(
+        reader
+            .read_batch_with(10, usize::MAX, Duration::from_secs(3))
+            .await?
+            .is_none()
+    );
+
+    topic_client.drop_topic(topic_path).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+#[ignore] // need YDB access
+async fn topic_consumer_dead_letters_failed_messages() -> YdbResult<()> {
+    use tokio_util::sync::CancellationToken;
+
+    let client = create_client().await?;
+    let topic_path = format!("{}/topic_consumer_dlq_source", client.database());
+    let dead_letter_path = format!("{}/topic_consumer_dlq", client.database());
+    let consumer_name = "test-consumer".to_string();
+    let mut topic_client = client.topic_client();
+
+    for path in [&topic_path, &dead_letter_path] {
+        let _ = topic_client.drop_topic(path.clone()).await; // ignoring error
+        topic_client
+            .create_topic(
+                path.clone(),
+                CreateTopicOptionsBuilder::default()
+                    .consumers(vec![
+                        ConsumerBuilder::default()
+                            .name(consumer_name.clone())
+                            .build()?,
+                    ])
+                    .build()?,
+            )
+            .await?;
+    }
+
+    let writer = topic_client.create_writer(topic_path.clone()).await?;
+    for payload in ["ok-1", "poison", "ok-2"] {
+        writer
+            .write(TopicWriterMessage::builder().data(payload.into()).build())
+            .await?;
+    }
+    writer.stop().await?;
+
+    let consumer = topic_client
+        .create_consumer(
+            TopicConsumerOptions::builder()
+                .reader_options(
+                    TopicReaderOptions::builder()
+                        .consumer(consumer_name.clone())
+                        .topic(topic_path.clone())
+                        .batch_size(1)
+                        .build(),
+                )
+                .commit_interval(Duration::from_millis(100))
+                .dead_letter_policy(
+                    DeadLetterPolicy::builder()
+                        .writer_options(
+                            TopicWriterOptions::builder()
+                                .topic_path(dead_letter_path.clone())
+                                .build(),
+                        )
+                        .max_attempts(2)
+                        .backoff(Duration::from_millis(10))
+                        .build(),
+                )
+                .build(),
+        )
+        .await?;
+
+    let last_handled = CancellationToken::new();
+    let run = tokio::spawn(consumer.run(
+        {
+            let last_handled = last_handled.clone();
+            move |mut batch: TopicReaderBatch, _revoked| {
+                let last_handled = last_handled.clone();
+                async move {
+                    let data = batch.messages[0].read_and_take().await?.unwrap_or_default();
+                    match data.as_slice() {
+                        b"poison" => Err(YdbError::custom("poison message")),
+                        b"ok-2" => {
+                            last_handled.cancel();
+                            Ok(())
+                        }
+                        _ => Ok(()),
+                    }
+                }
+            }
+        },
+        last_handled.clone(),
+    ));
+
+    timeout(Duration::from_secs(30), run)
+        .await
+        .map_err(|_| YdbError::custom("timeout waiting for consumer"))?
+        .expect("consumer task must not panic")?;
+
+    let mut dead_letter_reader = topic_client
+        .create_reader(consumer_name.clone(), dead_letter_path.clone())
+        .await?;
+    let mut dead_letters = timeout(Duration::from_secs(10), dead_letter_reader.read_batch())
+        .await
+        .map_err(|_| YdbError::custom("timeout waiting for dead letter"))??;
+    assert_eq!(dead_letters.messages.len(), 1);
+    let dead_letter = &mut dead_letters.messages[0];
+    assert_eq!(
+        dead_letter.metadata_value(DeadLetterPolicy::METADATA_SOURCE_TOPIC),
+        Some(topic_path.as_bytes())
+    );
+    assert_eq!(
+        dead_letter.metadata_value(DeadLetterPolicy::METADATA_SOURCE_OFFSET),
+        Some(b"1".as_slice())
+    );
+    assert_eq!(
+        dead_letter.metadata_value(DeadLetterPolicy::METADATA_ATTEMPTS),
+        Some(b"2".as_slice())
+    );
+    assert_eq!(dead_letter.read_and_take().await?, Some(b"poison".to_vec()));
+
+    let mut reader = topic_client
+        .create_reader(consumer_name.clone(), topic_path.clone())
+        .await?;
+    assert!(
+        reader
+            .read_batch_with(10, usize::MAX, Duration::from_secs(3))
+            .await?
+            .is_none(),
+        "dead-lettered message offset must be committed"
+    );
+
+    topic_client.drop_topic(topic_path).await?;
+    topic_client.drop_topic(dead_letter_path).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+#[ignore] // need YDB access
+async fn large_messages_are_chunked_and_reassembled() -> YdbResult<()> {
+    let client = create_client().await?;
+    let topic_path = format!("{}/chunked_messages_topic", client.database());
+    let consumer_name = "test-consumer".to_string();
+    let mut topic_client = client.topic_client();
+
+    let _ = topic_client.drop_topic(topic_path.clone()).await; // ignoring error
+    topic_client
+        .create_topic(
+            topic_path.clone(),
+            CreateTopicOptionsBuilder::default()
+                .consumers(vec![
+                    ConsumerBuilder::default()
+                        .name(consumer_name.clone())
+                        .build()?,
+                ])
+                .build()?,
+        )
+        .await?;
+
+    let large: Vec<u8> = (0..10_000u32).map(|i| i as u8).collect();
+    let writer = topic_client
+        .create_writer_with_params(
+            TopicWriterOptions::builder()
+                .topic_path(topic_path.clone())
+                .chunk_size(1024)
+                .build(),
+        )
+        .await?;
+    writer
+        .write(
+            TopicWriterMessage::builder()
+                .metadata("trace-id", "abc")
+                .data(large.clone())
+                .build(),
+        )
+        .await?;
+    writer
+        .write(
+            TopicWriterMessage::builder()
+                .data(b"small".to_vec())
+                .build(),
+        )
+        .await?;
+    writer.stop().await?;
+
+    let mut reader = topic_client
+        .create_reader_with_params(
+            TopicReaderOptions::builder()
+                .consumer(consumer_name.clone())
+                .topic(topic_path.clone())
+                .reassemble_chunks(true)
+                .build(),
+        )
+        .await?;
+
+    let mut messages = Vec::new();
+    while messages.len() < 2 {
+        let batch = timeout(Duration::from_secs(10), reader.read_batch())
+            .await
+            .map_err(|_| YdbError::custom("timeout waiting for messages"))??;
+        messages.extend(batch.messages);
+    }
+    assert_eq!(
+        messages[0].metadata_value("trace-id"),
+        Some(b"abc".as_slice())
+    );
+    assert_eq!(messages[0].read_and_take().await?, Some(large));
+    assert_eq!(messages[0].offset, 9);
+    assert_eq!(messages[1].read_and_take().await?, Some(b"small".to_vec()));
+
+    reader.commit(messages[1].get_commit_marker())?;
+    topic_client.drop_topic(topic_path).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+#[ignore] // need YDB access
+async fn typed_json_messages_roundtrip() -> YdbResult<()> {
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Event {
+        id: u64,
+        name: String,
+    }
+
+    let client = create_client().await?;
+    let topic_path = format!("{}/typed_messages_topic", client.database());
+    let consumer_name = "test-consumer".to_string();
+    let mut topic_client = client.topic_client();
+
+    let _ = topic_client.drop_topic(topic_path.clone()).await; // ignoring error
+    topic_client
+        .create_topic(
+            topic_path.clone(),
+            CreateTopicOptionsBuilder::default()
+                .consumers(vec![
+                    ConsumerBuilder::default()
+                        .name(consumer_name.clone())
+                        .build()?,
+                ])
+                .build()?,
+        )
+        .await?;
+
+    let event = Event {
+        id: 1,
+        name: "created".to_string(),
+    };
+    let writer = TypedTopicWriter::new(
+        topic_client.create_writer(topic_path.clone()).await?,
+        JsonPayload,
+        2,
+    );
+    writer.write(&event).await?;
+    writer.stop().await?;
+
+    let reader_options = || {
+        TopicReaderOptions::builder()
+            .consumer(consumer_name.clone())
+            .topic(topic_path.clone())
+            .build()
+    };
+
+    let mut old_reader = TypedTopicReader::<Event, _>::new(
+        topic_client
+            .create_reader_with_params(reader_options())
+            .await?,
+        JsonPayload,
+        [1],
+    );
+    let batch = timeout(Duration::from_secs(10), old_reader.read_batch())
+        .await
+        .map_err(|_| YdbError::custom("timeout waiting for messages"))??;
+    assert_eq!(batch.messages.len(), 1);
+    let rejected = batch.messages[0].as_ref().unwrap_err();
+    assert!(matches!(rejected.error, YdbError::Convert(_)));
+    assert!(rejected.message().raw_data.is_some());
+    drop(old_reader);
+
+    let mut reader = TypedTopicReader::<Event, _>::new(
+        topic_client
+            .create_reader_with_params(reader_options())
+            .await?,
+        JsonPayload,
+        [1, 2],
+    );
+    let batch = timeout(Duration::from_secs(10), reader.read_batch())
+        .await
+        .map_err(|_| YdbError::custom("timeout waiting for messages"))??;
+    assert_eq!(batch.messages.len(), 1);
+    let message = batch.messages[0].as_ref().unwrap();
+    assert_eq!(message.schema_version, 2);
+    assert_eq!(message.value, event);
+    reader.commit(batch.get_commit_marker())?;
+
+    topic_client.drop_topic(topic_path).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+#[ignore] // need YDB access
+async fn changefeed_records_are_typed() -> YdbResult<()> {
+    let client = create_client().await?;
+    let table_path = format!("{}/cdc_typed_records_table", client.database());
+    let consumer_name = "test-consumer".to_string();
+    let mut query_client = client.query_client();
+
+    let _ = query_client
+        .exec(format!("DROP TABLE IF EXISTS `{table_path}`"))
+        .await; // ignoring error
+    query_client
+        .exec(format!(
+            "CREATE TABLE `{table_path}` (id Uint64 NOT NULL, name Utf8, PRIMARY KEY (id))"
+        ))
+        .await?;
+    query_client
+        .exec(format!(
+            "ALTER TABLE `{table_path}` ADD CHANGEFEED updates \
+             WITH (FORMAT = 'JSON', MODE = 'NEW_AND_OLD_IMAGES')"
+        ))
+        .await?;
+    query_client
+        .exec(format!(
+            "ALTER TOPIC `{table_path}/updates` ADD CONSUMER `{consumer_name}`"
+        ))
+        .await?;
+
+    for query in [
+        format!("UPSERT INTO `{table_path}` (id, name) VALUES (1, 'a')"),
+        format!("UPSERT INTO `{table_path}` (id, name) VALUES (1, 'b')"),
+        format!("DELETE FROM `{table_path}` WHERE id = 1"),
+    ] {
+        query_client.exec(query).await?;
+    }
+
+    let table = client
+        .table_client()
+        .describe_table(table_path.clone())
+        .await?;
+    let decoder = ChangeRecordDecoder::new(&table, ChangefeedMode::NewAndOldImages)?;
+    let mut reader = client
+        .topic_client()
+        .create_reader_with_params(
+            TopicReaderOptions::builder()
+                .consumer(consumer_name)
+                .topic(format!("{table_path}/updates"))
+                .build(),
+        )
+        .await?;
+
+    let mut changes = Vec::new();
+    while changes.len() < 3 {
+        let mut batch = timeout(Duration::from_secs(10), reader.read_batch())
+            .await
+            .map_err(|_| YdbError::custom("timeout waiting for changes"))??;
+        for message in batch.messages.iter_mut() {
+            if let ChangeRecord::Row(change) = decoder.decode_message(message).await? {
+                changes.push(change);
+            }
+        }
+        reader.commit(batch.get_commit_marker())?;
+    }
+
+    let kinds: Vec<_> = changes.iter().map(|change| change.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![ChangeKind::Insert, ChangeKind::Update, ChangeKind::Delete]
+    );
+    assert_eq!(changes[1].key, vec![("id".to_string(), Value::Uint64(1))]);
+    assert_eq!(
+        changes[1].new_image.as_ref().unwrap()["name"]
+            .clone()
+            .to_option(),
+        Some(Value::Text("b".to_string()))
+    );
+
+    query_client
+        .exec(format!("DROP TABLE `{table_path}`"))
+        .await?;
+    Ok(())
+}
@@ -7,7 +7,8 @@ use ydb_grpc::ydb_proto::discovery::{ListEndpointsResponse, WhoAmIResponse};
 use ydb_grpc::ydb_proto::issue::IssueMessage;
 use ydb_grpc::ydb_proto::operations::Operation as YdbOperation;
 use ydb_grpc::ydb_proto::scheme::{
-    DescribePathResponse, ListDirectoryResponse, MakeDirectoryResponse, RemoveDirectoryResponse,
+    DescribePathResponse, ListDirectoryResponse, MakeDirectoryResponse, ModifyPermissionsResponse,
+    RemoveDirectoryResponse,
 };
 use ydb_grpc::ydb_proto::status_ids::StatusCode;
 use ydb_grpc::ydb_proto::table::{
@@ -19,8 +20,8 @@ use ydb_grpc::ydb_proto::table::{
     RollbackTransactionResponse,
 };
 use ydb_grpc::ydb_proto::topic::{
-    AlterTopicResponse, CreateTopicResponse, DescribeConsumerResponse, DescribeTopicResponse,
-    DropTopicResponse, UpdateOffsetsInTransactionResponse,
+    AlterTopicResponse, CommitOffsetResponse, CreateTopicResponse, DescribeConsumerResponse,
+    DescribeTopicResponse, DropTopicResponse, UpdateOffsetsInTransactionResponse,
 };
 
 use crate::grpc_wrapper::raw_errors::{RawError, RawResult};
@@ -119,6 +120,7 @@ operation_impl_for!(MakeDirectoryResponse);
 operation_impl_for!(ListDirectoryResponse);
 operation_impl_for!(DescribePathResponse);
 operation_impl_for!(RemoveDirectoryResponse);
+operation_impl_for!(ModifyPermissionsResponse);
 operation_impl_for!(AlterTopicResponse);
 operation_impl_for!(CreateTopicResponse);
 operation_impl_for!(DescribeTopicResponse);
@@ -134,6 +136,7 @@ operation_impl_for!(DescribeTableResponse);
 operation_impl_for!(LoginResponse);
 operation_impl_for!(DescribeConsumerResponse);
 operation_impl_for!(UpdateOffsetsInTransactionResponse);
+operation_impl_for!(CommitOffsetResponse);
 operation_impl_for!(BulkUpsertResponse);
 operation_impl_for!(CreateTableResponse);
 operation_impl_for!(DropTableResponse);